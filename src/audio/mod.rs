@@ -0,0 +1,17 @@
+//! Audio encoding for synthesised speech.
+//!
+//! [`crate::dectalk`] produces raw audio in one of its
+//! [`crate::dectalk::WaveFormat`]s; the modules here package it into
+//! something Discord can play.
+//!
+//! | Module | Purpose |
+//! |---|---|
+//! | [`ogg`] | Muxes Opus packets into an Ogg file |
+//! | [`opus`] | Encodes speech as Ogg Opus for voice clips (encoder with the `opus` feature) |
+//! | [`resample`] | Converts DECtalk's 11.025 kHz mono to Discord voice's 48 kHz stereo |
+//! | [`wav`] | Encodes PCM or µ-law audio as a RIFF/WAVE file in memory |
+
+pub mod ogg;
+pub mod opus;
+pub mod resample;
+pub mod wav;
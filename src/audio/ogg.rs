@@ -0,0 +1,278 @@
+//! Minimal Ogg muxer for a single Opus stream (RFC 7845).
+//!
+//! [`opus_file`] writes the two header pages — `OpusHead`, then `OpusTags`
+//! — and packs the encoded packets into pages after them. Packets never
+//! span pages, so every audio page ends on a whole packet and carries a
+//! granule position: the 48 kHz sample count decoded by its end.
+
+/// Stream serial number; a file only ever holds the one stream.
+const SERIAL: u32 = 0x5041_5754;
+
+/// Most lacing values one page's segment table holds.
+const MAX_SEGMENTS: usize = 255;
+
+/// Page header flags.
+const BEGINNING_OF_STREAM: u8 = 0x02;
+const END_OF_STREAM: u8 = 0x04;
+
+/// Written as the `OpusTags` vendor string.
+const VENDOR: &str = "logosV3";
+
+/// The fields of an `OpusHead` packet for a mono or stereo stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpusHead {
+    pub channels: u8,
+
+    /// 48 kHz samples the decoder drops from the start: the encoder's
+    /// lookahead.
+    pub pre_skip: u16,
+
+    /// Sample rate of the audio before it was encoded, for information.
+    pub input_sample_rate: u32,
+}
+
+impl OpusHead {
+    fn packet(self) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(19);
+        packet.extend_from_slice(b"OpusHead");
+        packet.push(1); // version
+        packet.push(self.channels);
+        packet.extend_from_slice(&self.pre_skip.to_le_bytes());
+        packet.extend_from_slice(&self.input_sample_rate.to_le_bytes());
+        packet.extend_from_slice(&0i16.to_le_bytes()); // output gain
+        packet.push(0); // channel mapping family: mono or stereo
+        packet
+    }
+}
+
+/// The `OpusTags` packet: vendor string and no comments.
+fn tags_packet() -> Vec<u8> {
+    let mut packet = Vec::with_capacity(16 + VENDOR.len());
+    packet.extend_from_slice(b"OpusTags");
+    packet.extend_from_slice(&(VENDOR.len() as u32).to_le_bytes());
+    packet.extend_from_slice(VENDOR.as_bytes());
+    packet.extend_from_slice(&0u32.to_le_bytes());
+    packet
+}
+
+/// An Ogg Opus file of `packets`, each `frame_samples` long at 48 kHz,
+/// holding `length` samples of audio after `head.pre_skip`.
+///
+/// The last page's granule position ends the stream at `length`, so the
+/// decoder drops the silence padding out the final frame.
+pub fn opus_file(head: OpusHead, packets: &[Vec<u8>], frame_samples: u64, length: u64) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.page(&[head.packet()], BEGINNING_OF_STREAM, 0);
+    writer.page(&[tags_packet()], 0, 0);
+
+    let end = u64::from(head.pre_skip) + length;
+    let mut decoded = 0;
+    let mut rest = packets;
+    while !rest.is_empty() {
+        let mut count = 0;
+        let mut segments = 0;
+        for packet in rest {
+            let lacing = packet.len() / 255 + 1;
+            if segments + lacing > MAX_SEGMENTS {
+                break;
+            }
+            segments += lacing;
+            count += 1;
+        }
+        let (page, after) = rest.split_at(count.max(1));
+        decoded += frame_samples * page.len() as u64;
+        let (flags, granule) = if after.is_empty() {
+            (END_OF_STREAM, end.min(decoded))
+        } else {
+            (0, decoded)
+        };
+        writer.page(page, flags, granule);
+        rest = after;
+    }
+    writer.out
+}
+
+/// Pages written so far, and the next page's sequence number.
+#[derive(Default)]
+struct Writer {
+    out: Vec<u8>,
+    sequence: u32,
+}
+
+impl Writer {
+    /// Append a page holding all of `packets`.
+    fn page(&mut self, packets: &[Vec<u8>], flags: u8, granule: u64) {
+        let start = self.out.len();
+        let lacing: Vec<u8> = packets
+            .iter()
+            .flat_map(|packet| {
+                let full = packet.len() / 255;
+                std::iter::repeat_n(255, full).chain([(packet.len() % 255) as u8])
+            })
+            .collect();
+
+        self.out.extend_from_slice(b"OggS");
+        self.out.push(0); // version
+        self.out.push(flags);
+        self.out.extend_from_slice(&granule.to_le_bytes());
+        self.out.extend_from_slice(&SERIAL.to_le_bytes());
+        self.out.extend_from_slice(&self.sequence.to_le_bytes());
+        self.out.extend_from_slice(&0u32.to_le_bytes()); // CRC, filled in below
+        self.out.push(lacing.len() as u8);
+        self.out.extend_from_slice(&lacing);
+        for packet in packets {
+            self.out.extend_from_slice(packet);
+        }
+
+        let checksum = crc(&self.out[start..]);
+        self.out[start + 22..start + 26].copy_from_slice(&checksum.to_le_bytes());
+        self.sequence += 1;
+    }
+}
+
+/// Ogg's page checksum: CRC-32 with polynomial `0x04C11DB7`, not reflected,
+/// starting from zero.
+fn crc(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ (u32::from(byte) << 24), |crc, _| {
+            if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One page read back, its checksum verified.
+    struct Page {
+        flags: u8,
+        granule: u64,
+        serial: u32,
+        sequence: u32,
+        packets: Vec<Vec<u8>>,
+    }
+
+    fn parse(mut file: &[u8]) -> Vec<Page> {
+        let mut pages = Vec::new();
+        while !file.is_empty() {
+            assert_eq!(&file[..4], b"OggS");
+            assert_eq!(file[4], 0);
+            let segments = usize::from(file[26]);
+            let lacing = &file[27..27 + segments];
+            let body_len: usize = lacing.iter().map(|&l| usize::from(l)).sum();
+            let len = 27 + segments + body_len;
+
+            let mut zeroed = file[..len].to_vec();
+            zeroed[22..26].fill(0);
+            let stored = u32::from_le_bytes(file[22..26].try_into().unwrap());
+            assert_eq!(crc(&zeroed), stored);
+
+            let mut packets = Vec::new();
+            let mut packet = Vec::new();
+            let mut body = &file[27 + segments..len];
+            for &l in lacing {
+                packet.extend_from_slice(&body[..usize::from(l)]);
+                body = &body[usize::from(l)..];
+                if l < 255 {
+                    packets.push(std::mem::take(&mut packet));
+                }
+            }
+            assert!(packet.is_empty(), "packet continued past its page");
+
+            pages.push(Page {
+                flags: file[5],
+                granule: u64::from_le_bytes(file[6..14].try_into().unwrap()),
+                serial: u32::from_le_bytes(file[14..18].try_into().unwrap()),
+                sequence: u32::from_le_bytes(file[18..22].try_into().unwrap()),
+                packets,
+            });
+            file = &file[len..];
+        }
+        pages
+    }
+
+    const HEAD: OpusHead = OpusHead {
+        channels: 1,
+        pre_skip: 312,
+        input_sample_rate: 11_025,
+    };
+
+    #[test]
+    fn crc_matches_ogg() {
+        assert_eq!(crc(b""), 0);
+        assert_eq!(crc(b"123456789"), 0x89A1_897F);
+    }
+
+    #[test]
+    fn headers_round_trip() {
+        let file = opus_file(HEAD, &[vec![0xAA; 3]], 960, 500);
+        let pages = parse(&file);
+        assert_eq!(pages.len(), 3);
+
+        let head = &pages[0];
+        assert_eq!(head.flags, BEGINNING_OF_STREAM);
+        assert_eq!(head.granule, 0);
+        assert_eq!(head.packets.len(), 1);
+        let packet = &head.packets[0];
+        assert_eq!(packet.len(), 19);
+        assert_eq!(&packet[..8], b"OpusHead");
+        assert_eq!(packet[8], 1);
+        assert_eq!(packet[9], HEAD.channels);
+        assert_eq!(u16::from_le_bytes([packet[10], packet[11]]), HEAD.pre_skip);
+        assert_eq!(
+            u32::from_le_bytes(packet[12..16].try_into().unwrap()),
+            HEAD.input_sample_rate
+        );
+        assert_eq!(&packet[16..], [0, 0, 0]);
+
+        let tags = &pages[1];
+        assert_eq!(tags.flags, 0);
+        assert_eq!(tags.granule, 0);
+        let packet = &tags.packets[0];
+        assert_eq!(&packet[..8], b"OpusTags");
+        let vendor_len = u32::from_le_bytes(packet[8..12].try_into().unwrap()) as usize;
+        assert_eq!(&packet[12..12 + vendor_len], VENDOR.as_bytes());
+        assert_eq!(&packet[12 + vendor_len..], [0, 0, 0, 0]);
+
+        let audio = &pages[2];
+        assert_eq!(audio.flags, END_OF_STREAM);
+        assert_eq!(audio.granule, 312 + 500);
+        assert_eq!(audio.packets, [vec![0xAA; 3]]);
+
+        for (n, page) in pages.iter().enumerate() {
+            assert_eq!(page.serial, SERIAL);
+            assert_eq!(page.sequence, n as u32);
+        }
+    }
+
+    #[test]
+    fn packets_round_trip_across_pages() {
+        // Lengths around the lacing boundaries, enough to fill several pages.
+        let packets: Vec<Vec<u8>> = (0..300)
+            .map(|n| vec![n as u8; [0, 1, 254, 255, 256, 510, 600][n % 7]])
+            .collect();
+        let pages = parse(&opus_file(HEAD, &packets, 960, 300 * 960 - 400));
+        let audio = &pages[2..];
+        assert!(audio.len() > 1);
+
+        let read: Vec<Vec<u8>> = audio.iter().flat_map(|p| p.packets.clone()).collect();
+        assert_eq!(read, packets);
+
+        let mut decoded = 0;
+        for (n, page) in audio.iter().enumerate() {
+            decoded += 960 * page.packets.len() as u64;
+            if n + 1 == audio.len() {
+                assert_eq!(page.flags, END_OF_STREAM);
+                assert_eq!(page.granule, 312 + 300 * 960 - 400);
+            } else {
+                assert_eq!(page.flags, 0);
+                assert_eq!(page.granule, decoded);
+            }
+        }
+    }
+}
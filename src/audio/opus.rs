@@ -0,0 +1,66 @@
+//! Ogg/Opus encoding, so speech can be posted as a voice clip.
+//!
+//! Opus only runs at a handful of rates, so audio is first resampled to
+//! 48 kHz, then cut into 20 ms frames for libopus and packed into an Ogg
+//! file by [`ogg::opus_file`]. The encoder is built with the `opus` cargo
+//! feature; without it [`encode`] fails with [`VoxError::OpusUnsupported`].
+
+use crate::audio::{ogg, resample};
+use crate::pawthos::enums::vox_errors::VoxError;
+
+/// Sample rate Opus is encoded at, and Ogg Opus granule positions count in.
+const OPUS_SAMPLE_RATE: u32 = 48_000;
+
+/// Samples in one 20 ms frame at [`OPUS_SAMPLE_RATE`].
+const FRAME_SAMPLES: usize = 960;
+
+/// libopus' lookahead at 48 kHz, which the decoder skips.
+const PRE_SKIP: u16 = 312;
+
+/// Encode mono `samples` at `sample_rate` as an Ogg Opus file.
+///
+/// # Errors
+/// [`VoxError::OpusUnsupported`] if the bot was built without the `opus`
+/// feature, or whatever the encoder failed with.
+pub fn encode(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>, VoxError> {
+    let mut pcm = resample::resample(samples, sample_rate, OPUS_SAMPLE_RATE);
+    let length = pcm.len() as u64;
+    // Push the last real samples out past the encoder's lookahead.
+    pcm.resize(pcm.len() + usize::from(PRE_SKIP), 0);
+    let packets = encode_frames(&pcm)?;
+    let head = ogg::OpusHead {
+        channels: 1,
+        pre_skip: PRE_SKIP,
+        input_sample_rate: sample_rate,
+    };
+    Ok(ogg::opus_file(head, &packets, FRAME_SAMPLES as u64, length))
+}
+
+/// One Opus packet for each [`FRAME_SAMPLES`] of `pcm`, the last frame
+/// padded with silence.
+#[cfg(feature = "opus")]
+fn encode_frames(pcm: &[i16]) -> Result<Vec<Vec<u8>>, VoxError> {
+    use audiopus::coder::Encoder;
+    use audiopus::{Application, Channels, SampleRate};
+
+    /// Largest packet Opus produces for one frame.
+    const MAX_PACKET_BYTES: usize = 1275;
+
+    let opus_error = |e: audiopus::Error| VoxError::Opus(e.to_string());
+    let encoder =
+        Encoder::new(SampleRate::Hz48000, Channels::Mono, Application::Voip).map_err(opus_error)?;
+    let mut packet = [0u8; MAX_PACKET_BYTES];
+    pcm.chunks(FRAME_SAMPLES)
+        .map(|chunk| {
+            let mut frame = [0i16; FRAME_SAMPLES];
+            frame[..chunk.len()].copy_from_slice(chunk);
+            let len = encoder.encode(&frame, &mut packet).map_err(opus_error)?;
+            Ok(packet[..len].to_vec())
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "opus"))]
+fn encode_frames(_: &[i16]) -> Result<Vec<Vec<u8>>, VoxError> {
+    Err(VoxError::OpusUnsupported)
+}
@@ -0,0 +1,77 @@
+//! Sample-rate and channel conversion for Discord voice.
+//!
+//! Discord voice carries 48 kHz stereo, while DECtalk speaks 11.025 kHz
+//! mono. Speech has little above 5 kHz, so linear interpolation is plenty:
+//! its slight high-frequency roll-off is inaudible next to DECtalk's own.
+
+/// Sample rate of Discord voice audio.
+pub const DISCORD_SAMPLE_RATE: u32 = 48_000;
+
+/// Channels in Discord voice audio.
+pub const DISCORD_CHANNELS: u16 = 2;
+
+/// Convert mono `samples` at `sample_rate` to interleaved stereo at
+/// [`DISCORD_SAMPLE_RATE`].
+pub fn to_discord(samples: &[i16], sample_rate: u32) -> Vec<i16> {
+    let resampled = resample(samples, sample_rate, DISCORD_SAMPLE_RATE);
+    resampled
+        .into_iter()
+        .flat_map(|s| std::iter::repeat_n(s, usize::from(DISCORD_CHANNELS)))
+        .collect()
+}
+
+/// Resample mono `samples` from `from` Hz to `to` Hz by linear
+/// interpolation.
+pub fn resample(samples: &[i16], from: u32, to: u32) -> Vec<i16> {
+    if from == to || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let out_len = (samples.len() as u64 * u64::from(to)).div_ceil(u64::from(from));
+    let step = f64::from(from) / f64::from(to);
+    let last = samples.len() - 1;
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * step;
+            let index = (pos as usize).min(last);
+            let frac = pos - index as f64;
+            let a = f64::from(samples[index]);
+            let b = f64::from(samples[(index + 1).min(last)]);
+            (a + (b - a) * frac).round() as i16
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_second_at_dectalk_rate_is_one_second_at_discord_rate() {
+        let out = to_discord(&[0; 11_025], 11_025);
+        assert_eq!(out.len(), 48_000 * usize::from(DISCORD_CHANNELS));
+    }
+
+    #[test]
+    fn length_rounds_up() {
+        assert_eq!(resample(&[0; 3], 11_025, 48_000).len(), 14);
+        assert_eq!(resample(&[0; 1], 11_025, 48_000).len(), 5);
+    }
+
+    #[test]
+    fn stereo_frames_repeat_each_mono_sample() {
+        let ramp: Vec<i16> = (0..1_000).map(|n| n * 20).collect();
+        let out = to_discord(&ramp, 11_025);
+        assert!(out.chunks_exact(2).all(|frame| frame[0] == frame[1]));
+
+        let left: Vec<i16> = out.iter().step_by(2).copied().collect();
+        assert_eq!(left, resample(&ramp, 11_025, DISCORD_SAMPLE_RATE));
+        assert_eq!(left[0], 0);
+        assert!(left.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn same_rate_is_unchanged() {
+        let samples = [1, -2, 3];
+        assert_eq!(resample(&samples, 48_000, 48_000), samples);
+    }
+}
@@ -0,0 +1,230 @@
+//! Minimal RIFF/WAVE encoder for PCM and µ-law audio.
+//!
+//! PCM gets the canonical 44-byte header (`RIFF`, `fmt `, `data`); µ-law,
+//! like any non-PCM format, also needs the `fmt ` extension size and a
+//! `fact` chunk. Samples follow in little-endian order — enough for
+//! Discord's audio player and every common media player.
+
+/// How each sample is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Linear PCM: 8-bit unsigned or 16-bit signed little-endian.
+    Pcm,
+
+    /// G.711 µ-law, 8 bits a sample.
+    MuLaw,
+}
+
+impl Encoding {
+    /// The `wFormatTag` a WAV file gives this encoding.
+    fn format_tag(self) -> u16 {
+        match self {
+            Encoding::Pcm => 1,
+            Encoding::MuLaw => 7,
+        }
+    }
+}
+
+/// Everything a WAV header says about the samples after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spec {
+    pub encoding: Encoding,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
+}
+
+impl Spec {
+    /// Bytes in one frame: a sample for every channel.
+    fn block_align(self) -> u16 {
+        self.channels * self.bits_per_sample / 8
+    }
+}
+
+/// Encode `data`, samples already laid out as `spec` says, as a WAV file.
+///
+/// With more than one channel, `data` alternates between them frame by
+/// frame (left, right, left, …).
+pub fn encode(data: &[u8], spec: Spec) -> Vec<u8> {
+    let pcm = spec.encoding == Encoding::Pcm;
+    let block_align = spec.block_align();
+    let byte_rate = spec.sample_rate * u32::from(block_align);
+    let data_len = u32::try_from(data.len()).unwrap_or(u32::MAX);
+    let fmt_len: u32 = if pcm { 16 } else { 18 };
+    let fact_len: u32 = if pcm { 0 } else { 12 };
+    let header_len = 12 + (8 + fmt_len) + fact_len + 8;
+
+    let mut out = Vec::with_capacity(header_len as usize + data.len());
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(data_len.saturating_add(header_len - 8)).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&fmt_len.to_le_bytes());
+    out.extend_from_slice(&spec.encoding.format_tag().to_le_bytes());
+    out.extend_from_slice(&spec.channels.to_le_bytes());
+    out.extend_from_slice(&spec.sample_rate.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&spec.bits_per_sample.to_le_bytes());
+    if !pcm {
+        out.extend_from_slice(&0u16.to_le_bytes()); // no format-specific extra
+
+        out.extend_from_slice(b"fact");
+        out.extend_from_slice(&4u32.to_le_bytes());
+        let frames = data_len / u32::from(block_align.max(1));
+        out.extend_from_slice(&frames.to_le_bytes());
+    }
+
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    out.extend_from_slice(data);
+    out
+}
+
+/// Decode `data`, laid out as `spec` says, into 16-bit samples.
+pub fn samples(data: &[u8], spec: Spec) -> Vec<i16> {
+    match (spec.encoding, spec.bits_per_sample) {
+        (Encoding::MuLaw, _) => data.iter().map(|&b| mulaw_to_linear(b)).collect(),
+        (Encoding::Pcm, 8) => data.iter().map(|&b| (i16::from(b) - 128) << 8).collect(),
+        (Encoding::Pcm, _) => data
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect(),
+    }
+}
+
+/// Expand one G.711 µ-law byte to a 16-bit sample.
+fn mulaw_to_linear(byte: u8) -> i16 {
+    let byte = !byte;
+    let exponent = (byte & 0x70) >> 4;
+    let magnitude = ((i16::from(byte & 0x0F) << 3) + 0x84) << exponent;
+    if byte & 0x80 != 0 {
+        0x84 - magnitude
+    } else {
+        magnitude - 0x84
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dectalk::WaveFormat;
+    use poise::ChoiceParameter;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    /// Read a WAV file back: its spec, `fact` frame count and data.
+    fn parse(file: &[u8]) -> (Spec, Option<u32>, &[u8]) {
+        assert_eq!(&file[..4], b"RIFF");
+        assert_eq!(u32_at(file, 4) as usize, file.len() - 8);
+        assert_eq!(&file[8..12], b"WAVE");
+
+        let mut spec = None;
+        let mut fact = None;
+        let mut at = 12;
+        while at < file.len() {
+            let id = &file[at..at + 4];
+            let len = u32_at(file, at + 4) as usize;
+            let body = &file[at + 8..at + 8 + len];
+            match id {
+                b"fmt " => {
+                    let encoding = match u16_at(body, 0) {
+                        1 => Encoding::Pcm,
+                        7 => Encoding::MuLaw,
+                        tag => panic!("unexpected format tag {tag}"),
+                    };
+                    let spec_read = Spec {
+                        encoding,
+                        channels: u16_at(body, 2),
+                        sample_rate: u32_at(body, 4),
+                        bits_per_sample: u16_at(body, 14),
+                    };
+                    let block_align = u16_at(body, 12);
+                    assert_eq!(block_align, spec_read.block_align());
+                    assert_eq!(
+                        u32_at(body, 8),
+                        spec_read.sample_rate * u32::from(block_align)
+                    );
+                    if encoding != Encoding::Pcm {
+                        assert_eq!(len, 18);
+                        assert_eq!(u16_at(body, 16), 0);
+                    }
+                    spec = Some(spec_read);
+                }
+                b"fact" => fact = Some(u32_at(body, 0)),
+                b"data" => return (spec.expect("fmt before data"), fact, body),
+                _ => panic!("unexpected chunk {id:?}"),
+            }
+            at += 8 + len;
+        }
+        panic!("no data chunk");
+    }
+
+    #[test]
+    fn every_dectalk_format_round_trips() {
+        let data: Vec<u8> = (0..=255).collect();
+        let formats: Vec<_> = (0..).map_while(WaveFormat::from_index).collect();
+        assert_eq!(formats.len(), 4);
+        for format in formats {
+            let spec = format.spec();
+            let file = encode(&data, spec);
+            let (read, fact, body) = parse(&file);
+            assert_eq!(read, spec, "{format:?}");
+            assert_eq!(body, &data[..], "{format:?}");
+            match spec.encoding {
+                Encoding::Pcm => assert_eq!(fact, None),
+                Encoding::MuLaw => assert_eq!(fact, Some(256)),
+            }
+        }
+    }
+
+    #[test]
+    fn pcm_header_is_canonical() {
+        let spec = WaveFormat::DT_1M16.spec();
+        let file = encode(&[0; 10], spec);
+        assert_eq!(file.len(), 44 + 10);
+        assert_eq!(&file[36..40], b"data");
+    }
+
+    #[test]
+    fn stereo_block_align() {
+        let spec = Spec {
+            encoding: Encoding::Pcm,
+            sample_rate: 48_000,
+            channels: 2,
+            bits_per_sample: 16,
+        };
+        let (read, _, _) = parse(&encode(&[0; 8], spec));
+        assert_eq!(read, spec);
+        assert_eq!(read.block_align(), 4);
+    }
+
+    #[test]
+    fn decodes_sixteen_bit_little_endian() {
+        let spec = WaveFormat::DT_1M16.spec();
+        let data = [0x34, 0x12, 0xFF, 0xFF, 0x00, 0x80];
+        assert_eq!(samples(&data, spec), [0x1234, -1, i16::MIN]);
+    }
+
+    #[test]
+    fn decodes_eight_bit_unsigned() {
+        let spec = WaveFormat::DT_1M08.spec();
+        assert_eq!(samples(&[0, 128, 255], spec), [i16::MIN, 0, 127 << 8]);
+    }
+
+    #[test]
+    fn decodes_mulaw() {
+        let spec = WaveFormat::DT_08M08.spec();
+        assert_eq!(
+            samples(&[0xFF, 0x7F, 0x00, 0x80, 0x70], spec),
+            [0, 0, -32124, 32124, -120]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Poise command checks backed by the permission sub-system.
+//!
+//! Attach one to a command with `#[poise::command(check = "checks::admin")]`.
+//! A caller below the required [`PermissionLevel`] gets
+//! [`PermissionError::Denied`], which [`crate::handlers::error_handler`]
+//! turns into a "Permission Denied" embed.
+//!
+//! # Levels
+//!
+//! | Level | Who |
+//! |---|---|
+//! | Owner | `owners` in `s.json`, plus whoever owns the bot's Discord application |
+//! | Admin | The guild owner, members with Discord's Administrator or Manage Server permission, and holders of the guild's admin roles |
+//! | Moderator | Holders of the guild's moderator roles |
+//!
+//! Each level includes everything below it. Admin and moderator roles are
+//! set per guild with `/permissions`; outside a guild only owners are above
+//! [`PermissionLevel::Member`].
+
+use crate::pawthos::enums::{
+    permission_errors::PermissionError, permission_level::PermissionLevel,
+};
+use crate::pawthos::types::{Context, Result};
+
+/// Work out the caller's level for this invocation.
+pub async fn permission_level(ctx: Context<'_>) -> PermissionLevel {
+    let author = ctx.author().id;
+    if ctx.framework().options().owners.contains(&author) {
+        return PermissionLevel::Owner;
+    }
+    let Some(guild_id) = ctx.guild_id() else {
+        return PermissionLevel::Member;
+    };
+    let Some(member) = ctx.author_member().await else {
+        return PermissionLevel::Member;
+    };
+
+    // Read from the cache and drop the guard before the next await.
+    let discord_admin = ctx.guild().is_some_and(|guild| {
+        guild.owner_id == author
+            || member.roles.iter().any(|id| {
+                guild.roles.get(id).is_some_and(|role| {
+                    role.permissions.administrator() || role.permissions.manage_guild()
+                })
+            })
+    });
+    let staff = ctx
+        .data()
+        .guild_permissions(guild_id)
+        .await
+        .level_for(&member.roles);
+
+    if discord_admin {
+        staff.max(PermissionLevel::Admin)
+    } else {
+        staff
+    }
+}
+
+/// Fail with [`PermissionError::Denied`] unless the caller is at least
+/// `required`.
+async fn require(ctx: Context<'_>, required: PermissionLevel) -> Result<bool> {
+    if permission_level(ctx).await >= required {
+        Ok(true)
+    } else {
+        Err(PermissionError::Denied { required }.into())
+    }
+}
+
+/// Bot owners only.
+pub async fn owner(ctx: Context<'_>) -> Result<bool> {
+    require(ctx, PermissionLevel::Owner).await
+}
+
+/// Guild admins and above.
+pub async fn admin(ctx: Context<'_>) -> Result<bool> {
+    require(ctx, PermissionLevel::Admin).await
+}
+
+/// Guild moderators and above.
+pub async fn moderator(ctx: Context<'_>) -> Result<bool> {
+    require(ctx, PermissionLevel::Moderator).await
+}
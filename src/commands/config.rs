@@ -0,0 +1,142 @@
+//! `/config` command suite — per-server economy settings.
+//!
+//! # Commands in this file
+//! - [`config`] — parent command (required by Poise).
+//! - [`get`] — show one setting, or all of them.
+//! - [`set`] — change a setting for this server.
+//! - [`reset`] — go back to the default for one setting, or all of them.
+//!
+//! Every command needs admin permission (see [`crate::checks`]). The
+//! settings themselves are described in
+//! [`crate::pawthos::structs::guild_config`].
+
+use crate::checks;
+use crate::pawthos::{
+    enums::embed_type::EmbedType,
+    structs::guild_config::{ConfigKey, GuildConfig},
+    types::{Context, Result},
+};
+use crate::utils;
+use poise::ChoiceParameter;
+
+/// Server settings — rewards, prices, fees and odds. Admins only.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    subcommands("get", "set", "reset"),
+    subcommand_required
+)]
+pub async fn config(_ctx: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Show this server's settings, or just one of them.
+///
+/// Settings nobody has changed are marked *(default)*. Response is
+/// ephemeral — only you can see it.
+#[poise::command(slash_command, guild_only, check = "checks::admin")]
+pub async fn get(
+    ctx: Context<'_>,
+    #[description = "Setting to show (defaults to all)"] key: Option<ConfigKey>,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let config = ctx.data().guild_config(guild_id).await;
+
+    let description = match key {
+        Some(key) => render_key(&config, key),
+        None => ConfigKey::ALL
+            .iter()
+            .map(|&key| render_key(&config, key))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+
+    let embed = utils::create_embed_builder("Server Config", description, EmbedType::Neutral);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Change a setting for this server.
+///
+/// The value is checked against the setting's type and range before
+/// anything is saved. Takes effect immediately.
+#[poise::command(slash_command, guild_only, check = "checks::admin")]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Setting to change"] key: ConfigKey,
+    #[description = "New value"] value: String,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let new_value = ctx
+        .data()
+        .with_guild_config_write(guild_id, |config| {
+            config.set(key, &value)?;
+            Ok::<_, crate::pawthos::enums::config_errors::ConfigError>(config.get(key))
+        })
+        .await?;
+
+    ctx.send(utils::reply_ok(
+        "Config Set",
+        format!(
+            "`{}` is now **{new_value}** (default {}).",
+            key.name(),
+            key.default_value()
+        ),
+    ))
+    .await?;
+
+    log::info!(
+        "{} set {} = {new_value} in guild {guild_id}",
+        ctx.author().name,
+        key.name()
+    );
+    Ok(())
+}
+
+/// Go back to the default for a setting, or for every setting.
+#[poise::command(slash_command, guild_only, check = "checks::admin")]
+pub async fn reset(
+    ctx: Context<'_>,
+    #[description = "Setting to reset (defaults to all)"] key: Option<ConfigKey>,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    ctx.data()
+        .with_guild_config_write(guild_id, |config| {
+            match key {
+                Some(key) => config.reset(key),
+                None => *config = GuildConfig::default(),
+            }
+            Ok::<_, crate::pawthos::enums::config_errors::ConfigError>(())
+        })
+        .await?;
+
+    let message = match key {
+        Some(key) => format!("`{}` is back to **{}**.", key.name(), key.default_value()),
+        None => "Every setting is back to its default.".to_string(),
+    };
+    ctx.send(utils::reply_ok("Config Reset", message)).await?;
+
+    log::info!(
+        "{} reset {} in guild {guild_id}",
+        ctx.author().name,
+        key.map_or("all settings", |k| k.name())
+    );
+    Ok(())
+}
+
+/// `` `daily_reward` = **10** *(default)* — Tabs awarded by … ``
+fn render_key(config: &GuildConfig, key: ConfigKey) -> String {
+    let marker = if config.is_set(key) {
+        ""
+    } else {
+        " *(default)*"
+    };
+    format!(
+        "`{}` = **{}**{marker} — {}",
+        key.name(),
+        config.get(key),
+        key.description()
+    )
+}
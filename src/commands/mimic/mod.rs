@@ -17,6 +17,7 @@
 
 use crate::commands::mimic::{delete::*, set::*};
 use crate::pawthos::{
+    enums::domain_event::DomainEvent,
     structs::mimic::Mimic,
     types::{Context, Embed, Reply, Result},
 };
@@ -97,6 +98,17 @@ pub async fn add(
         format!("Success! Your mimic \"{}\" has been added :3c", name),
     ))
     .await?;
+
+    ctx.data()
+        .publish(
+            ctx.http(),
+            DomainEvent::MimicSaved {
+                guild_id: ctx.guild_id(),
+                user_id,
+                channel_id: ctx.channel_id(),
+            },
+        )
+        .await;
     Ok(())
 }
 
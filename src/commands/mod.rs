@@ -7,20 +7,30 @@
 //! - The `/color preview` command (free hex preview swatch). Paid role
 //!   colour and name changes live in `/shop buy rolecolor` and
 //!   `/shop buy rolename`.
-//! - Admin prefix commands (`register`, `give_tabs`, `fix_color_role_names`).
+//! - Admin prefix commands (`register`, `give_tabs`, `reload_catalog`,
+//!   `fix_color_role_names`), gated by the checks in [`crate::checks`].
 //!
 //! Feature-specific command groups live in their own sub-modules:
+//! - [`config`] — per-server settings (`/config get|set|reset`).
 //! - [`mimic`] — webhook-based persona impersonation.
+//! - [`permissions`] — per-server admin and moderator roles.
 //! - [`schedule`] — timezone-aware event reminders.
 //! - [`shop`] — shop catalog, purchases, inventory.
+//! - [`time`] — members' local times (`/time convert|now`).
 //! - [`vox`] — DECtalk text-to-speech synthesis.
+//! - [`wallet`] — `/pay`, tab ledger history and the moderator-only `!audit`.
 
-use crate::commands::{mimic::*, profile::*, schedule::*, shop::*, vox::*};
-use crate::pawthos::consts::{
-    COLOR_PREVIEW_SIZE, DAILY_REWARD, FIZZ_ID, LEADERBOARD_SIZE, TAB_EMOJI,
-};
+use crate::commands::{config::*, mimic::*, permissions::*, profile::*, schedule::*, shop::*, time::*, vox::*, wallet::*};
+use crate::checks;
+use crate::pawthos::consts::{COLOR_PREVIEW_SIZE, TAB_EMOJI};
 use crate::pawthos::enums::color_errors::ColorError;
-use crate::pawthos::structs::shop_catalog::ACHIEVEMENTS;
+use crate::pawthos::enums::domain_event::DomainEvent;
+use crate::pawthos::enums::ledger_reason::LedgerReason;
+use crate::pawthos::enums::wallet_errors::WalletError;
+use crate::pawthos::structs::ledger_entry::TabMemo;
+use crate::pawthos::structs::catalog_file;
+use crate::pawthos::structs::achievement_rule::RuleSubject;
+use crate::pawthos::structs::shop_catalog;
 use crate::pawthos::{
     enums::embed_type::EmbedType,
     structs::data::Data,
@@ -29,11 +39,15 @@ use crate::pawthos::{
 use crate::utils::{self};
 use image::ImageEncoder;
 use poise::serenity_prelude::{self as serenity, EditRole, RoleId, User};
+mod config;
 mod mimic;
+mod permissions;
 mod profile;
 mod schedule;
 mod shop;
+mod time;
 mod vox;
+mod wallet;
 
 /// Register all commands with the Poise framework.
 ///
@@ -45,15 +59,22 @@ pub fn return_commands() -> Vec<poise::Command<Data, Error>> {
         help(),
         daily(),
         balance(),
+        pay(),
         pfp(),
         register(),
         give_tabs(),
+        reload_catalog(),
+        audit(),
         vox(),
         mimic(),
         schedule(),
+        time(),
         color(),
         profile(),
         shop(),
+        wallet(),
+        config(),
+        permissions(),
         leaderboard(),
         achievements(),
         fix_color_role_names(),
@@ -106,12 +127,14 @@ pub async fn pfp(
 /// The response is ephemeral so only you can see it. The daily window resets
 /// at midnight local time; the cooldown message tells you exactly how long
 /// remains if you've already claimed. Consecutive daily claims build a streak
-/// that awards bonus tabs (up to +5).
-#[poise::command(slash_command)]
+/// that awards bonus tabs (up to +5 by default). Each server keeps its own
+/// wallet, daily window and reward size (see `/config`).
+#[poise::command(slash_command, guild_only)]
 pub async fn daily(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
 
-    let result = ctx.data().wallet_user_daily(user_id).await?;
+    let result = ctx.data().wallet_user_daily(guild_id, user_id).await?;
 
     let streak_msg = if result.current_streak > 1 {
         format!(" You're on a **{}-day streak**!", result.current_streak)
@@ -119,8 +142,8 @@ pub async fn daily(ctx: Context<'_>) -> Result {
         String::new()
     };
 
-    let bonus_msg = if result.reward > DAILY_REWARD {
-        format!(" ({}+{} streak bonus)", DAILY_REWARD, result.reward - DAILY_REWARD)
+    let bonus_msg = if result.bonus > 0 {
+        format!(" ({}+{} streak bonus)", result.reward - result.bonus, result.bonus)
     } else {
         String::new()
     };
@@ -135,20 +158,29 @@ pub async fn daily(ctx: Context<'_>) -> Result {
     )
     .await?;
 
-    // Phase 7: streak achievements may unlock here.
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::TabsEarned {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                amount: result.reward,
+                reason: LedgerReason::Daily,
+            },
+        )
         .await;
     Ok(())
 }
 
-/// Check your current tab balance (ephemeral — only you can see it).
-#[poise::command(slash_command)]
+/// Check your tab balance in this server (ephemeral — only you can see it).
+#[poise::command(slash_command, guild_only)]
 pub async fn balance(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let balance = ctx
         .data()
-        .with_wallet_user_read(user_id, |user| Ok(user.tabs))
+        .with_wallet_user_read(guild_id, user_id, |user| Ok(user.tabs))
         .await?;
 
     ctx.send(
@@ -169,11 +201,14 @@ pub async fn balance(ctx: Context<'_>) -> Result {
 
 /// Show the top tab holders in the server.
 ///
-/// Displays the top 10 users ranked by tab balance, with their current daily
-/// streak shown alongside if active.
-#[poise::command(slash_command)]
+/// Displays the top users (10 by default) ranked by tab balance, with their
+/// current daily streak shown alongside if active. Only this server's
+/// economy is counted.
+#[poise::command(slash_command, guild_only)]
 pub async fn leaderboard(ctx: Context<'_>) -> Result {
-    let entries = ctx.data().get_tab_leaderboard(LEADERBOARD_SIZE).await;
+    let guild_id = ctx.guild_id().unwrap();
+    let size = ctx.data().guild_config(guild_id).await.leaderboard_size();
+    let entries = ctx.data().get_tab_leaderboard(guild_id, size).await;
 
     if entries.is_empty() {
         ctx.send(utils::reply_info(
@@ -215,28 +250,39 @@ pub async fn leaderboard(ctx: Context<'_>) -> Result {
 // Achievements
 // ---------------------------------------------------------------------------
 
-/// Show your achievement progress.
+/// Show your achievement progress in this server.
 ///
-/// Lists every achievement grouped into Unlocked ✅ and Locked 🔒. Response
-/// is ephemeral — only you see it.
-#[poise::command(slash_command)]
+/// Lists every achievement grouped into Unlocked ✅ and Locked 🔒; locked
+/// ones show how far along you are (e.g. `37/100 messages`). Response is
+/// ephemeral — only you see it.
+#[poise::command(slash_command, guild_only)]
 pub async fn achievements(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
-    let unlocked_ids = ctx
+    let (user, member) = ctx
         .data()
-        .with_inventory_user_read(user_id, |inv| Ok(inv.unlocked_achievements.clone()))
+        .achievement_subject(guild_id, user_id)
         .await
         .unwrap_or_default();
+    let subject = RuleSubject {
+        user: &user,
+        member: &member,
+    };
+    let unlocked_ids = &member.inventory.unlocked_achievements;
 
-    let (unlocked, locked): (Vec<&_>, Vec<&_>) = ACHIEVEMENTS
+    // Retired achievements only show up for members who earned them.
+    let catalog = shop_catalog::current();
+    let (unlocked, locked): (Vec<&_>, Vec<&_>) = catalog
+        .achievements
         .iter()
-        .partition(|a| unlocked_ids.iter().any(|id| id == a.id));
+        .filter(|a| !a.retired || unlocked_ids.contains(&a.id))
+        .partition(|a| unlocked_ids.contains(&a.id));
 
     let mut description = String::new();
     description.push_str(&format!(
         "**{} / {}** unlocked\n\n",
         unlocked.len(),
-        ACHIEVEMENTS.len()
+        unlocked.len() + locked.len()
     ));
 
     if !unlocked.is_empty() {
@@ -250,7 +296,13 @@ pub async fn achievements(ctx: Context<'_>) -> Result {
     if !locked.is_empty() {
         description.push_str("**🔒 Locked**\n");
         for a in &locked {
-            description.push_str(&format!("{} **{}** — *{}*\n", a.emoji, a.name, a.description));
+            description.push_str(&format!(
+                "{} **{}** — *{}* · `{}`\n",
+                a.emoji,
+                a.name,
+                a.description,
+                a.rule.progress(&subject),
+            ));
         }
     }
 
@@ -316,57 +368,82 @@ pub async fn preview(
 }
 
 // ---------------------------------------------------------------------------
-// Admin prefix commands (owner-only)
+// Admin prefix commands
 // ---------------------------------------------------------------------------
 
 /// Register slash commands globally (owner-only, prefix command).
 ///
-/// Opens the interactive Poise registration UI. Only works for `FIZZ_ID`;
-/// silently does nothing for anyone else.
-#[poise::command(prefix_command)]
+/// Opens the interactive Poise registration UI.
+#[poise::command(prefix_command, check = "checks::owner")]
 pub async fn register(ctx: Context<'_>) -> Result {
-    if ctx.author().id != FIZZ_ID {
-        return Ok(());
-    }
-
     poise::builtins::register_application_commands_buttons(ctx).await?;
     log::warn!("Debug register command called!!!");
     Ok(())
 }
 
-/// Give tabs to any user (owner-only, prefix command).
+/// Give tabs to any user in the current server (admin-only, prefix command).
 ///
 /// Usage: `!give_tabs @user 50`
-#[poise::command(prefix_command)]
+#[poise::command(prefix_command, guild_only, check = "checks::admin")]
 pub async fn give_tabs(ctx: Context<'_>, user: User, tabs: i64) -> Result {
-    if ctx.author().id != FIZZ_ID {
-        return Ok(());
-    }
-    let user_id = user.id;
+    let guild_id = ctx.guild_id().unwrap();
+    let memo = TabMemo::new(LedgerReason::Admin).counterparty(ctx.author().id);
     ctx.data()
-        .with_wallet_user_write(user_id, |user| {
-            user.add_tabs(tabs);
-            Ok(())
-        })
+        .transact(|tx| Ok::<_, WalletError>(tx.credit(guild_id, user.id, tabs, memo)))
         .await?;
+    ctx.data()
+        .publish(
+            ctx.http(),
+            DomainEvent::TabsEarned {
+                guild_id,
+                user_id: user.id,
+                channel_id: ctx.channel_id(),
+                amount: tabs,
+                reason: LedgerReason::Admin,
+            },
+        )
+        .await;
 
     log::warn!("Gave {} tabs to {}!", tabs, user.name);
     Ok(())
 }
 
-/// Retrofit old colour roles with the zero-width-space name prefix (owner-only).
+/// Re-read the shop catalog file without restarting (owner-only, prefix command).
+///
+/// Items missing from the new file are retired rather than deleted. A file
+/// that fails validation is rejected and the current catalog stays in place.
+///
+/// Usage: `!reload_catalog`
+#[poise::command(prefix_command, check = "checks::owner")]
+pub async fn reload_catalog(ctx: Context<'_>) -> Result {
+    let path = ctx.data().catalog_path.clone();
+    let summary = tokio::task::spawn_blocking(move || catalog_file::load(&path)).await??;
+
+    let mut body = format!(
+        "Active: **{}** titles · **{}** colorways · **{}** badges · **{}** unlocks · **{}** achievements",
+        summary.titles, summary.colorways, summary.badges, summary.unlocks, summary.achievements,
+    );
+    if !summary.retired.is_empty() {
+        body.push_str(&format!("\nRetired: `{}`", summary.retired.join("`, `")));
+    }
+    if !summary.restored.is_empty() {
+        body.push_str(&format!("\nRestored: `{}`", summary.restored.join("`, `")));
+    }
+    ctx.send(utils::reply_ok("Catalog Reloaded", body)).await?;
+
+    log::warn!("Reloaded shop catalog from {}", ctx.data().catalog_path.display());
+    Ok(())
+}
+
+/// Retrofit old colour roles with the zero-width-space name prefix (admin-only).
 ///
 /// Custom colour roles are identified by a leading `\u{200B}` in their name.
 /// This one-off utility command adds that prefix to an existing role that was
 /// created before the convention was introduced.
 ///
 /// Usage: `!fix_color_role_names <role_id>`
-#[poise::command(prefix_command, guild_only)]
+#[poise::command(prefix_command, guild_only, check = "checks::admin")]
 pub async fn fix_color_role_names(ctx: Context<'_>, role_id: u64) -> Result {
-    if ctx.author().id != FIZZ_ID {
-        return Ok(());
-    }
-
     let mut role = ctx
         .guild_id()
         .unwrap()
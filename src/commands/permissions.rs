@@ -0,0 +1,116 @@
+//! `/permissions` command suite — who counts as staff in this server.
+//!
+//! # Commands in this file
+//! - [`permissions`] — parent command (required by Poise).
+//! - [`list`] — show the admin and moderator roles, and your own level.
+//! - [`grant`] — make a role an admin or moderator role.
+//! - [`revoke`] — stop treating a role as staff.
+//!
+//! Changing roles needs admin permission; see [`crate::checks`] for how
+//! levels are worked out.
+
+use crate::checks;
+use crate::pawthos::{
+    enums::{embed_type::EmbedType, permission_level::StaffLevel},
+    types::{Context, Result},
+};
+use crate::utils;
+use poise::ChoiceParameter;
+use poise::serenity_prelude::{Role, RoleId};
+
+/// Admin and moderator roles for this server's bot commands.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("list", "grant", "revoke"),
+    subcommand_required
+)]
+pub async fn permissions(_ctx: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Show this server's admin and moderator roles, and your own level.
+///
+/// Response is ephemeral — only you can see it.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let permissions = ctx.data().guild_permissions(guild_id).await;
+    let level = checks::permission_level(ctx).await;
+
+    let description = format!(
+        "**Admin roles:** {}\n**Moderator roles:** {}\n\nYour level: **{level}**\n\
+         Server owners and members with Administrator or Manage Server are always admins.",
+        mention_roles(&permissions.admin_roles),
+        mention_roles(&permissions.mod_roles),
+    );
+    let embed = utils::create_embed_builder("Permissions", description, EmbedType::Neutral);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Make a role an admin or moderator role in this server.
+///
+/// Granting a role the other level moves it.
+#[poise::command(slash_command, guild_only, check = "checks::admin")]
+pub async fn grant(
+    ctx: Context<'_>,
+    #[description = "Role to grant"] role: Role,
+    #[description = "Level its members get"] level: StaffLevel,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    ctx.data()
+        .with_guild_permissions_write(guild_id, |p| p.grant(role.id, level))
+        .await?;
+
+    ctx.send(utils::reply_ok(
+        "Role Granted",
+        format!("Members of <@&{}> are now **{}s**.", role.id, level.name()),
+    ))
+    .await?;
+
+    log::info!(
+        "{} granted {} to role {} in guild {guild_id}",
+        ctx.author().name,
+        level.name(),
+        role.name
+    );
+    Ok(())
+}
+
+/// Stop treating a role as an admin or moderator role.
+#[poise::command(slash_command, guild_only, check = "checks::admin")]
+pub async fn revoke(ctx: Context<'_>, #[description = "Role to revoke"] role: Role) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let level = ctx
+        .data()
+        .with_guild_permissions_write(guild_id, |p| p.revoke(role.id))
+        .await?;
+
+    ctx.send(utils::reply_ok(
+        "Role Revoked",
+        format!("<@&{}> is no longer a {} role.", role.id, level.name()),
+    ))
+    .await?;
+
+    log::info!(
+        "{} revoked {} from role {} in guild {guild_id}",
+        ctx.author().name,
+        level.name(),
+        role.name
+    );
+    Ok(())
+}
+
+/// `<@&1>, <@&2>`, or `none`.
+fn mention_roles(roles: &[RoleId]) -> String {
+    if roles.is_empty() {
+        return "none".to_string();
+    }
+    roles
+        .iter()
+        .map(|r| format!("<@&{r}>"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -32,12 +32,14 @@ pub async fn profile(_ctx: Context<'_>) -> Result {
 /// Display a user's profile card as a rich embed.
 ///
 /// Shows their bio, badges, tab balance, custom colorway, and banner.
-/// Defaults to showing your own profile; pass a user to view theirs.
-#[poise::command(slash_command)]
+/// Defaults to showing your own profile; pass a user to view theirs. Balance
+/// and owned items come from this server's economy.
+#[poise::command(slash_command, guild_only)]
 pub async fn view(
     ctx: Context<'_>,
     #[description = "User to view (defaults to yourself)"] user: Option<serenity::User>,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let target = user.as_ref().unwrap_or_else(|| ctx.author());
     let target_id = target.id;
 
@@ -51,18 +53,18 @@ pub async fn view(
     // Read inventory (custom title + owned items).
     let inventory = ctx
         .data()
-        .with_inventory_user_read(target_id, |i| Ok(i.clone()))
+        .with_inventory_user_read(guild_id, target_id, |i| Ok(i.clone()))
         .await
         .unwrap_or_default();
 
     // Read tab balance (may not exist for new users).
     let tabs = ctx
         .data()
-        .with_wallet_user_read(target_id, |w| Ok(w.tabs))
+        .with_wallet_user_read(guild_id, target_id, |w| Ok(w.tabs))
         .await
         .unwrap_or(0);
 
-    let accent = resolve_colorway(&profile);
+    let accent = resolve_colorway(&profile, &inventory);
 
     let bio = profile
         .bio
@@ -107,22 +109,30 @@ pub async fn view(
 
 /// Resolve which title string (if any) to display on a profile card.
 ///
-/// See [`ProfileUser`] doc for resolution priority.
+/// See [`ProfileUser`] doc for resolution priority. The equipped title is
+/// global but ownership is per guild, so it's only shown where `inventory`
+/// (this server's) has it.
 fn resolve_title(profile: &ProfileUser, inventory: &InventoryUser) -> Option<String> {
-    if profile.use_custom_title {
-        inventory.custom_title.clone()
-    } else if let Some(ref id) = profile.active_title_id {
-        shop_catalog::lookup_title(id).map(|t| t.item.name.to_string())
-    } else {
-        None
+    if profile.use_custom_title
+        && inventory.unlocked_custom_title
+        && let Some(ref title) = inventory.custom_title
+    {
+        return Some(title.clone());
+    }
+    let id = profile.active_title_id.as_ref()?;
+    if !inventory.owned_titles.contains(id) {
+        return None;
     }
+    shop_catalog::lookup_title(id).map(|t| t.item.name)
 }
 
 /// Resolve which accent colour to render.
 ///
-/// Priority: named colorway (catalog lookup) → custom hex → default.
-fn resolve_colorway(profile: &ProfileUser) -> Color {
+/// Priority: named colorway owned in this server (catalog lookup) → custom
+/// hex → default.
+fn resolve_colorway(profile: &ProfileUser, inventory: &InventoryUser) -> Color {
     if let Some(ref id) = profile.active_colorway_id
+        && inventory.owned_colorways.contains(id)
         && let Some(def) = shop_catalog::lookup_colorway(id)
     {
         return Color::new(def.hex);
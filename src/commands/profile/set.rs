@@ -8,13 +8,14 @@
 //! - [`customtitle`] — set a user-written title (requires the unlock).
 
 use crate::pawthos::{
-    consts::{
-        BANNER_SET_COST, CUSTOM_COLORWAY_SET_COST, MAX_ACTIVE_BADGES, MAX_CUSTOM_TITLE_LEN,
-        TAB_EMOJI,
-    },
+    consts::{MAX_ACTIVE_BADGES, MAX_CUSTOM_TITLE_LEN, TAB_EMOJI},
+    enums::domain_event::DomainEvent,
     enums::inventory_errors::InventoryError,
+    enums::ledger_reason::LedgerReason,
     enums::profile_errors::ProfileError,
-    structs::shop_catalog::{self, COLORWAYS, TITLES},
+    enums::wallet_errors::WalletError,
+    structs::ledger_entry::TabMemo,
+    structs::shop_catalog,
     types::{Context, Result},
 };
 use crate::utils;
@@ -62,10 +63,11 @@ pub async fn bio(
 
 /// Set a custom banner image for your profile card. Charged per-set.
 ///
-/// Costs [`BANNER_SET_COST`] tabs every time you store a non-empty URL.
+/// Costs the server's `banner_set_cost` (10 tabs by default) every time you
+/// store a non-empty URL.
 /// Provide either a URL or an attachment (attachment wins on conflict).
 /// Calling this with neither argument clears your banner — that's free.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn banner(
     ctx: Context<'_>,
     #[description = "Banner image URL"] url: Option<String>,
@@ -73,6 +75,7 @@ pub async fn banner(
         serenity::Attachment,
     >,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let banner_url = attachment.as_ref().map(|a| a.url.clone()).or(url);
 
@@ -92,106 +95,117 @@ pub async fn banner(
         return Ok(());
     };
 
-    // Charge first — propagates `WalletError::NotEnoughTabs` to the error handler.
+    // Charge and apply together — `WalletError::NotEnoughTabs` leaves the
+    // banner untouched.
+    let cost = ctx.data().guild_config(guild_id).await.banner_set_cost();
     let tabs = ctx
         .data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(BANNER_SET_COST))
-        .await?;
-
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
-            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(BANNER_SET_COST);
-            Ok(())
-        })
-        .await?;
-
-    ctx.data()
-        .with_profile_user_write(user_id, |p| {
-            p.banner_url = Some(new_url);
-            Ok(())
+        .transact(|tx| {
+            let memo = TabMemo::new(LedgerReason::Purchase).item("banner");
+            let tabs = tx.debit(guild_id, user_id, cost, memo)?;
+            let inv = tx.inventory(guild_id, user_id);
+            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(cost);
+            tx.profile(user_id).banner_url = Some(new_url);
+            Ok::<_, WalletError>(tabs)
         })
         .await?;
 
     ctx.send(utils::reply_ok(
         "Profile Set Banner",
         format!(
-            "Your banner has been updated for **{BANNER_SET_COST} {TAB_EMOJI}**! Balance: **{tabs} {TAB_EMOJI}**.",
+            "Your banner has been updated for **{cost} {TAB_EMOJI}**! Balance: **{tabs} {TAB_EMOJI}**.",
         ),
     ))
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: "banner".into(),
+                cost,
+            },
+        )
         .await;
     Ok(())
 }
 
 /// Set a custom accent colour for your profile card embed. Charged per-set.
 ///
-/// Costs [`CUSTOM_COLORWAY_SET_COST`] tabs every time. Accepts bare hex
+/// Costs the server's `custom_colorway_set_cost` (5 tabs by default) every
+/// time. Accepts bare hex
 /// (`FF8800`) or `0x`-prefixed (`0xFF8800`). Equipping an *owned* named
 /// colorway via `/profile set namedcolorway` is free instead.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn colorway(
     ctx: Context<'_>,
     #[description = "Hex colour code (e.g. FF8800 or 0xFF8800)"] color: String,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
 
     // Validate before charging — bad hex shouldn't cost the user anything.
     let (color_int, trimmed) =
         utils::parse_hex_color(&color).ok_or(ProfileError::InvalidColorway)?;
 
+    let cost = ctx.data().guild_config(guild_id).await.custom_colorway_set_cost();
     let tabs = ctx
         .data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(CUSTOM_COLORWAY_SET_COST))
-        .await?;
-
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
-            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(CUSTOM_COLORWAY_SET_COST);
-            Ok(())
-        })
-        .await?;
-
-    ctx.data()
-        .with_profile_user_write(user_id, |p| {
+        .transact(|tx| {
+            let memo = TabMemo::new(LedgerReason::Purchase).item("colorway");
+            let tabs = tx.debit(guild_id, user_id, cost, memo)?;
+            let inv = tx.inventory(guild_id, user_id);
+            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(cost);
+            let p = tx.profile(user_id);
             p.colorway = Some(color_int);
             // Custom beats named: clear named-equip so the render picks up the new custom.
             p.active_colorway_id = None;
-            Ok(())
+            Ok::<_, WalletError>(tabs)
         })
         .await?;
 
     ctx.send(utils::reply_ok(
         "Profile Set Colorway",
         format!(
-            "Your profile accent colour is now `#{trimmed}` for **{CUSTOM_COLORWAY_SET_COST} {TAB_EMOJI}**. Balance: **{tabs} {TAB_EMOJI}**.",
+            "Your profile accent colour is now `#{trimmed}` for **{cost} {TAB_EMOJI}**. Balance: **{tabs} {TAB_EMOJI}**.",
         ),
     ))
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: "colorway".into(),
+                cost,
+            },
+        )
         .await;
     Ok(())
 }
 
 /// Equip one of your owned named colorways.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn namedcolorway(
     ctx: Context<'_>,
     #[description = "Which colorway to equip"]
     #[autocomplete = "owned_colorways_ac"]
     id: String,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let def = shop_catalog::lookup_colorway(&id)
         .ok_or_else(|| InventoryError::UnknownItem(id.clone()))?;
 
     let owned = ctx
         .data()
-        .with_inventory_user_read(user_id, |inv| {
+        .with_inventory_user_read(guild_id, user_id, |inv| {
             Ok(inv.owned_colorways.iter().any(|c| c == &id))
         })
         .await
@@ -222,13 +236,14 @@ pub async fn namedcolorway(
 ///
 /// Autocomplete shows only titles you've purchased. Use `/shop buy title <id>`
 /// first to acquire one.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn title(
     ctx: Context<'_>,
     #[description = "Which title to equip"]
     #[autocomplete = "owned_titles_ac"]
     id: String,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
 
     let def = shop_catalog::lookup_title(&id)
@@ -237,7 +252,7 @@ pub async fn title(
     // Verify ownership.
     let owned = ctx
         .data()
-        .with_inventory_user_read(user_id, |inv| Ok(inv.owned_titles.iter().any(|t| t == &id)))
+        .with_inventory_user_read(guild_id, user_id, |inv| Ok(inv.owned_titles.iter().any(|t| t == &id)))
         .await
         .unwrap_or(false);
     if !owned {
@@ -262,11 +277,12 @@ pub async fn title(
 }
 
 /// Write your own title — requires the Custom Title Unlock from the shop.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn customtitle(
     ctx: Context<'_>,
     #[description = "Your custom title text"] text: String,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let trimmed = text.trim();
 
@@ -288,7 +304,7 @@ pub async fn customtitle(
     // Must have the unlock.
     let unlocked = ctx
         .data()
-        .with_inventory_user_read(user_id, |inv| Ok(inv.unlocked_custom_title))
+        .with_inventory_user_read(guild_id, user_id, |inv| Ok(inv.unlocked_custom_title))
         .await
         .unwrap_or(false);
     if !unlocked {
@@ -297,7 +313,7 @@ pub async fn customtitle(
 
     // Store + auto-equip.
     ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
+        .with_inventory_user_write(guild_id, user_id, |inv| {
             inv.custom_title = Some(trimmed.to_string());
             Ok(())
         })
@@ -322,40 +338,49 @@ pub async fn customtitle(
 // ---------------------------------------------------------------------------
 
 async fn owned_titles_ac(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
     let p = partial.to_lowercase();
     let owned = ctx
         .data()
-        .with_inventory_user_read(ctx.author().id, |inv| Ok(inv.owned_titles.clone()))
+        .with_inventory_user_read(guild_id, ctx.author().id, |inv| Ok(inv.owned_titles.clone()))
         .await
         .unwrap_or_default();
 
-    TITLES
+    // Retired titles included — owners keep them.
+    shop_catalog::current()
+        .titles
         .iter()
-        .filter(|t| owned.iter().any(|id| id == t.item.id))
+        .filter(|t| owned.contains(&t.item.id))
         .filter(|t| {
             t.item.name.to_lowercase().contains(&p) || t.item.id.to_lowercase().contains(&p)
         })
         .take(25)
-        .map(|t| AutocompleteChoice::new(t.item.name.to_string(), t.item.id.to_string()))
+        .map(|t| AutocompleteChoice::new(t.item.name.clone(), t.item.id.clone()))
         .collect()
 }
 
 async fn owned_colorways_ac(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
     let p = partial.to_lowercase();
     let owned = ctx
         .data()
-        .with_inventory_user_read(ctx.author().id, |inv| Ok(inv.owned_colorways.clone()))
+        .with_inventory_user_read(guild_id, ctx.author().id, |inv| Ok(inv.owned_colorways.clone()))
         .await
         .unwrap_or_default();
 
-    COLORWAYS
+    shop_catalog::current()
+        .colorways
         .iter()
-        .filter(|c| owned.iter().any(|id| id == c.item.id))
+        .filter(|c| owned.contains(&c.item.id))
         .filter(|c| {
             c.item.name.to_lowercase().contains(&p) || c.item.id.to_lowercase().contains(&p)
         })
         .take(25)
-        .map(|c| AutocompleteChoice::new(c.item.name.to_string(), c.item.id.to_string()))
+        .map(|c| AutocompleteChoice::new(c.item.name.clone(), c.item.id.clone()))
         .collect()
 }
 
@@ -369,7 +394,7 @@ async fn owned_colorways_ac(ctx: Context<'_>, partial: &str) -> Vec<Autocomplete
 /// a slot to leave it empty. Badges are shared across lootbox pulls and
 /// achievement unlocks — autocomplete lists every badge you currently own.
 /// Running this command with no args clears your pinned badges.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn badges(
     ctx: Context<'_>,
     #[description = "First badge slot"]
@@ -382,6 +407,7 @@ pub async fn badges(
     #[autocomplete = "owned_badges_ac"]
     slot3: Option<String>,
 ) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let raw: Vec<String> = [slot1, slot2, slot3].into_iter().flatten().collect();
 
@@ -405,13 +431,13 @@ pub async fn badges(
     // mutating so the user either gets the full equip or a clean failure.
     let owned = ctx
         .data()
-        .with_inventory_user_read(user_id, |inv| Ok(inv.owned_badges.clone()))
+        .with_inventory_user_read(guild_id, user_id, |inv| Ok(inv.owned_badges.clone()))
         .await
         .unwrap_or_default();
     for id in &ids {
         if !owned.iter().any(|b| b == id) {
             let label = shop_catalog::resolve_badge_display(id)
-                .map(|(_, n)| n.to_string())
+                .map(|(_, n)| n)
                 .unwrap_or_else(|| id.clone());
             return Err(InventoryError::NotOwned(label).into());
         }
@@ -442,10 +468,13 @@ pub async fn badges(
 /// Autocomplete over every badge the user owns, across lootbox and
 /// achievement pools. Shows `{emoji} {name}` as the label.
 async fn owned_badges_ac(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
     let p = partial.to_lowercase();
     let owned = ctx
         .data()
-        .with_inventory_user_read(ctx.author().id, |inv| Ok(inv.owned_badges.clone()))
+        .with_inventory_user_read(guild_id, ctx.author().id, |inv| Ok(inv.owned_badges.clone()))
         .await
         .unwrap_or_default();
 
@@ -0,0 +1,299 @@
+//! `/schedule group …` subcommands — events announced in a server channel.
+//!
+//! A group event is posted as an announcement with join/leave buttons (see
+//! [`crate::handlers`] for the button presses). At the event time the bot
+//! posts a reminder in the same channel, mentioning the chosen role, and
+//! DMs everyone who joined. The event lives in its creator's schedule, so
+//! only they can edit or cancel it, and it shows up in their
+//! `/schedule list`.
+
+use crate::checks;
+use crate::pawthos::enums::domain_event::DomainEvent;
+use crate::pawthos::enums::permission_level::PermissionLevel;
+use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::structs::group_event::GroupEvent;
+use crate::pawthos::structs::lead_time::LeadTimes;
+use crate::pawthos::structs::schedule_event::{EventId, ScheduleEvent};
+use crate::pawthos::structs::time_input;
+use crate::pawthos::types::{Context, Result};
+use crate::utils::{self, Announcement};
+use poise::serenity_prelude::{self as serenity, AutocompleteChoice, GuildChannel, Role};
+
+/// Group events — announced in a channel, with RSVPs and a role mention.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("create", "edit", "cancel"),
+    subcommand_required
+)]
+pub async fn group(_ctx: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Autocomplete the caller's group events in this server.
+async fn fetch_group_events(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let guild_id = ctx.guild_id();
+    ctx.data()
+        .with_schedule_user_read(ctx.author().id, |user| {
+            Ok(user
+                .events
+                .iter()
+                .filter(|e| e.group.as_ref().map(|g| g.guild_id) == guild_id)
+                .filter(|e| e.name.starts_with(partial))
+                .map(|e| AutocompleteChoice::new(e.name.clone(), e.id.to_string()))
+                .collect())
+        })
+        .await
+        .unwrap_or_default()
+}
+
+/// Announce an event in a channel, with buttons for people to join.
+///
+/// `when` takes the same forms as `/schedule add`, in your timezone. At
+/// that time the bot posts a reminder in the channel (mentioning `role`, if
+/// given) and DMs everyone who joined — you're on the list to begin with.
+/// `remind` adds earlier reminders as for `/schedule add`. Mentioning a role
+/// that isn't normally mentionable needs moderator permission. Use
+/// `/schedule repeat` afterwards to make it recur.
+#[poise::command(slash_command, guild_only)]
+pub async fn create(
+    ctx: Context<'_>,
+    #[description = "Name of the event."] name: String,
+    #[description = "When, e.g. \"friday 8pm\", \"in 2 hours\", \"2026-05-01 14:00\""] when: String,
+    #[description = "Channel to announce it in"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+    #[description = "Role to mention in the reminder"] role: Option<Role>,
+    #[description = "Reminders before it, e.g. \"1d, 1h, now\" (default: yours)"] remind: Option<
+        String,
+    >,
+) -> Result {
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap();
+    let lead_times = remind.as_deref().map(LeadTimes::parse).transpose()?;
+    check_target(ctx, &channel, role.as_ref()).await?;
+
+    let local_tz = ctx
+        .data()
+        .with_schedule_user_read(user_id, |u| Ok(u.timezone))
+        .await
+        .unwrap_or_default();
+    let local_dt = time_input::resolve(&when, chrono::Utc::now(), local_tz)?;
+    if !super::confirm_event(ctx, &name, local_dt).await? {
+        return Ok(());
+    }
+
+    let role_id = role.map(|r| r.id);
+    let rsvps = vec![user_id];
+    let preview = ScheduleEvent {
+        id: EventId::random(),
+        name: name.clone(),
+        when: local_dt.to_utc(),
+        tz: local_tz,
+        recurrence: None,
+        group: None,
+        lead_times: LeadTimes::default(),
+        reminded: None,
+        snooze: None,
+        done: None,
+    };
+    let (embed, buttons) =
+        utils::group_announcement(&preview, role_id, &rsvps, user_id, Announcement::Open);
+    let announcement = channel
+        .send_message(
+            ctx,
+            serenity::CreateMessage::new()
+                .embed(embed)
+                .components(buttons),
+        )
+        .await?;
+
+    let group = GroupEvent {
+        guild_id,
+        channel_id: channel.id,
+        role_id,
+        announcement: announcement.id,
+        rsvps,
+    };
+    let event = ctx
+        .data()
+        .with_schedule_user_write(user_id, |user| {
+            Ok(user.add_group_event(name, local_dt.to_utc(), lead_times, group))
+        })
+        .await?;
+    ctx.data().queue_reminder(user_id, event.clone());
+
+    ctx.send(utils::reply_ok(
+        "Group Event",
+        format!(
+            "**{}** is announced in <#{}> for <t:{}:F>.",
+            event.name,
+            channel.id,
+            event.when.timestamp()
+        ),
+    ))
+    .await?;
+
+    ctx.data()
+        .publish(
+            ctx.http(),
+            DomainEvent::EventScheduled {
+                guild_id: Some(guild_id),
+                user_id,
+                channel_id: ctx.channel_id(),
+            },
+        )
+        .await;
+    Ok(())
+}
+
+/// Move one of your group events to another channel, or change the role
+/// its reminders mention.
+///
+/// Moving to another channel posts a fresh announcement there, keeping the
+/// RSVPs. Use `/schedule edit` to change its name, time or reminders.
+#[poise::command(slash_command, guild_only)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "Group event to edit."]
+    #[autocomplete = "fetch_group_events"]
+    event: String,
+    #[description = "New channel to announce it in"]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "New role to mention in the reminder"] role: Option<Role>,
+) -> Result {
+    let user_id = ctx.author().id;
+    let old = ctx
+        .data()
+        .with_schedule_user_read(user_id, |user| user.group_event(&event))
+        .await?;
+    let old_group = old.group.clone().ok_or(ScheduleError::NotAGroupEvent)?;
+
+    let mut group = old_group.clone();
+    let target = match &channel {
+        Some(channel) => channel.clone(),
+        None => group
+            .channel_id
+            .to_channel(ctx)
+            .await?
+            .guild()
+            .ok_or(ScheduleError::CannotPostIn(group.channel_id))?,
+    };
+    check_target(ctx, &target, role.as_ref()).await?;
+    if let Some(role) = role {
+        group.role_id = Some(role.id);
+    }
+    if target.id != old_group.channel_id {
+        let (embed, buttons) = utils::group_announcement(
+            &old,
+            group.role_id,
+            &group.rsvps,
+            user_id,
+            Announcement::Open,
+        );
+        let announcement = target
+            .send_message(
+                ctx,
+                serenity::CreateMessage::new()
+                    .embed(embed)
+                    .components(buttons),
+            )
+            .await?;
+        group.channel_id = target.id;
+        group.announcement = announcement.id;
+    }
+    let mut edited = old;
+    edited.group = Some(group);
+
+    let updated = ctx
+        .data()
+        .with_schedule_user_write(user_id, |user| user.replace_event(edited))
+        .await?;
+    if updated.group.as_ref().map(|g| g.announcement) != Some(old_group.announcement) {
+        let _ = old_group
+            .channel_id
+            .delete_message(ctx, old_group.announcement)
+            .await;
+    }
+    utils::update_group_announcement(ctx.http(), &updated, user_id, Announcement::Open).await;
+
+    ctx.send(utils::reply_ok(
+        "Group Event",
+        format!("**{}** is announced in <#{}>.", updated.name, target.id),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Cancel one of your group events. Everyone who joined is DMed.
+#[poise::command(slash_command, guild_only)]
+pub async fn cancel(
+    ctx: Context<'_>,
+    #[description = "Group event to cancel."]
+    #[autocomplete = "fetch_group_events"]
+    event: String,
+) -> Result {
+    let user_id = ctx.author().id;
+    let cancelled = ctx
+        .data()
+        .with_schedule_user_write(user_id, |user| {
+            user.group_event(&event)?;
+            user.delete_event(&event)
+        })
+        .await?;
+    ctx.data().cancel_reminder(user_id, cancelled.id);
+    announce_cancelled(ctx.http(), &cancelled, user_id).await;
+
+    ctx.send(utils::reply_ok(
+        "Group Event",
+        format!("**{}** has been cancelled.", cancelled.name),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Mark a removed group event's announcement as cancelled and tell everyone
+/// who joined, except the creator.
+pub(super) async fn announce_cancelled(
+    http: &serenity::Http,
+    event: &ScheduleEvent,
+    creator: serenity::UserId,
+) {
+    utils::update_group_announcement(http, event, creator, Announcement::Cancelled).await;
+    utils::dm_rsvps(
+        http,
+        event,
+        Some(creator),
+        &format!(
+            "❌ **{}** (<t:{}:F>) has been cancelled.",
+            event.name,
+            event.when.timestamp()
+        ),
+    )
+    .await;
+}
+
+/// Check the caller may post in `channel` and, unless they're a moderator,
+/// that `role` is one anybody could mention.
+async fn check_target(ctx: Context<'_>, channel: &GuildChannel, role: Option<&Role>) -> Result {
+    let member = ctx
+        .author_member()
+        .await
+        .ok_or(ScheduleError::CannotPostIn(channel.id))?;
+    let can_post = ctx.guild_id() == Some(channel.guild_id)
+        && ctx
+            .guild()
+            .is_some_and(|guild| guild.user_permissions_in(channel, &member).send_messages());
+    if !can_post {
+        return Err(ScheduleError::CannotPostIn(channel.id).into());
+    }
+
+    if let Some(role) = role
+        && !role.mentionable
+        && checks::permission_level(ctx).await < PermissionLevel::Moderator
+    {
+        return Err(ScheduleError::RoleNotMentionable(role.id).into());
+    }
+    Ok(())
+}
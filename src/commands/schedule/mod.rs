@@ -1,33 +1,57 @@
 //! `/schedule` command suite — timezone-aware event reminders.
 //!
-//! Users can add named events with a date, time, and their configured timezone.
+//! Users can add named events at a free-form time (`tomorrow 9am`, `in 20
+//! minutes`) in their configured timezone.
 //! The bot stores events in UTC and sends the user a DM when the event time
 //! arrives (via [`crate::framework`]'s reminder task).
 //!
 //! # Commands
 //! - [`schedule`] — parent command.
-//! - [`add`] — add an event (date + time + current timezone).
+//! - [`add`] — add an event at a free-form time in your timezone, after
+//!   confirming what it resolved to.
+//! - [`repeat`] — make an event repeat, change its repeat, or stop it.
 //! - [`list`] — list upcoming events (prunes past ones first).
-//! - [`delete`] — remove an event by name.
+//! - [`edit`] — change an event's name, time or reminders.
+//! - [`delete`] — remove an event and stop its reminders.
 //! - [`set_tz`] — set your home timezone (used when parsing event times).
+//! - [`defaults`] — show or set the reminders new events get.
+//! - [`export`] — download your schedule as an `.ics` file.
+//! - [`import`] — add the events in an `.ics` file to your schedule.
+//!
+//! # Sub-modules
+//! - [`group`] — events announced in a server channel, with RSVPs
+//!   (`/schedule group create|edit|cancel`).
 
 use std::str::FromStr;
+use std::time::Duration;
 
+use crate::commands::schedule::group::group;
+use crate::pawthos::consts::{
+    SCHEDULE_CONFIRM_TIMEOUT_SECS, SCHEDULE_IMPORT_MAX_BYTES, SCHEDULE_IMPORT_SKIPPED_SHOWN,
+};
+use crate::pawthos::enums::domain_event::DomainEvent;
 use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::structs::ical::{self, SkipReason, Skipped};
+use crate::pawthos::structs::lead_time::LeadTimes;
+use crate::pawthos::structs::recurrence::{MonthWeek, Recurrence, RecurrenceRule, RepeatKind};
+use crate::pawthos::structs::time_input;
 use crate::pawthos::types::{Context, Result};
 use crate::utils;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, Datelike, NaiveDate, Weekday};
 use chrono_tz::{TZ_VARIANTS, Tz};
 use poise::serenity_prelude::{self as serenity};
 use serenity::AutocompleteChoice;
+mod group;
 
 // ---------------------------------------------------------------------------
 // Autocomplete helper
 // ---------------------------------------------------------------------------
 
-/// Provide autocomplete choices for commands that accept an event name.
+/// Provide autocomplete choices for commands that accept an event.
 ///
-/// Filters the user's event list by the partial string typed so far.
+/// Filters the user's event list by the partial string typed so far. Each
+/// choice shows the event's name and fills in its ID, so events sharing a
+/// name can still be told apart.
 async fn fetch_events(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     ctx.data()
         .with_schedule_user_read(ctx.author().id, |user| {
@@ -37,7 +61,7 @@ async fn fetch_events(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice
                 .filter_map(|e| {
                     e.name
                         .starts_with(partial)
-                        .then_some(AutocompleteChoice::new(e.name.clone(), e.name.clone()))
+                        .then_some(AutocompleteChoice::new(e.name.clone(), e.id.to_string()))
                 })
                 .collect())
         })
@@ -50,31 +74,37 @@ async fn fetch_events(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice
 // ---------------------------------------------------------------------------
 
 /// Schedule suite of commands for timezone-aware event reminders.
-#[poise::command(slash_command, subcommands("add", "list", "delete", "set_tz"))]
+#[poise::command(
+    slash_command,
+    subcommands(
+        "add", "edit", "repeat", "list", "delete", "set_tz", "defaults", "export", "import",
+        "group"
+    )
+)]
 pub async fn schedule(_ctx: Context<'_>) -> Result {
     Ok(())
 }
 
 /// Add an event to your schedule and receive a DM reminder when it arrives.
 ///
-/// Times are interpreted in your configured timezone (set with `/schedule set_tz`).
-/// The event is stored in UTC internally. The bot will DM you at the event time
-/// with a reminder — this persists across bot restarts.
-///
-/// Returns an error if the date/time string is malformed, the timezone is
-/// invalid, or the time falls in a DST gap.
+/// `when` can be relative (`in 20 minutes`, `2h30m`), a day and time
+/// (`tomorrow 9am`, `next friday 17:30`) or a full date (`2026-05-01 14:00`),
+/// and is read in your configured timezone (set with `/schedule set_tz`).
+/// The resolved local and UTC times are shown for you to confirm before
+/// anything is saved. The bot will DM you a reminder at each of `remind`
+/// (e.g. `1d, 1h, now`; default: your `/schedule defaults`) — this persists
+/// across bot restarts. Use `/schedule repeat` afterwards to make it recur.
 #[poise::command(slash_command)]
 pub async fn add(
     ctx: Context<'_>,
     #[description = "Name of the event."] name: String,
-    #[description = "Date (YYYY-MM-DD)"] date: String,
-    #[description = "Time (HH:MM 24-hour)"] time: String,
+    #[description = "When, e.g. \"tomorrow 9am\", \"in 20 minutes\", \"2026-05-01 14:00\""]
+    when: String,
+    #[description = "Reminders before it, e.g. \"1d, 1h, 10m, now\" (default: yours)"]
+    remind: Option<String>,
 ) -> Result {
     let user_id = ctx.author().id;
-    // parse date/time
-    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
-    let time = NaiveTime::parse_from_str(&time, "%H:%M")?;
-    let naive_dt = NaiveDateTime::new(date, time);
+    let lead_times = remind.as_deref().map(LeadTimes::parse).transpose()?;
     // now we need to grab the users timezone to create a tz datetime
     let local_tz = ctx
         .data()
@@ -82,10 +112,10 @@ pub async fn add(
         .await?;
 
     // this is the local date time :3c
-    let local_dt = naive_dt
-        .and_local_timezone(local_tz)
-        .single()
-        .ok_or(ScheduleError::AmbiguousOrInvalidTime)?;
+    let local_dt = time_input::resolve(&when, chrono::Utc::now(), local_tz)?;
+    if !confirm_event(ctx, &name, local_dt).await? {
+        return Ok(());
+    }
 
     let embed_reply = utils::reply_ok(
         "Schedule Add",
@@ -95,45 +125,204 @@ pub async fn add(
     let event = ctx
         .data()
         .with_schedule_user_write(user_id, |user| {
-            let ev = user.add_event(name, local_dt.to_utc());
+            let ev = user.add_event(name, local_dt.to_utc(), lead_times);
             Ok(ev)
         })
         .await?;
-    if let Err(e) = ctx
+    ctx.data().queue_reminder(user_id, event.clone());
+
+    ctx.send(embed_reply).await?;
+
+    ctx.data()
+        .publish(
+            ctx.http(),
+            DomainEvent::EventScheduled {
+                guild_id: ctx.guild_id(),
+                user_id,
+                channel_id: ctx.channel_id(),
+            },
+        )
+        .await;
+    Ok(())
+}
+
+/// Change the name, time or reminders of one of your events.
+///
+/// A new `when` is read like `/schedule add`'s, in your timezone; on a
+/// repeating event it moves the series to that date and time of day, and
+/// every reminder is sent again for the new time. The old reminders are
+/// cancelled. For a group event, the announcement is updated and everyone
+/// who joined is DMed if the time changes.
+#[poise::command(slash_command)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "Event to edit."]
+    #[autocomplete = "fetch_events"]
+    event: String,
+    #[description = "New name"] name: Option<String>,
+    #[description = "New time, e.g. \"saturday 3pm\", \"in 2 hours\""] when: Option<String>,
+    #[description = "New reminders, e.g. \"1d, 1h, now\""] remind: Option<String>,
+) -> Result {
+    let user_id = ctx.author().id;
+    let lead_times = remind.as_deref().map(LeadTimes::parse).transpose()?;
+    let (old, local_tz) = ctx
+        .data()
+        .with_schedule_user_read(user_id, |user| Ok((user.event(&event)?, user.timezone)))
+        .await?;
+
+    let mut edited = old.clone();
+    if let Some(name) = name {
+        edited.name = name;
+    }
+    if let Some(when) = when {
+        let local_dt = time_input::resolve(&when, chrono::Utc::now(), local_tz)?;
+        edited.when = local_dt.to_utc();
+        edited.tz = local_tz;
+        if let Some(recurrence) = &mut edited.recurrence {
+            recurrence.time = local_dt.time();
+        }
+    }
+    if let Some(lead_times) = lead_times {
+        edited.lead_times = lead_times;
+    }
+
+    let updated = ctx
         .data()
-        .schedule_events_channel
-        .send((user_id, event.clone()))
-    {
-        log::error!("Failed to queue reminder task! {}", e);
+        .with_schedule_user_write(user_id, |user| user.replace_event(edited))
+        .await?;
+    ctx.data().queue_reminder(user_id, updated.clone());
+
+    if updated.group.is_some() {
+        utils::update_group_announcement(ctx.http(), &updated, user_id, utils::Announcement::Open)
+            .await;
+        if updated.when != old.when {
+            utils::dm_rsvps(
+                ctx.http(),
+                &updated,
+                Some(user_id),
+                &format!(
+                    "📅 **{}** has moved to <t:{}:F>.",
+                    updated.name,
+                    updated.when.timestamp()
+                ),
+            )
+            .await;
+        }
     }
 
-    ctx.send(embed_reply).await?;
+    ctx.send(utils::reply_ok(
+        "Schedule Edit",
+        format!(
+            "**{}** is at <t:{}:F>, reminding {}.",
+            updated.name,
+            updated.when.timestamp(),
+            updated.lead_times
+        ),
+    ))
+    .await?;
     Ok(())
 }
 
+/// Ask the user to confirm the time `when` resolved to, with buttons.
+/// Nothing has been saved yet; returns whether to go ahead.
+async fn confirm_event(
+    ctx: Context<'_>,
+    name: &str,
+    when: DateTime<Tz>,
+) -> std::result::Result<bool, serenity::Error> {
+    let confirm_id = format!("{}:schedule_confirm", ctx.id());
+    let cancel_id = format!("{}:schedule_cancel", ctx.id());
+
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&confirm_id)
+            .label("Save")
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(&cancel_id)
+            .label("Cancel")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+    let prompt = ctx
+        .send(
+            poise::CreateReply::default()
+                .content(format!(
+                    "Schedule **{name}** for **{}** ({} UTC)?",
+                    when.format("%A %Y-%m-%d %H:%M %Z"),
+                    when.to_utc().format("%Y-%m-%d %H:%M"),
+                ))
+                .components(vec![buttons])
+                .ephemeral(true),
+        )
+        .await?;
+
+    let ids = (confirm_id.clone(), cancel_id);
+    let press = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id == ids.0 || i.data.custom_id == ids.1)
+        .timeout(Duration::from_secs(SCHEDULE_CONFIRM_TIMEOUT_SECS))
+        .await;
+
+    let (confirmed, outcome) = match &press {
+        Some(p) if p.data.custom_id == confirm_id => (true, "Confirmed — saving…"),
+        Some(_) => (false, "Cancelled — nothing was scheduled."),
+        None => (false, "Timed out — nothing was scheduled."),
+    };
+    match press {
+        Some(p) => {
+            p.create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(outcome)
+                        .components(Vec::new()),
+                ),
+            )
+            .await?;
+        }
+        None => {
+            prompt
+                .edit(
+                    ctx,
+                    poise::CreateReply::default()
+                        .content(outcome)
+                        .components(Vec::new()),
+                )
+                .await?
+        }
+    }
+    Ok(confirmed)
+}
+
 /// List all of your upcoming events, sorted by time.
 ///
 /// Past events are pruned from your list before displaying (and the pruned
-/// list is saved). Each event is shown as `"<name> : <local datetime>"`.
+/// list is saved); repeating events show their next occurrence. Each event
+/// is shown as `"<name> : <local datetime>"`.
 #[poise::command(slash_command)]
 pub async fn list(ctx: Context<'_>) -> Result {
     let user_id = ctx.author().id;
     let now = chrono::Utc::now();
-    let reply = ctx
+    let (reply, advanced) = ctx
         .data()
         .with_schedule_user_write(user_id, |u| {
-            u.prune_past_events(now);
-            Ok(utils::reply_info("Schedule list", u.list_events()))
+            let advanced = u.prune_past_events(now);
+            Ok((
+                utils::reply_info("Schedule list", u.list_events()),
+                advanced,
+            ))
         })
         .await?;
+    for event in advanced {
+        ctx.data().queue_reminder(user_id, event);
+    }
 
     ctx.send(reply).await?;
     Ok(())
 }
 
-/// Delete an event from your schedule by name.
+/// Delete an event from your schedule; its reminders stop straight away.
 ///
-/// Autocomplete lists your current upcoming events.
+/// Autocomplete lists your current upcoming events. Deleting a group event
+/// cancels it, as `/schedule group cancel` does.
 #[poise::command(slash_command)]
 pub async fn delete(
     ctx: Context<'_>,
@@ -142,24 +331,151 @@ pub async fn delete(
     event: String,
 ) -> Result {
     let user_id = ctx.author().id;
-    let event_name = ctx
+    let removed = ctx
         .data()
-        .with_schedule_user_write(user_id, |user| user.delete_event(event))
+        .with_schedule_user_write(user_id, |user| user.delete_event(&event))
         .await?;
+    ctx.data().cancel_reminder(user_id, removed.id);
+    if removed.group.is_some() {
+        group::announce_cancelled(ctx.http(), &removed, user_id).await;
+    }
 
     ctx.send(utils::reply_ok(
         "Schedule Delete",
-        format!("Successfully deleted {} from your schedule", event_name),
+        format!("Successfully deleted {} from your schedule", removed.name),
     ))
     .await?;
     Ok(())
 }
 
+/// Make one of your events repeat, change how it repeats, or stop it.
+///
+/// The event's current date and local time start the series: every `every`
+/// days, weekly on `weekdays` (default: the event's day), or monthly on the
+/// `week`'th weekday (default: the one the event falls on). If the event's
+/// date doesn't fit the rule, it moves to the first date that does. Every
+/// occurrence keeps the same local time, DST or not, and `until` ends the
+/// series after that date.
+#[poise::command(slash_command)]
+pub async fn repeat(
+    ctx: Context<'_>,
+    #[description = "Event to repeat."]
+    #[autocomplete = "fetch_events"]
+    event: String,
+    #[description = "How it repeats"] repeat: RepeatKind,
+    #[description = "Every N days (default 1)"]
+    #[min = 1]
+    #[max = 365]
+    every: Option<u32>,
+    #[description = "Weekly: days, e.g. mon,wed,fri"] weekdays: Option<String>,
+    #[description = "Monthly: which week of the month"] week: Option<MonthWeek>,
+    #[description = "Last date to repeat on (YYYY-MM-DD)"] until: Option<String>,
+) -> Result {
+    let user_id = ctx.author().id;
+    let until = until
+        .map(|u| NaiveDate::parse_from_str(&u, "%Y-%m-%d"))
+        .transpose()?;
+    let now = chrono::Utc::now();
+
+    let updated = ctx
+        .data()
+        .with_schedule_user_write(user_id, |user| {
+            user.set_recurrence(&event, now, |start| {
+                let rule = repeat_rule(repeat, every, weekdays.as_deref(), week, start.date())?;
+                match rule {
+                    Some(rule) => Ok(Some(Recurrence {
+                        rule,
+                        time: start.time(),
+                        until,
+                    })),
+                    None if until.is_some() => Err(ScheduleError::UnusedRepeatOption("until")),
+                    None => Ok(None),
+                }
+            })
+        })
+        .await?;
+    ctx.data().queue_reminder(user_id, updated.clone());
+
+    if updated.group.is_some() {
+        utils::update_group_announcement(ctx.http(), &updated, user_id, utils::Announcement::Open)
+            .await;
+    }
+
+    let message = match &updated.recurrence {
+        Some(rec) => format!(
+            "{} repeats {rec}. Next: {}",
+            updated.name,
+            updated.when.with_timezone(&updated.tz)
+        ),
+        None => format!("{} no longer repeats.", updated.name),
+    };
+    ctx.send(utils::reply_ok("Schedule Repeat", message))
+        .await?;
+    Ok(())
+}
+
+/// Build the repeat rule from `/schedule repeat`'s options, or `None` to
+/// stop repeating. Options that don't belong to the chosen kind are rejected rather
+/// than ignored, so a typo doesn't silently make a different series.
+fn repeat_rule(
+    repeat: RepeatKind,
+    every: Option<u32>,
+    weekdays: Option<&str>,
+    week: Option<MonthWeek>,
+    date: NaiveDate,
+) -> Result<Option<RecurrenceRule>, ScheduleError> {
+    let options = [
+        (every.is_some(), "every", RepeatKind::Days),
+        (weekdays.is_some(), "weekdays", RepeatKind::Weekly),
+        (week.is_some(), "week", RepeatKind::Monthly),
+    ];
+    if let Some((_, option, _)) = options
+        .into_iter()
+        .find(|(given, _, kind)| *given && repeat != *kind)
+    {
+        return Err(ScheduleError::UnusedRepeatOption(option));
+    }
+
+    Ok(match repeat {
+        RepeatKind::Never => None,
+        RepeatKind::Days => Some(RecurrenceRule::EveryNDays {
+            interval: every.unwrap_or(1),
+        }),
+        RepeatKind::Weekly => Some(RecurrenceRule::Weekdays {
+            days: match weekdays {
+                Some(raw) => parse_weekdays(raw)?,
+                None => vec![date.weekday()],
+            },
+        }),
+        RepeatKind::Monthly => Some(RecurrenceRule::NthWeekday {
+            week: week.unwrap_or_else(|| MonthWeek::of(date)),
+            weekday: date.weekday(),
+        }),
+    })
+}
+
+/// Parse `mon,wed,fri` (or full names, any case, commas or spaces) into
+/// weekdays in week order.
+fn parse_weekdays(raw: &str) -> Result<Vec<Weekday>, ScheduleError> {
+    let mut days = raw
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<Weekday>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| ScheduleError::InvalidWeekdays(raw.to_string()))?;
+    if days.is_empty() {
+        return Err(ScheduleError::InvalidWeekdays(raw.to_string()));
+    }
+    days.sort_by_key(|d| d.num_days_from_monday());
+    days.dedup();
+    Ok(days)
+}
+
 /// Provide autocomplete choices for timezone names.
 ///
 /// Matches all IANA timezone strings (from `chrono_tz`) that contain the
 /// partial input as a case-insensitive substring.
-async fn fetch_timezones(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+pub(super) async fn fetch_timezones(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     let partial = &partial.to_lowercase();
 
     TZ_VARIANTS
@@ -203,3 +519,143 @@ pub async fn set_tz(
 
     Ok(())
 }
+
+/// Show or set the reminders your new events get.
+///
+/// `remind` is a comma-separated list of lead times, e.g. `1d, 1h, 10m,
+/// now` — `now` is the reminder at the start. Existing events keep the
+/// reminders they were made with. Without `remind`, shows your current
+/// defaults.
+#[poise::command(slash_command)]
+pub async fn defaults(
+    ctx: Context<'_>,
+    #[description = "Reminders before each event, e.g. \"1d, 1h, 10m, now\""] remind: Option<
+        String,
+    >,
+) -> Result {
+    let user_id = ctx.author().id;
+    let lead_times = remind.as_deref().map(LeadTimes::parse).transpose()?;
+    let message = match lead_times {
+        Some(lead_times) => {
+            let message = format!("New events will remind you {lead_times}.");
+            ctx.data()
+                .with_schedule_user_write(user_id, |user| {
+                    user.set_default_lead_times(lead_times);
+                    Ok(())
+                })
+                .await?;
+            message
+        }
+        None => {
+            let current = ctx
+                .data()
+                .with_schedule_user_read(user_id, |user| Ok(user.default_lead_times.clone()))
+                .await
+                .unwrap_or_default();
+            format!("New events remind you {current}.")
+        }
+    };
+
+    ctx.send(utils::reply_ok("Schedule Defaults", message))
+        .await?;
+    Ok(())
+}
+
+/// Download your schedule as an iCalendar (`.ics`) file.
+///
+/// Past events are pruned first, as for `/schedule list`. Each event keeps
+/// its timezone and repeat, so calendar apps show it at the same local
+/// time; reminders and group event details aren't included.
+#[poise::command(slash_command)]
+pub async fn export(ctx: Context<'_>) -> Result {
+    let user_id = ctx.author().id;
+    let now = chrono::Utc::now();
+    let (events, advanced) = ctx
+        .data()
+        .with_schedule_user_write(user_id, |u| {
+            let advanced = u.prune_past_events(now);
+            Ok((u.events.clone(), advanced))
+        })
+        .await?;
+    for event in advanced {
+        ctx.data().queue_reminder(user_id, event);
+    }
+    if events.is_empty() {
+        return Err(ScheduleError::NothingToExport.into());
+    }
+
+    let file = serenity::CreateAttachment::bytes(ical::export(&events, now), "schedule.ics");
+    ctx.send(
+        utils::reply_ok(
+            "Schedule Export",
+            format!("Here are your {} events.", events.len()),
+        )
+        .attachment(file),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Add the events in an iCalendar (`.ics`) file to your schedule.
+///
+/// Timed events are imported with their timezone (`TZID`), or in yours if
+/// they don't name one, and with your default reminders. Daily, weekly and
+/// nth-weekday-monthly repeats carry over; a series that started in the past
+/// joins at its next occurrence. Anything past, unreadable or already in
+/// your schedule is skipped, and listed in the reply.
+#[poise::command(slash_command)]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "Calendar file (.ics)"] file: serenity::Attachment,
+) -> Result {
+    let user_id = ctx.author().id;
+    if file.size > SCHEDULE_IMPORT_MAX_BYTES {
+        return Err(ScheduleError::CalendarTooLarge.into());
+    }
+    let bytes = file.download().await?;
+    let text = String::from_utf8(bytes).map_err(|_| ScheduleError::NotACalendar)?;
+
+    let local_tz = ctx
+        .data()
+        .with_schedule_user_read(user_id, |u| Ok(u.timezone))
+        .await
+        .unwrap_or_default();
+    let report = ical::parse(&text, local_tz, chrono::Utc::now())?;
+    let mut skipped = report.skipped;
+    let added = ctx
+        .data()
+        .with_schedule_user_write(user_id, |user| {
+            let mut added = Vec::new();
+            for event in report.events {
+                match user.import_event(event.clone()) {
+                    Some(event) => added.push(event),
+                    None => skipped.push(Skipped {
+                        name: event.name,
+                        reason: SkipReason::AlreadyScheduled,
+                    }),
+                }
+            }
+            Ok(added)
+        })
+        .await?;
+    for event in &added {
+        ctx.data().queue_reminder(user_id, event.clone());
+    }
+
+    let mut message = format!("Imported **{}** events.", added.len());
+    if !skipped.is_empty() {
+        message += &format!("\n\nSkipped {}:", skipped.len());
+        for entry in skipped.iter().take(SCHEDULE_IMPORT_SKIPPED_SHOWN) {
+            message += &format!("\n- **{}** — {}", entry.name, entry.reason);
+        }
+        if skipped.len() > SCHEDULE_IMPORT_SKIPPED_SHOWN {
+            message += &format!(
+                "\n…and {} more.",
+                skipped.len() - SCHEDULE_IMPORT_SKIPPED_SHOWN
+            );
+        }
+    }
+    ctx.send(utils::reply_ok("Schedule Import", message))
+        .await?;
+    Ok(())
+}
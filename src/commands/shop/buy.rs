@@ -1,31 +1,27 @@
 //! `/shop buy …` subcommands.
 //!
-//! Each subcommand performs a three-step purchase:
+//! Each subcommand performs a three-step purchase inside one
+//! [`crate::pawthos::structs::data::Data::transact`] call:
 //!
-//! 1. **Ownership check** via `with_inventory_user_read`. Returning
-//!    [`InventoryError::AlreadyOwned`] short-circuits before any tab
-//!    deduction.
-//! 2. **Tab deduction** via `with_wallet_user_write`. Surfaces
+//! 1. **Ownership check.** Returning [`InventoryError::AlreadyOwned`]
+//!    short-circuits before any tab deduction.
+//! 2. **Tab deduction.** Surfaces
 //!    [`crate::pawthos::enums::wallet_errors::WalletError::NotEnoughTabs`]
 //!    on insufficient balance.
-//! 3. **Grant + stats update** via `with_inventory_user_write`.
+//! 3. **Grant + stats update.**
 //!
-//! Steps 2 and 3 are not atomic across the two sub-struct writes, but both
-//! operate on the same in-memory `UserDB` so the window is vanishingly small.
-//! A long-term mitigation would coalesce the two writes under a single
-//! lock acquisition.
+//! All three run under one lock acquisition and persist as one batch; any
+//! error rolls the wallet and inventory back together.
 
 use crate::pawthos::{
-    consts::{
-        LOOTBOX_CHANCE_COMMON, LOOTBOX_CHANCE_LEGENDARY, LOOTBOX_CHANCE_RARE,
-        LOOTBOX_CHANCE_UNCOMMON, LOOTBOX_COST, LOOTBOX_SALVAGE, ROLE_COLOR_COST,
-        ROLE_NAME_COST, TAB_EMOJI,
-    },
+    consts::TAB_EMOJI,
     enums::color_errors::ColorError,
+    enums::domain_event::DomainEvent,
     enums::inventory_errors::InventoryError,
-    structs::shop_catalog::{
-        self, BadgeDef, COLORWAYS, LOOTBOX_POOL, Rarity, TITLES, UNLOCKS,
-    },
+    enums::ledger_reason::LedgerReason,
+    structs::guild_config::GuildConfig,
+    structs::ledger_entry::TabMemo,
+    structs::shop_catalog::{self, BadgeDef, Rarity},
     types::{Context, Result},
 };
 use crate::utils;
@@ -46,7 +42,7 @@ pub async fn buy(_ctx: Context<'_>) -> Result {
 }
 
 /// Buy a catalog title. Use `/profile set title <id>` afterwards to equip it.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn title(
     ctx: Context<'_>,
     #[description = "Which title to buy"]
@@ -55,29 +51,25 @@ pub async fn title(
 ) -> Result {
     let def = shop_catalog::lookup_title(&id)
         .ok_or_else(|| InventoryError::UnknownItem(id.clone()))?;
+    if def.item.retired {
+        return Err(InventoryError::Retired(def.item.name).into());
+    }
 
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
 
-    // 1. Ownership check. `NoUserFound` means no inventory yet → not owned.
-    let already = ctx
-        .data()
-        .with_inventory_user_read(user_id, |inv| {
-            Ok(inv.owned_titles.iter().any(|t| t == &id))
-        })
-        .await
-        .unwrap_or(false);
-    if already {
-        return Err(InventoryError::AlreadyOwned(def.item.name.to_string()).into());
-    }
-
-    // 2. Charge. Propagates `WalletError::NotEnoughTabs` to the error handler.
     ctx.data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(def.item.cost))
-        .await?;
+        .transact(|tx| {
+            // 1. Ownership check.
+            if tx.inventory(guild_id, user_id).owned_titles.iter().any(|t| t == &id) {
+                return Err(InventoryError::AlreadyOwned(def.item.name.to_string()));
+            }
 
-    // 3. Grant.
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
+            // 2. Charge. Propagates `WalletError::NotEnoughTabs` to the error handler.
+            tx.debit(guild_id, user_id, def.item.cost, TabMemo::new(LedgerReason::Purchase).item(&id))?;
+
+            // 3. Grant.
+            let inv = tx.inventory(guild_id, user_id);
             inv.owned_titles.push(id.clone());
             inv.tabs_spent_lifetime += def.item.cost;
             Ok(())
@@ -94,13 +86,22 @@ pub async fn title(
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: id,
+                cost: def.item.cost,
+            },
+        )
         .await;
     Ok(())
 }
 
 /// Buy a named colorway. Equip with `/profile set namedcolorway <id>`.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn colorway(
     ctx: Context<'_>,
     #[description = "Which colorway to buy"]
@@ -109,26 +110,22 @@ pub async fn colorway(
 ) -> Result {
     let def = shop_catalog::lookup_colorway(&id)
         .ok_or_else(|| InventoryError::UnknownItem(id.clone()))?;
+    if def.item.retired {
+        return Err(InventoryError::Retired(def.item.name).into());
+    }
 
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
 
-    let already = ctx
-        .data()
-        .with_inventory_user_read(user_id, |inv| {
-            Ok(inv.owned_colorways.iter().any(|c| c == &id))
-        })
-        .await
-        .unwrap_or(false);
-    if already {
-        return Err(InventoryError::AlreadyOwned(def.item.name.to_string()).into());
-    }
-
     ctx.data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(def.item.cost))
-        .await?;
+        .transact(|tx| {
+            if tx.inventory(guild_id, user_id).owned_colorways.iter().any(|c| c == &id) {
+                return Err(InventoryError::AlreadyOwned(def.item.name.to_string()));
+            }
 
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
+            tx.debit(guild_id, user_id, def.item.cost, TabMemo::new(LedgerReason::Purchase).item(&id))?;
+
+            let inv = tx.inventory(guild_id, user_id);
             inv.owned_colorways.push(id.clone());
             inv.tabs_spent_lifetime += def.item.cost;
             Ok(())
@@ -145,46 +142,50 @@ pub async fn colorway(
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: id,
+                cost: def.item.cost,
+            },
+        )
         .await;
     Ok(())
 }
 
 /// Buy a one-time unlock. Currently only the custom-title unlock exists.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn unlock(
     ctx: Context<'_>,
     #[description = "Which unlock to buy"]
     #[autocomplete = "buyable_unlocks"]
     id: String,
 ) -> Result {
-    let item = UNLOCKS
-        .iter()
-        .find(|u| u.id == id)
+    let item = shop_catalog::lookup_unlock(&id)
         .ok_or_else(|| InventoryError::UnknownItem(id.clone()))?;
+    if item.retired {
+        return Err(InventoryError::Retired(item.name).into());
+    }
 
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
 
-    let already = ctx
-        .data()
-        .with_inventory_user_read(user_id, |inv| {
-            Ok(match id.as_str() {
-                "unlock_custom_title" => inv.unlocked_custom_title,
+    ctx.data()
+        .transact(|tx| {
+            let already = match id.as_str() {
+                "unlock_custom_title" => tx.inventory(guild_id, user_id).unlocked_custom_title,
                 _ => false,
-            })
-        })
-        .await
-        .unwrap_or(false);
-    if already {
-        return Err(InventoryError::AlreadyOwned(item.name.to_string()).into());
-    }
+            };
+            if already {
+                return Err(InventoryError::AlreadyOwned(item.name.to_string()));
+            }
 
-    ctx.data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(item.cost))
-        .await?;
+            tx.debit(guild_id, user_id, item.cost, TabMemo::new(LedgerReason::Purchase).item(&id))?;
 
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
+            let inv = tx.inventory(guild_id, user_id);
             if id.as_str() == "unlock_custom_title" {
                 inv.unlocked_custom_title = true;
             }
@@ -208,7 +209,16 @@ pub async fn unlock(
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: id,
+                cost: item.cost,
+            },
+        )
         .await;
     Ok(())
 }
@@ -219,35 +229,35 @@ pub async fn unlock(
 
 async fn buyable_titles(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     let p = partial.to_lowercase();
-    TITLES
-        .iter()
+    shop_catalog::current()
+        .active_titles()
         .filter(|t| {
             t.item.name.to_lowercase().contains(&p) || t.item.id.to_lowercase().contains(&p)
         })
         .take(25)
-        .map(|t| AutocompleteChoice::new(t.item.name.to_string(), t.item.id.to_string()))
+        .map(|t| AutocompleteChoice::new(t.item.name.clone(), t.item.id.clone()))
         .collect()
 }
 
 async fn buyable_unlocks(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     let p = partial.to_lowercase();
-    UNLOCKS
-        .iter()
+    shop_catalog::current()
+        .active_unlocks()
         .filter(|u| u.name.to_lowercase().contains(&p) || u.id.to_lowercase().contains(&p))
         .take(25)
-        .map(|u| AutocompleteChoice::new(u.name.to_string(), u.id.to_string()))
+        .map(|u| AutocompleteChoice::new(u.name.clone(), u.id.clone()))
         .collect()
 }
 
 async fn buyable_colorways(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     let p = partial.to_lowercase();
-    COLORWAYS
-        .iter()
+    shop_catalog::current()
+        .active_colorways()
         .filter(|c| {
             c.item.name.to_lowercase().contains(&p) || c.item.id.to_lowercase().contains(&p)
         })
         .take(25)
-        .map(|c| AutocompleteChoice::new(c.item.name.to_string(), c.item.id.to_string()))
+        .map(|c| AutocompleteChoice::new(c.item.name.clone(), c.item.id.clone()))
         .collect()
 }
 
@@ -257,79 +267,80 @@ async fn buyable_colorways(_ctx: Context<'_>, partial: &str) -> Vec<Autocomplete
 
 /// Roll a badge lootbox. Duplicates salvage for tabs.
 ///
-/// Rolls a rarity tier using the `LOOTBOX_CHANCE_*` constants, then picks a
-/// random badge of that rarity from [`LOOTBOX_POOL`]. If the user already
-/// owns that badge, they get [`LOOTBOX_SALVAGE`] tabs back instead.
-#[poise::command(slash_command)]
+/// Rolls a rarity tier using the server's `lootbox_chance_*` weights, then
+/// picks a random active badge of that rarity from the catalog's lootbox
+/// pool. If the user already owns that badge, they get the server's
+/// `lootbox_salvage` tabs back instead.
+#[poise::command(slash_command, guild_only)]
 pub async fn lootbox(ctx: Context<'_>) -> Result {
-    if LOOTBOX_POOL.is_empty() {
+    let catalog = shop_catalog::current();
+    if catalog.active_badges().next().is_none() {
         return Err(
             InventoryError::UnknownItem("lootbox pool is currently empty".into()).into(),
         );
     }
 
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
+    let config = ctx.data().guild_config(guild_id).await;
+    let lootbox = shop_catalog::lootbox_item(config.lootbox_cost());
+    let salvage = config.lootbox_salvage();
 
-    // 1. Charge up front.
-    ctx.data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(LOOTBOX_COST))
-        .await?;
-
-    // 2. Roll rarity + pick badge.
+    // 1. Roll rarity + pick badge. Nothing is charged yet, so this can
+    //    happen outside the transaction.
     let pull = {
         let mut rng = rand::thread_rng();
-        let rarity = roll_rarity(&mut rng);
+        let rarity = roll_rarity(&mut rng, &config);
         // Fallback: if a rarity happens to have no candidates, degrade to
         // Common. Keeps the flow robust against lopsided pool edits.
-        let candidates: Vec<&BadgeDef> = LOOTBOX_POOL
-            .iter()
+        let candidates: Vec<&BadgeDef> = catalog
+            .active_badges()
             .filter(|b| b.item.rarity == rarity)
             .collect();
         let candidates = if candidates.is_empty() {
-            LOOTBOX_POOL
-                .iter()
+            catalog
+                .active_badges()
                 .filter(|b| b.item.rarity == Rarity::Common)
                 .collect::<Vec<_>>()
         } else {
             candidates
         };
         let idx = rng.gen_range(0..candidates.len());
-        *candidates[idx]
+        candidates[idx].clone()
     };
 
-    // 3. Dup check.
+    // 2. Charge, dup check, stats + grant (or salvage) — all or nothing.
     let already_owned = ctx
         .data()
-        .with_inventory_user_read(user_id, |inv| {
-            Ok(inv.owned_badges.iter().any(|b| b == pull.item.id))
-        })
-        .await
-        .unwrap_or(false);
-
-    // 4. Stats + grant (or salvage).
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
+        .transact(|tx| {
+            tx.debit(
+                guild_id,
+                user_id,
+                lootbox.cost,
+                TabMemo::new(LedgerReason::Lootbox).item(&lootbox.id),
+            )?;
+
+            let inv = tx.inventory(guild_id, user_id);
+            let already_owned = inv.owned_badges.contains(&pull.item.id);
             inv.lootboxes_opened = inv.lootboxes_opened.saturating_add(1);
-            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(LOOTBOX_COST);
+            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(lootbox.cost);
             if !already_owned {
-                inv.owned_badges.push(pull.item.id.to_string());
+                inv.owned_badges.push(pull.item.id.clone());
+            } else {
+                tx.credit(
+                    guild_id,
+                    user_id,
+                    salvage,
+                    TabMemo::new(LedgerReason::Salvage).item(&pull.item.id),
+                );
             }
-            Ok(())
+            Ok::<_, InventoryError>(already_owned)
         })
         .await?;
 
-    if already_owned {
-        ctx.data()
-            .with_wallet_user_write(user_id, |w| {
-                w.add_tabs(LOOTBOX_SALVAGE);
-                Ok(())
-            })
-            .await?;
-    }
-
     let message = if already_owned {
         format!(
-            "🔁 **Duplicate!** You rolled {} **{}** ({}). Salvaged for **{LOOTBOX_SALVAGE} {TAB_EMOJI}**.",
+            "🔁 **Duplicate!** You rolled {} **{}** ({}). Salvaged for **{salvage} {TAB_EMOJI}**.",
             pull.emoji,
             pull.item.name,
             rarity_name(pull.item.rarity),
@@ -341,35 +352,45 @@ pub async fn lootbox(ctx: Context<'_>) -> Result {
             pull.item.name,
             pull.item.description,
             rarity_name(pull.item.rarity),
-            (odds_for(pull.item.rarity) * 100.0) as u32,
+            (config.lootbox_odds(pull.item.rarity) * 100.0) as u32,
         )
     };
 
     ctx.send(utils::reply_ok("Lootbox", message)).await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: lootbox.id,
+                cost: lootbox.cost,
+            },
+        )
         .await;
     Ok(())
 }
 
-/// Weighted rarity roll using [`LOOTBOX_CHANCE_*`] constants.
+/// Weighted rarity roll using the guild's lootbox odds (see
+/// [`GuildConfig::lootbox_odds`]).
 ///
 /// Orders checks from rarest to most common so the cumulative probability
 /// comparisons work against a single uniform `[0, 1)` sample.
-fn roll_rarity(rng: &mut impl Rng) -> Rarity {
+fn roll_rarity(rng: &mut impl Rng, config: &GuildConfig) -> Rarity {
     let r: f64 = rng.r#gen();
     let mut threshold = 0.0;
 
-    threshold += LOOTBOX_CHANCE_LEGENDARY;
+    threshold += config.lootbox_odds(Rarity::Legendary);
     if r < threshold {
         return Rarity::Legendary;
     }
-    threshold += LOOTBOX_CHANCE_RARE;
+    threshold += config.lootbox_odds(Rarity::Rare);
     if r < threshold {
         return Rarity::Rare;
     }
-    threshold += LOOTBOX_CHANCE_UNCOMMON;
+    threshold += config.lootbox_odds(Rarity::Uncommon);
     if r < threshold {
         return Rarity::Uncommon;
     }
@@ -385,15 +406,6 @@ fn rarity_name(r: Rarity) -> &'static str {
     }
 }
 
-fn odds_for(r: Rarity) -> f64 {
-    match r {
-        Rarity::Common => LOOTBOX_CHANCE_COMMON,
-        Rarity::Uncommon => LOOTBOX_CHANCE_UNCOMMON,
-        Rarity::Rare => LOOTBOX_CHANCE_RARE,
-        Rarity::Legendary => LOOTBOX_CHANCE_LEGENDARY,
-    }
-}
-
 // ---------------------------------------------------------------------------
 // Per-use role cosmetics
 // ---------------------------------------------------------------------------
@@ -407,8 +419,8 @@ fn odds_for(r: Rarity) -> f64 {
 // Discord API work happens **before** any tab charge so a permission failure
 // or rate-limit on Discord's side never costs the user tabs.
 
-/// Change the colour of your custom colour role. Charged
-/// [`ROLE_COLOR_COST`] tabs every call.
+/// Change the colour of your custom colour role. Costs the server's
+/// `role_color_cost` every call.
 ///
 /// Accepts bare hex (`FF8800`) or `0x`-prefixed (`0xFF8800`). If you don't
 /// have a colour role yet, one is created for you (named after your display
@@ -422,6 +434,7 @@ pub async fn rolecolor(
 ) -> Result {
     let user_id = ctx.author().id;
     let guild_id = ctx.guild_id().unwrap();
+    let cost = ctx.data().guild_config(guild_id).await.role_color_cost();
 
     // Validate before doing any I/O.
     let (color_int, trimmed) =
@@ -454,30 +467,40 @@ pub async fn rolecolor(
     // Charge after Discord API success.
     let tabs = ctx
         .data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(ROLE_COLOR_COST))
-        .await?;
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
-            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(ROLE_COLOR_COST);
-            Ok(())
+        .transact(|tx| {
+            let memo = TabMemo::new(LedgerReason::Purchase).item("rolecolor");
+            let tabs = tx.debit(guild_id, user_id, cost, memo)?;
+            let inv = tx.inventory(guild_id, user_id);
+            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(cost);
+            Ok::<_, InventoryError>(tabs)
         })
         .await?;
 
     ctx.send(utils::reply_ok(
         "Shop Buy Role Color",
         format!(
-            "Your role colour is now `#{trimmed}` for **{ROLE_COLOR_COST} {TAB_EMOJI}**. Balance: **{tabs} {TAB_EMOJI}**.",
+            "Your role colour is now `#{trimmed}` for **{cost} {TAB_EMOJI}**. Balance: **{tabs} {TAB_EMOJI}**.",
         ),
     ))
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: "rolecolor".into(),
+                cost,
+            },
+        )
         .await;
     Ok(())
 }
 
-/// Rename your custom colour role. Charged [`ROLE_NAME_COST`] tabs every call.
+/// Rename your custom colour role. Costs the server's `role_name_cost`
+/// every call.
 ///
 /// If you don't have a colour role yet, one is created with the given name
 /// and no colour (use `/shop buy rolecolor` afterwards to set one).
@@ -488,6 +511,7 @@ pub async fn rolename(
 ) -> Result {
     let user_id = ctx.author().id;
     let guild_id = ctx.guild_id().unwrap();
+    let cost = ctx.data().guild_config(guild_id).await.role_name_cost();
 
     // Zero-width-space prefix marks this as a managed colour role.
     let role_name = format!("\u{200B}{name}");
@@ -508,25 +532,34 @@ pub async fn rolename(
     // Charge after Discord API success.
     let tabs = ctx
         .data()
-        .with_wallet_user_write(user_id, |w| w.remove_tabs(ROLE_NAME_COST))
-        .await?;
-    ctx.data()
-        .with_inventory_user_write(user_id, |inv| {
-            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(ROLE_NAME_COST);
-            Ok(())
+        .transact(|tx| {
+            let memo = TabMemo::new(LedgerReason::Purchase).item("rolename");
+            let tabs = tx.debit(guild_id, user_id, cost, memo)?;
+            let inv = tx.inventory(guild_id, user_id);
+            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(cost);
+            Ok::<_, InventoryError>(tabs)
         })
         .await?;
 
     ctx.send(utils::reply_ok(
         "Shop Buy Role Name",
         format!(
-            "Your role name is now **{name}** for **{ROLE_NAME_COST} {TAB_EMOJI}**. Balance: **{tabs} {TAB_EMOJI}**.",
+            "Your role name is now **{name}** for **{cost} {TAB_EMOJI}**. Balance: **{tabs} {TAB_EMOJI}**.",
         ),
     ))
     .await?;
 
     ctx.data()
-        .check_achievements(user_id, ctx.channel_id(), ctx.http())
+        .publish(
+            ctx.http(),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                channel_id: ctx.channel_id(),
+                item_id: "rolename".into(),
+                cost,
+            },
+        )
         .await;
     Ok(())
 }
@@ -1,7 +1,7 @@
 //! `/shop gift …` subcommands.
 //!
 //! Sending a gift is a *direct purchase for someone else* — the sender pays
-//! the item's cost plus a small [`GIFT_FEE`] and the item is added to the
+//! the item's cost plus the server's small `gift_fee` and the item is added to the
 //! recipient's inventory, even if they've never interacted with the bot
 //! before.
 //!
@@ -11,16 +11,22 @@
 //! # Flow
 //!
 //! 1. Reject self-gifts.
-//! 2. Reject if the recipient already owns the item.
-//! 3. Charge sender (item cost + fee).
-//! 4. Grant to recipient + increment `gifts_received`.
-//! 5. Update sender stats (`gifts_sent`, `tabs_spent_lifetime`).
-//! 6. Post an in-channel announcement (public; sender/recipient mentioned).
+//! 2. In one [`crate::pawthos::structs::data::Data::transact`] call:
+//!    - reject if the recipient already owns the item,
+//!    - charge sender (item cost + fee),
+//!    - grant to recipient + increment `gifts_received`,
+//!    - update sender stats (`gifts_sent`, `tabs_spent_lifetime`).
+//! 3. Post an in-channel announcement (public; sender/recipient mentioned).
+//! 4. Publish [`DomainEvent::GiftSent`].
 
 use crate::pawthos::{
-    consts::{GIFT_FEE, TAB_EMOJI},
+    consts::TAB_EMOJI,
+    enums::domain_event::DomainEvent,
     enums::inventory_errors::InventoryError,
-    structs::shop_catalog::{self, COLORWAYS, TITLES},
+    enums::ledger_reason::LedgerReason,
+    structs::ledger_entry::TabMemo,
+    structs::shop_catalog,
+    traits::InventoryDbMarker,
     types::{Context, Result},
 };
 use poise::serenity_prelude::{self as serenity, AutocompleteChoice};
@@ -32,7 +38,7 @@ pub async fn gift(_ctx: Context<'_>) -> Result {
 }
 
 /// Gift a catalog title to another user.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn title(
     ctx: Context<'_>,
     #[description = "Who to gift"] recipient: serenity::User,
@@ -42,12 +48,15 @@ pub async fn title(
 ) -> Result {
     let def = shop_catalog::lookup_title(&id)
         .ok_or_else(|| InventoryError::UnknownItem(id.clone()))?;
+    if def.item.retired {
+        return Err(InventoryError::Retired(def.item.name).into());
+    }
 
     let gift_context = GiftContext {
         sender: ctx.author(),
         recipient: &recipient,
         item_id: id.clone(),
-        item_name: def.item.name.to_string(),
+        item_name: def.item.name,
         item_cost: def.item.cost,
         category_label: "Title",
     };
@@ -61,7 +70,7 @@ pub async fn title(
 }
 
 /// Gift a named colorway to another user.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, guild_only)]
 pub async fn colorway(
     ctx: Context<'_>,
     #[description = "Who to gift"] recipient: serenity::User,
@@ -71,12 +80,15 @@ pub async fn colorway(
 ) -> Result {
     let def = shop_catalog::lookup_colorway(&id)
         .ok_or_else(|| InventoryError::UnknownItem(id.clone()))?;
+    if def.item.retired {
+        return Err(InventoryError::Retired(def.item.name).into());
+    }
 
     let gift_context = GiftContext {
         sender: ctx.author(),
         recipient: &recipient,
         item_id: id.clone(),
-        item_name: def.item.name.to_string(),
+        item_name: def.item.name,
         item_cost: def.item.cost,
         category_label: "Colorway",
     };
@@ -117,51 +129,47 @@ async fn perform_gift(
     if gc.sender.id == gc.recipient.id {
         return Err(InventoryError::GiftToSelf.into());
     }
+    let guild_id = ctx.guild_id().unwrap();
 
-    let recipient_owns = ctx
-        .data()
-        .with_inventory_user_read(gc.recipient.id, |inv| Ok(already_owns(inv, &gc.item_id)))
-        .await
-        .unwrap_or(false);
-    if recipient_owns {
-        return Err(InventoryError::RecipientOwns(
-            gc.recipient.name.clone(),
-            gc.item_name.clone(),
-        )
-        .into());
-    }
+    let fee = ctx.data().guild_config(guild_id).await.gift_fee();
+    let total = gc.item_cost + fee;
+    let (sender_id, recipient_id) = (gc.sender.id, gc.recipient.id);
 
-    let total = gc.item_cost + GIFT_FEE;
-
-    // 1. Charge sender — propagates NotEnoughTabs.
     ctx.data()
-        .with_wallet_user_write(gc.sender.id, |w| w.remove_tabs(total))
-        .await?;
-
-    // 2. Grant to recipient.
-    ctx.data()
-        .with_inventory_user_write(gc.recipient.id, |inv| {
+        .transact(|tx| {
+            let recipient_owns = tx
+                .get::<InventoryDbMarker>((guild_id, recipient_id))
+                .is_some_and(|inv| already_owns(inv, &gc.item_id));
+            if recipient_owns {
+                return Err(InventoryError::RecipientOwns(
+                    gc.recipient.name.clone(),
+                    gc.item_name.clone(),
+                ));
+            }
+
+            // Charge sender — propagates NotEnoughTabs.
+            let memo = TabMemo::new(LedgerReason::Gift)
+                .counterparty(recipient_id)
+                .item(&gc.item_id);
+            tx.debit(guild_id, sender_id, total, memo)?;
+
+            // Grant to recipient.
+            let inv = tx.inventory(guild_id, recipient_id);
             grant(inv, &gc.item_id);
             inv.gifts_received = inv.gifts_received.saturating_add(1);
-            Ok(())
-        })
-        .await?;
 
-    // 3. Sender-side stats.
-    ctx.data()
-        .with_inventory_user_write(gc.sender.id, |inv| {
+            // Sender-side stats.
+            let inv = tx.inventory(guild_id, sender_id);
             inv.gifts_sent = inv.gifts_sent.saturating_add(1);
             inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(total);
             Ok(())
         })
         .await?;
 
-    // 4. Announce publicly in the invoking channel.
-    let sender_id = gc.sender.id;
-    let recipient_id = gc.recipient.id;
+    // Announce publicly in the invoking channel.
     let announce = format!(
         "🎁 <@{sender_id}> gifted **{}** ({}) to <@{recipient_id}> for **{} {TAB_EMOJI}** (includes **{} {TAB_EMOJI}** fee).",
-        gc.item_name, gc.category_label, total, GIFT_FEE,
+        gc.item_name, gc.category_label, total, fee,
     );
     ctx.send(
         poise::CreateReply::default()
@@ -175,41 +183,47 @@ async fn perform_gift(
     )
     .await?;
 
-    // 5. Achievement checks for both sides — in the same channel.
-    let channel = ctx.channel_id();
-    let http = ctx.http();
-    ctx.data().check_achievements(sender_id, channel, http).await;
     ctx.data()
-        .check_achievements(recipient_id, channel, http)
+        .publish(
+            ctx.http(),
+            DomainEvent::GiftSent {
+                guild_id,
+                sender_id,
+                recipient_id,
+                channel_id: ctx.channel_id(),
+                item_id: gc.item_id,
+            },
+        )
         .await;
 
     Ok(())
 }
 
 // ---------------------------------------------------------------------------
-// Autocomplete — all gifters see the full catalog (you can gift anything).
+// Autocomplete — all gifters see every active item (you can gift anything
+// that's still on sale).
 // ---------------------------------------------------------------------------
 
 async fn giftable_titles(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     let p = partial.to_lowercase();
-    TITLES
-        .iter()
+    shop_catalog::current()
+        .active_titles()
         .filter(|t| {
             t.item.name.to_lowercase().contains(&p) || t.item.id.to_lowercase().contains(&p)
         })
         .take(25)
-        .map(|t| AutocompleteChoice::new(t.item.name.to_string(), t.item.id.to_string()))
+        .map(|t| AutocompleteChoice::new(t.item.name.clone(), t.item.id.clone()))
         .collect()
 }
 
 async fn giftable_colorways(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
     let p = partial.to_lowercase();
-    COLORWAYS
-        .iter()
+    shop_catalog::current()
+        .active_colorways()
         .filter(|c| {
             c.item.name.to_lowercase().contains(&p) || c.item.id.to_lowercase().contains(&p)
         })
         .take(25)
-        .map(|c| AutocompleteChoice::new(c.item.name.to_string(), c.item.id.to_string()))
+        .map(|c| AutocompleteChoice::new(c.item.name.clone(), c.item.id.clone()))
         .collect()
 }
@@ -11,12 +11,11 @@
 use crate::commands::shop::buy::buy;
 use crate::commands::shop::gift::gift;
 use crate::pawthos::{
-    consts::{
-        BANNER_SET_COST, CUSTOM_COLORWAY_SET_COST, ROLE_COLOR_COST, ROLE_NAME_COST, TAB_EMOJI,
-    },
+    consts::TAB_EMOJI,
     enums::embed_type::EmbedType,
+    structs::guild_config::GuildConfig,
     structs::inventory_user::InventoryUser,
-    structs::shop_catalog::{self, COLORWAYS, LOOTBOX_ITEM, LOOTBOX_POOL, TITLES, UNLOCKS},
+    structs::shop_catalog::{self, Rarity},
     types::{Context, Result},
 };
 use crate::utils;
@@ -32,14 +31,21 @@ pub async fn shop(_ctx: Context<'_>) -> Result {
 /// Browse every item in the shop, grouped by category.
 ///
 /// Items are listed by ID (copy-paste friendly), display name, cost, and
-/// a short description. Categories with nothing defined are hidden.
+/// a short description. Categories with nothing defined are hidden, and
+/// retired items are left out. Per-use prices and lootbox odds are this
+/// server's (see `/config`); in DMs the defaults are shown.
 #[poise::command(slash_command)]
 pub async fn browse(ctx: Context<'_>) -> Result {
+    let catalog = shop_catalog::current();
+    let config = match ctx.guild_id() {
+        Some(guild_id) => ctx.data().guild_config(guild_id).await,
+        None => GuildConfig::default(),
+    };
     let mut description = String::new();
 
-    if !TITLES.is_empty() {
+    if catalog.active_titles().next().is_some() {
         description.push_str("**✨ Titles** — a line under your name on `/profile view`\n");
-        for t in TITLES {
+        for t in catalog.active_titles() {
             description.push_str(&format!(
                 "`{}` — **{}** · {} {TAB_EMOJI} — *{}*\n",
                 t.item.id, t.item.name, t.item.cost, t.item.description,
@@ -48,11 +54,11 @@ pub async fn browse(ctx: Context<'_>) -> Result {
         description.push('\n');
     }
 
-    if !COLORWAYS.is_empty() {
+    if catalog.active_colorways().next().is_some() {
         description.push_str(
             "**🎨 Colorways** — owned named colorways equip free; setting a custom hex on your profile costs tabs (see Per-use below).\n",
         );
-        for c in COLORWAYS {
+        for c in catalog.active_colorways() {
             description.push_str(&format!(
                 "`{}` — **{}** · {} {TAB_EMOJI}\n",
                 c.item.id, c.item.name, c.item.cost,
@@ -63,42 +69,44 @@ pub async fn browse(ctx: Context<'_>) -> Result {
 
     description.push_str(&format!(
         "**🛠 Per-use cosmetics** — charged each time you invoke them.\n\
-         `/shop buy rolecolor <hex>` — change your colour role's colour · {ROLE_COLOR_COST} {TAB_EMOJI}\n\
-         `/shop buy rolename <text>` — rename your colour role · {ROLE_NAME_COST} {TAB_EMOJI}\n\
-         `/profile set colorway <hex>` — custom hex profile accent · {CUSTOM_COLORWAY_SET_COST} {TAB_EMOJI}\n\
-         `/profile set banner <url|attachment>` — custom profile banner · {BANNER_SET_COST} {TAB_EMOJI}\n\n",
+         `/shop buy rolecolor <hex>` — change your colour role's colour · {} {TAB_EMOJI}\n\
+         `/shop buy rolename <text>` — rename your colour role · {} {TAB_EMOJI}\n\
+         `/profile set colorway <hex>` — custom hex profile accent · {} {TAB_EMOJI}\n\
+         `/profile set banner <url|attachment>` — custom profile banner · {} {TAB_EMOJI}\n\n",
+        config.role_color_cost(),
+        config.role_name_cost(),
+        config.custom_colorway_set_cost(),
+        config.banner_set_cost(),
     ));
 
-    if !LOOTBOX_POOL.is_empty() {
-        use crate::pawthos::consts::{
-            LOOTBOX_CHANCE_COMMON, LOOTBOX_CHANCE_LEGENDARY, LOOTBOX_CHANCE_RARE,
-            LOOTBOX_CHANCE_UNCOMMON, LOOTBOX_SALVAGE,
-        };
-        use crate::pawthos::structs::shop_catalog::Rarity;
+    if catalog.active_badges().next().is_some() {
+        let lootbox = shop_catalog::lootbox_item(config.lootbox_cost());
+        let odds = |r: Rarity| config.lootbox_odds(r) * 100.0;
         let count_of = |r: Rarity| -> usize {
-            LOOTBOX_POOL.iter().filter(|b| b.item.rarity == r).count()
+            catalog.active_badges().filter(|b| b.item.rarity == r).count()
         };
         description.push_str(&format!(
             "**🎁 Badge Lootbox** — `/shop buy lootbox` · {} {TAB_EMOJI} per pull\n\
              *{}*\n\
-             Duplicates salvage for **{LOOTBOX_SALVAGE} {TAB_EMOJI}**.\n\n\
+             Duplicates salvage for **{} {TAB_EMOJI}**.\n\n\
              **Odds:**\n\
              🟢 Common {:.0}% — {} items\n\
              🔵 Uncommon {:.0}% — {} items\n\
              🟣 Rare {:.0}% — {} items\n\
              🟡 Legendary {:.0}% — {} items\n\n",
-            LOOTBOX_ITEM.cost,
-            LOOTBOX_ITEM.description,
-            LOOTBOX_CHANCE_COMMON * 100.0, count_of(Rarity::Common),
-            LOOTBOX_CHANCE_UNCOMMON * 100.0, count_of(Rarity::Uncommon),
-            LOOTBOX_CHANCE_RARE * 100.0, count_of(Rarity::Rare),
-            LOOTBOX_CHANCE_LEGENDARY * 100.0, count_of(Rarity::Legendary),
+            lootbox.cost,
+            lootbox.description,
+            config.lootbox_salvage(),
+            odds(Rarity::Common), count_of(Rarity::Common),
+            odds(Rarity::Uncommon), count_of(Rarity::Uncommon),
+            odds(Rarity::Rare), count_of(Rarity::Rare),
+            odds(Rarity::Legendary), count_of(Rarity::Legendary),
         ));
     }
 
-    if !UNLOCKS.is_empty() {
+    if catalog.active_unlocks().next().is_some() {
         description.push_str("**🔓 Unlocks** — enable custom `/profile set …` commands\n");
-        for u in UNLOCKS {
+        for u in catalog.active_unlocks() {
             description.push_str(&format!(
                 "`{}` — **{}** · {} {TAB_EMOJI} — *{}*\n",
                 u.id, u.name, u.cost, u.description,
@@ -117,13 +125,14 @@ pub async fn browse(ctx: Context<'_>) -> Result {
 
 /// Show what you own: titles, colorways, banners, badges, unlocks.
 ///
-/// Response is ephemeral — only you can see it.
-#[poise::command(slash_command)]
+/// Inventories are per server. Response is ephemeral — only you can see it.
+#[poise::command(slash_command, guild_only)]
 pub async fn inventory(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let inv = ctx
         .data()
-        .with_inventory_user_read(user_id, |i| Ok(i.clone()))
+        .with_inventory_user_read(guild_id, user_id, |i| Ok(i.clone()))
         .await
         .unwrap_or_default();
 
@@ -166,7 +175,7 @@ fn render_titles(inv: &InventoryUser) -> String {
         .owned_titles
         .iter()
         .map(|id| match shop_catalog::lookup_title(id) {
-            Some(t) => format!("• **{}**", t.item.name),
+            Some(t) => format!("• **{}**{}", t.item.name, retired_tag(&t.item)),
             None => format!("• `{id}` *(unknown)*"),
         })
         .collect();
@@ -183,7 +192,12 @@ fn render_colorways(inv: &InventoryUser) -> String {
     inv.owned_colorways
         .iter()
         .map(|id| match shop_catalog::lookup_colorway(id) {
-            Some(c) => format!("• **{}** · `#{:06X}`", c.item.name, c.hex),
+            Some(c) => format!(
+                "• **{}** · `#{:06X}`{}",
+                c.item.name,
+                c.hex,
+                retired_tag(&c.item)
+            ),
             None => format!("• `{id}` *(unknown)*"),
         })
         .collect::<Vec<_>>()
@@ -204,7 +218,7 @@ fn render_badges(inv: &InventoryUser) -> String {
         let line = lootbox
             .iter()
             .map(|id| match shop_catalog::lookup_badge(id) {
-                Some(b) => format!("{} {}", b.emoji, b.item.name),
+                Some(b) => format!("{} {}{}", b.emoji, b.item.name, retired_tag(&b.item)),
                 None => format!("`{id}`"),
             })
             .collect::<Vec<_>>()
@@ -222,6 +236,11 @@ fn render_badges(inv: &InventoryUser) -> String {
     sections.join("\n")
 }
 
+/// ` *(retired)*` for items no longer in the catalog file, else nothing.
+fn retired_tag(item: &shop_catalog::ShopItem) -> &'static str {
+    if item.retired { " *(retired)*" } else { "" }
+}
+
 fn render_unlocks(inv: &InventoryUser) -> String {
     if inv.unlocked_custom_title {
         "• Custom Title".into()
@@ -0,0 +1,197 @@
+//! `/time` command suite — times in other members' timezones.
+//!
+//! Uses the home timezone each member picked with `/schedule set_tz` (see
+//! [`crate::pawthos::structs::schedule_user::ScheduleUser::home_timezone`]).
+//! Times are shown both as Discord `<t:…>` timestamps, which every reader
+//! sees in their own local time, and spelled out per timezone.
+//!
+//! # Commands
+//! - [`time`] — parent command.
+//! - [`convert`] — show a time for the members mentioned, or everyone here.
+//! - [`now`] — show another member's local time.
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use crate::commands::schedule::fetch_timezones;
+use crate::pawthos::consts::{TIME_CONVERT_MENTIONS_SHOWN, TIME_CONVERT_ZONES_SHOWN};
+use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::structs::time_input;
+use crate::pawthos::types::{Context, Result};
+use crate::utils;
+use chrono::{DateTime, Offset, Utc};
+use chrono_tz::Tz;
+use poise::serenity_prelude::{self as serenity, UserId};
+
+/// Time suite of commands for working across members' timezones.
+#[poise::command(slash_command, subcommands("convert", "now"), subcommand_required)]
+pub async fn time(_ctx: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Show a time in the local time of the members you mention, or everyone
+/// here.
+///
+/// Without `members`, that's everyone in this channel who has set a
+/// timezone. `when` takes the same forms as `/schedule add` (past times
+/// are fine too) and is read in `from_tz`, or in your own timezone if you
+/// leave it out. Mentioned members who haven't set a timezone are listed
+/// at the end.
+#[poise::command(slash_command)]
+pub async fn convert(
+    ctx: Context<'_>,
+    #[description = "When, e.g. \"friday 8pm\", \"in 2 hours\", \"2026-05-01 14:00\""] when: String,
+    #[description = "Timezone `when` is in (default: yours)"]
+    #[autocomplete = "fetch_timezones"]
+    from_tz: Option<String>,
+    #[description = "Members to show it for, e.g. @alice @bob (default: everyone here)"]
+    members: Option<String>,
+) -> Result {
+    let timezones: HashMap<UserId, Tz> = ctx.data().home_timezones().await.into_iter().collect();
+    let from_tz = match from_tz {
+        Some(name) => {
+            Tz::from_str(&name).map_err(|e| ScheduleError::InvalidTimezone(e.to_string()))?
+        }
+        None => timezones.get(&ctx.author().id).copied().unwrap_or_default(),
+    };
+    let when = time_input::interpret(&when, Utc::now(), from_tz)?.to_utc();
+
+    let people = match members.as_deref() {
+        Some(raw) => mentioned(raw)?,
+        None => here(ctx, &timezones),
+    };
+    let (zoned, unset): (Vec<UserId>, Vec<UserId>) = people
+        .into_iter()
+        .partition(|id| timezones.contains_key(id));
+
+    // Group by timezone, west to east.
+    let mut zones: BTreeMap<(i32, &str), (Tz, Vec<UserId>)> = BTreeMap::new();
+    for id in zoned {
+        let tz = timezones[&id];
+        let offset = when.with_timezone(&tz).offset().fix().local_minus_utc();
+        zones
+            .entry((offset, tz.name()))
+            .or_insert_with(|| (tz, Vec::new()))
+            .1
+            .push(id);
+    }
+
+    let mut message = format!("<t:{0}:F> (<t:{0}:R>)\n", when.timestamp());
+    for (tz, ids) in zones.values().take(TIME_CONVERT_ZONES_SHOWN) {
+        message += &format!("\n{} — {}", local_time(when, *tz), mentions(ids));
+    }
+    if zones.len() > TIME_CONVERT_ZONES_SHOWN {
+        message += &format!(
+            "\n…and {} more timezones.",
+            zones.len() - TIME_CONVERT_ZONES_SHOWN
+        );
+    }
+    if zones.is_empty() {
+        message += "\nNobody here has set a timezone with `/schedule set_tz` yet.";
+    }
+    if !unset.is_empty() {
+        message += &format!("\n\nNo timezone set: {}", mentions(&unset));
+    }
+
+    ctx.send(utils::reply_info("Time Convert", message)).await?;
+    Ok(())
+}
+
+/// Show another member's local time, from the timezone they set with
+/// `/schedule set_tz`.
+#[poise::command(slash_command)]
+pub async fn now(
+    ctx: Context<'_>,
+    #[description = "Member whose time to show"] user: serenity::User,
+) -> Result {
+    let tz = ctx
+        .data()
+        .with_schedule_user_read(user.id, |u| Ok(u.home_timezone()))
+        .await
+        .ok()
+        .flatten()
+        .ok_or(ScheduleError::NoTimezone(user.id))?;
+
+    ctx.send(utils::reply_info(
+        "Time Now",
+        format!("It's {} for <@{}>.", local_time(Utc::now(), tz), user.id),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// The users mentioned in `raw`, in order, without repeats.
+fn mentioned(raw: &str) -> std::result::Result<Vec<UserId>, ScheduleError> {
+    let mut ids = Vec::new();
+    for id in raw
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(serenity::parse_user_mention)
+    {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.is_empty() {
+        return Err(ScheduleError::NoMembersMentioned);
+    }
+    Ok(ids)
+}
+
+/// The caller plus everyone with a timezone who can see this channel, as
+/// far as the cache knows. Outside a server, just the caller.
+fn here(ctx: Context<'_>, timezones: &HashMap<UserId, Tz>) -> Vec<UserId> {
+    let author = ctx.author().id;
+    let Some(guild) = ctx.guild() else {
+        return vec![author];
+    };
+    // A thread's members are whoever can see its parent.
+    let channel_id = guild
+        .threads
+        .iter()
+        .find(|t| t.id == ctx.channel_id())
+        .and_then(|t| t.parent_id)
+        .unwrap_or(ctx.channel_id());
+    let Some(channel) = guild.channels.get(&channel_id) else {
+        return vec![author];
+    };
+
+    let mut people: Vec<UserId> = timezones
+        .keys()
+        .filter(|id| **id != author)
+        .filter(|id| {
+            guild
+                .members
+                .get(id)
+                .is_some_and(|m| guild.user_permissions_in(channel, m).view_channel())
+        })
+        .copied()
+        .collect();
+    people.insert(0, author);
+    people
+}
+
+/// `**Fri 1 May 20:00** Europe/London (UTC+01:00)`.
+fn local_time(when: DateTime<Utc>, tz: Tz) -> String {
+    let local = when.with_timezone(&tz);
+    format!(
+        "**{}** {} (UTC{})",
+        local.format("%a %-d %b %H:%M"),
+        tz.name(),
+        local.format("%:z")
+    )
+}
+
+/// Mention the first [`TIME_CONVERT_MENTIONS_SHOWN`] of `ids` and count the
+/// rest.
+fn mentions(ids: &[UserId]) -> String {
+    let mut list = ids
+        .iter()
+        .take(TIME_CONVERT_MENTIONS_SHOWN)
+        .map(|id| format!("<@{id}>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if ids.len() > TIME_CONVERT_MENTIONS_SHOWN {
+        list += &format!(" +{} more", ids.len() - TIME_CONVERT_MENTIONS_SHOWN);
+    }
+    list
+}
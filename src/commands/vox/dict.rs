@@ -0,0 +1,115 @@
+//! `/vox dict …` subcommands — the server's pronunciation dictionary.
+//!
+//! Each server keeps its own list of words and how DECtalk should say them,
+//! stored in [`crate::pawthos::structs::guild_dictionary`]. `/vox say`
+//! compiles the list into a DECtalk user dictionary (see
+//! [`crate::dectalk::dictionary`]) and the engine loads it before speaking.
+//! Changing the list needs moderator permission; anyone can read it.
+
+use crate::checks;
+use crate::dectalk::dictionary;
+use crate::pawthos::consts::VOX_DICT_LIST_SHOWN;
+use crate::pawthos::types::{Context, Result};
+use crate::utils;
+use poise::serenity_prelude::AutocompleteChoice;
+
+/// This server's pronunciations for names and jargon, used by `/vox say`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("add", "remove", "list"),
+    subcommand_required
+)]
+pub async fn dict(_ctx: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Autocomplete the words in this server's dictionary.
+async fn fetch_words(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.to_ascii_lowercase();
+    ctx.data()
+        .guild_dictionary(guild_id)
+        .await
+        .entries
+        .into_keys()
+        .filter(|word| word.starts_with(&partial))
+        .map(|word| AutocompleteChoice::new(word.clone(), word))
+        .collect()
+}
+
+/// Teach DECtalk how to say a word in this server, or change how it says it.
+///
+/// `pronunciation` is in DECtalk's arpabet, with `'` before the stressed
+/// vowel — e.g. `p 'aw th ow s`. Only English (US) speech uses it.
+#[poise::command(slash_command, guild_only, check = "checks::moderator")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Word to pronounce, e.g. a member's name"] word: String,
+    #[description = "How to say it in DECtalk arpabet, e.g. \"p 'aw th ow s\""]
+    pronunciation: String,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let word = dictionary::normalise_word(&word)?;
+    let pronunciation = pronunciation.trim().to_string();
+    dictionary::phonemes(&pronunciation)?;
+
+    let previous = ctx
+        .data()
+        .with_guild_dictionary_write(guild_id, |d| d.add(word.clone(), pronunciation.clone()))
+        .await?;
+    let message = match previous {
+        Some(previous) => format!("`{word}` is now said `{pronunciation}` (was `{previous}`)."),
+        None => format!("`{word}` is now said `{pronunciation}`."),
+    };
+    ctx.send(utils::reply_ok("Vox Dictionary", message)).await?;
+    Ok(())
+}
+
+/// Go back to DECtalk's own pronunciation of a word.
+#[poise::command(slash_command, guild_only, check = "checks::moderator")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Word to remove"]
+    #[autocomplete = "fetch_words"]
+    word: String,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let word = word.trim().to_ascii_lowercase();
+    let pronunciation = ctx
+        .data()
+        .with_guild_dictionary_write(guild_id, |d| d.remove(&word))
+        .await?;
+    ctx.send(utils::reply_ok(
+        "Vox Dictionary",
+        format!("Removed `{word}` (was `{pronunciation}`)."),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// List the words in this server's dictionary and how they're said.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let entries = ctx.data().guild_dictionary(guild_id).await.entries;
+
+    let mut message = entries
+        .iter()
+        .take(VOX_DICT_LIST_SHOWN)
+        .map(|(word, pronunciation)| format!("`{word}` — `{pronunciation}`"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if entries.len() > VOX_DICT_LIST_SHOWN {
+        message += &format!("\n…and {} more.", entries.len() - VOX_DICT_LIST_SHOWN);
+    }
+    if entries.is_empty() {
+        message = "No words yet — a moderator can add some with `/vox dict add`.".to_string();
+    }
+
+    ctx.send(utils::reply_info("Vox Dictionary", message))
+        .await?;
+    Ok(())
+}
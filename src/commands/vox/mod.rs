@@ -0,0 +1,289 @@
+//! `/vox` command suite — DECtalk text-to-speech synthesis.
+//!
+//! DECtalk runs synchronous blocking C calls that must not execute on the
+//! async executor, so speech is synthesised into memory on the bot's
+//! [`crate::dectalk::pool::Pool`] of engine threads; the audio is encoded as
+//! a WAV in the chosen [`WaveFormat`], or as an Ogg Opus voice clip, and
+//! uploaded straight from memory.
+//!
+//! Each user can save a default [`Voice`] with `/vox voice`; options given to
+//! `/vox say` override it setting by setting. Text is cleaned up first by
+//! [`crate::dectalk::text`], within the server's `vox_*` settings.
+//!
+//! With `live`, `/vox say` speaks in the caller's voice channel instead,
+//! through the guild's queue in [`crate::voice::player`]; `/vox skip` and
+//! `/vox stop` control that queue.
+//!
+//! # Sub-modules
+//! - [`dict`] — the server's pronunciation dictionary
+//!   (`/vox dict add|remove|list`), loaded for every `/vox say` there.
+
+pub mod dict;
+
+use crate::audio::{opus, resample};
+use crate::commands::vox::dict::dict;
+use crate::dectalk::text::{self, Mention};
+use crate::dectalk::{DectalkError, Language, Rate, Speaker, Speech, Voice, Volume, WaveFormat};
+use crate::pawthos::consts::VOX_IDLE_DISCONNECT_SECS;
+use crate::pawthos::enums::vox_errors::VoxError;
+use crate::pawthos::structs::guild_config::GuildConfig;
+use crate::pawthos::types::{Context, Result};
+use crate::utils;
+use crate::voice::{self, player::PlaybackCommand};
+use poise::serenity_prelude::{self as serenity, ChannelId, GuildId, RoleId, UserId};
+use std::time::Duration;
+
+/// Voice synthesis commands powered by DECtalk.
+///
+/// This is a parent command; use `/vox say` to synthesise speech.
+#[poise::command(
+    slash_command,
+    subcommands("say", "skip", "stop", "voice", "reset_voice", "dict"),
+    subcommand_required
+)]
+pub async fn vox(_: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Synthesise text as speech using the DECtalk TTS engine and post the WAV.
+///
+/// The audio is generated by one of the DECtalk worker threads (see
+/// [`crate::dectalk::pool`]) to avoid stalling the async executor during the
+/// synchronous DECtalk calls. The audio is encoded as a WAV in memory and
+/// attached to the reply; with `voice_clip` it's encoded as Ogg Opus instead,
+/// the format of Discord voice messages, which it plays inline.
+///
+/// DECtalk supports its own markup language for controlling prosody, pitch,
+/// and speaking rate — e.g. `[:rate 200]` sets the words-per-minute.
+///
+/// Any voice option left out comes from your saved `/vox voice`, then from
+/// DECtalk's defaults. In a server, words in its `/vox dict` are said the
+/// way it gives. Mentions and emoji are read as names, and the server's
+/// `/config` can cap the length and turn off `[:...]` commands. With
+/// `live`, it's spoken in your voice channel instead, after anything
+/// already queued there.
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)] // one argument per slash option
+pub async fn say(
+    ctx: Context<'_>,
+    #[description = "Text to synthesize"] text: String,
+    #[description = "Built-in voice to speak with"] speaker: Option<Speaker>,
+    #[description = "Speaking rate in words per minute"]
+    #[min = 75]
+    #[max = 600]
+    rate: Option<u32>,
+    #[description = "Volume, 0-100"]
+    #[max = 100]
+    volume: Option<u32>,
+    #[description = "Language to speak in"] language: Option<Language>,
+    #[description = "Speak it in your voice channel instead of posting a file"] live: Option<bool>,
+    #[description = "Audio format DECtalk speaks in"] format: Option<WaveFormat>,
+    #[description = "Post an Ogg Opus voice clip instead of a WAV"] voice_clip: Option<bool>,
+) -> Result {
+    let target = match live {
+        Some(true) => Some(voice_channel(ctx)?),
+        _ => None,
+    };
+    if target.is_some() {
+        // Joining the channel can take a few seconds.
+        ctx.defer().await?;
+    }
+    let saved = saved_voice(ctx).await;
+    let voice = voice_from(speaker, rate, volume, language)?.or(saved);
+
+    let text = spoken_text(ctx, &text).await;
+    let dictionary = match ctx.guild_id() {
+        Some(guild_id) => ctx.data().guild_dictionary(guild_id).await.compile()?,
+        None => None,
+    };
+    let format = format.unwrap_or_default();
+    let speech = ctx
+        .data()
+        .tts
+        .speak(text, voice, dictionary, format)
+        .await?;
+
+    if let Some((guild_id, channel_id)) = target {
+        return speak_live(ctx, guild_id, channel_id, &speech).await;
+    }
+    let attachment = match voice_clip {
+        Some(true) => {
+            let ogg = opus::encode(&speech.samples(), format.sample_rate())?;
+            serenity::CreateAttachment::bytes(ogg, "dectalk.ogg")
+        }
+        _ => serenity::CreateAttachment::bytes(speech.wav(), "dectalk.wav"),
+    };
+    ctx.send(poise::CreateReply::default().attachment(attachment))
+        .await?;
+    Ok(())
+}
+
+/// Skip what's being said in the voice channel and go on to the next in the
+/// queue.
+#[poise::command(slash_command, guild_only)]
+pub async fn skip(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().ok_or(VoxError::NotPlaying)?;
+    ctx.data().voice.send(guild_id, PlaybackCommand::Skip)?;
+    ctx.send(utils::reply_ok("Vox", "Skipped.")).await?;
+    Ok(())
+}
+
+/// Stop speaking, clear the queue and leave the voice channel.
+#[poise::command(slash_command, guild_only)]
+pub async fn stop(ctx: Context<'_>) -> Result {
+    let guild_id = ctx.guild_id().ok_or(VoxError::NotPlaying)?;
+    ctx.data().voice.send(guild_id, PlaybackCommand::Stop)?;
+    ctx.send(utils::reply_ok(
+        "Vox",
+        "Stopped and left the voice channel.",
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Save the voice `/vox say` uses by default, or show it if no options are
+/// given.
+///
+/// Only the settings you give are changed; use `/vox reset_voice` to go back
+/// to DECtalk's defaults.
+#[poise::command(slash_command)]
+pub async fn voice(
+    ctx: Context<'_>,
+    #[description = "Built-in voice to speak with"] speaker: Option<Speaker>,
+    #[description = "Speaking rate in words per minute"]
+    #[min = 75]
+    #[max = 600]
+    rate: Option<u32>,
+    #[description = "Volume, 0-100"]
+    #[max = 100]
+    volume: Option<u32>,
+    #[description = "Language to speak in"] language: Option<Language>,
+) -> Result {
+    let changes = voice_from(speaker, rate, volume, language)?;
+    if changes == Voice::default() {
+        let saved = saved_voice(ctx).await;
+        ctx.send(utils::reply_info(
+            "Vox Voice",
+            format!("Your voice: {saved}."),
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let saved = ctx
+        .data()
+        .with_vox_user_write(ctx.author().id, |u| {
+            u.voice = changes.or(u.voice);
+            Ok(u.voice)
+        })
+        .await?;
+    ctx.send(utils::reply_ok(
+        "Vox Voice",
+        format!("Your voice is now: {saved}."),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Forget your saved voice and go back to DECtalk's defaults.
+#[poise::command(slash_command)]
+pub async fn reset_voice(ctx: Context<'_>) -> Result {
+    ctx.data()
+        .with_vox_user_write(ctx.author().id, |u| {
+            u.voice = Voice::default();
+            Ok(())
+        })
+        .await?;
+    ctx.send(utils::reply_ok(
+        "Vox Voice",
+        "Your voice is back to DECtalk's defaults.",
+    ))
+    .await?;
+    Ok(())
+}
+
+/// The voice channel the caller is in, as far as the cache knows.
+fn voice_channel(ctx: Context<'_>) -> std::result::Result<(GuildId, ChannelId), VoxError> {
+    let guild = ctx.guild().ok_or(VoxError::NotInVoice)?;
+    let channel_id = guild
+        .voice_states
+        .get(&ctx.author().id)
+        .and_then(|state| state.channel_id)
+        .ok_or(VoxError::NotInVoice)?;
+    Ok((guild.id, channel_id))
+}
+
+/// Queue DECtalk `speech` on the guild's player, joining `channel_id` and
+/// starting one if there isn't one yet.
+async fn speak_live(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    speech: &Speech,
+) -> Result {
+    let audio = resample::to_discord(&speech.samples(), speech.format.sample_rate());
+    let players = &ctx.data().voice;
+    let message = match players.speak(guild_id, channel_id, audio)? {
+        None => format!("Queued in <#{channel_id}>."),
+        Some(audio) => {
+            let connection = voice::connect(ctx.serenity_context(), guild_id, channel_id).await?;
+            let idle = Duration::from_secs(VOX_IDLE_DISCONNECT_SECS);
+            players.start(guild_id, channel_id, connection, audio, idle);
+            format!("Speaking in <#{channel_id}>.")
+        }
+    };
+    ctx.send(utils::reply_ok("Vox", message)).await?;
+    Ok(())
+}
+
+/// `text` as DECtalk should get it, within this server's `/vox` settings:
+/// mentions named from the cache, then [`text::prepare`]'s other steps.
+async fn spoken_text(ctx: Context<'_>, text: &str) -> String {
+    let config = match ctx.guild_id() {
+        Some(guild_id) => ctx.data().guild_config(guild_id).await,
+        None => GuildConfig::default(),
+    };
+    let options = text::Options {
+        max_chars: config.vox_max_length(),
+        inline_commands: config.vox_inline_commands(),
+    };
+    let guild = ctx.guild();
+    text::prepare(text, options, |mention| match mention {
+        Mention::User(id) => {
+            let id = UserId::new(id);
+            guild
+                .as_ref()
+                .and_then(|g| g.members.get(&id).map(|m| m.display_name().to_string()))
+                .or_else(|| ctx.cache().user(id).map(|u| u.display_name().to_string()))
+        }
+        Mention::Channel(id) => guild
+            .as_ref()
+            .and_then(|g| g.channels.get(&ChannelId::new(id)).map(|c| c.name.clone())),
+        Mention::Role(id) => guild
+            .as_ref()
+            .and_then(|g| g.roles.get(&RoleId::new(id)).map(|r| r.name.clone())),
+    })
+}
+
+/// The caller's saved voice; all defaults if they never saved one.
+async fn saved_voice(ctx: Context<'_>) -> Voice {
+    ctx.data()
+        .with_vox_user_read(ctx.author().id, |u| Ok(u.voice))
+        .await
+        .unwrap_or_default()
+}
+
+/// A [`Voice`] from slash options, checking the numbers are in range.
+fn voice_from(
+    speaker: Option<Speaker>,
+    rate: Option<u32>,
+    volume: Option<u32>,
+    language: Option<Language>,
+) -> Result<Voice, DectalkError> {
+    Ok(Voice {
+        speaker,
+        rate: rate.map(Rate::try_from).transpose()?,
+        volume: volume.map(Volume::try_from).transpose()?,
+        language,
+    })
+}
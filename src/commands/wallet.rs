@@ -0,0 +1,329 @@
+//! `/wallet` command suite and `/pay` — move tabs and look back over where
+//! they went.
+//!
+//! # Commands in this file
+//! - [`pay`] — send tabs to another member, with a fee and a daily cap.
+//! - [`wallet`] — parent command (required by Poise).
+//! - [`history`] — page through your own ledger entries in this server.
+//! - [`audit`] — moderator-only prefix command to page through the whole
+//!   server's ledger, optionally filtered by [`LedgerReason`].
+//!
+//! Entries come from the guild's append-only ledger (see
+//! [`crate::pawthos::structs::ledger_entry::LedgerEntry`]) and are always
+//! shown newest first.
+
+use crate::checks;
+use crate::pawthos::{
+    consts::{LEDGER_PAGE_SIZE, MAX_PAY_NOTE_LEN, PAY_CONFIRM_TIMEOUT_SECS, TAB_EMOJI},
+    enums::domain_event::DomainEvent,
+    enums::embed_type::EmbedType,
+    enums::ledger_reason::LedgerReason,
+    enums::wallet_errors::WalletError,
+    structs::ledger_entry::{LedgerEntry, TabMemo},
+    traits::WalletDbMarker,
+    types::{Context, Result},
+};
+use crate::utils;
+use poise::ChoiceParameter;
+use poise::serenity_prelude as serenity;
+use std::time::Duration;
+
+// ---------------------------------------------------------------------------
+// /pay
+// ---------------------------------------------------------------------------
+
+/// Send tabs to another member of this server.
+///
+/// The sender also pays a small transfer fee (a percentage of the amount,
+/// rounded up) and can send only so much per day. Payments above a
+/// threshold ask for confirmation first. The fee, cap and threshold are set
+/// per server with `/config`. Successful payments are announced in the
+/// channel, like gifts.
+#[poise::command(slash_command, guild_only)]
+pub async fn pay(
+    ctx: Context<'_>,
+    #[description = "Who to pay"] recipient: serenity::User,
+    #[description = "How many tabs to send"]
+    #[min = 1]
+    amount: i64,
+    #[description = "Optional note shown with the payment"]
+    #[max_length = 100]
+    note: Option<String>,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let (sender_id, recipient_id) = (ctx.author().id, recipient.id);
+    if sender_id == recipient_id {
+        return Err(WalletError::PayToSelf.into());
+    }
+    if recipient.bot {
+        return Err(WalletError::PayToBot.into());
+    }
+    let note = note.map(|n| n.chars().take(MAX_PAY_NOTE_LEN).collect::<String>());
+    let config = ctx.data().guild_config(guild_id).await;
+    let fee = pay_fee(amount, config.pay_fee_percent());
+    let total = amount.saturating_add(fee);
+
+    // Refuse before asking for confirmation. The transaction below checks
+    // again, since either can change while the buttons wait.
+    let cap = config.pay_daily_cap();
+    let (remaining, balance) = ctx
+        .data()
+        .with_wallet_user_read(guild_id, sender_id, |w| Ok((w.pay_allowance(cap), w.tabs)))
+        .await
+        .unwrap_or((cap, 0));
+    if amount > remaining {
+        return Err(WalletError::PayCapExceeded { cap, remaining }.into());
+    }
+    if total > balance {
+        return Err(WalletError::NotEnoughTabs {
+            cost: total,
+            balance,
+        }
+        .into());
+    }
+
+    if amount >= config.pay_confirm_threshold()
+        && !confirm_pay(ctx, recipient_id, amount, fee).await?
+    {
+        return Ok(());
+    }
+
+    ctx.data()
+        .transact(|tx| {
+            tx.get_mut::<WalletDbMarker>((guild_id, sender_id))
+                .use_pay_allowance(amount, cap)?;
+
+            // Charge sender — propagates NotEnoughTabs.
+            let memo = TabMemo::new(LedgerReason::Transfer).counterparty(recipient_id);
+            tx.debit(guild_id, sender_id, total, memo)?;
+
+            let memo = TabMemo::new(LedgerReason::Transfer).counterparty(sender_id);
+            tx.credit(guild_id, recipient_id, amount, memo);
+
+            let inv = tx.inventory(guild_id, sender_id);
+            inv.transfers_sent = inv.transfers_sent.saturating_add(1);
+            inv.tabs_spent_lifetime = inv.tabs_spent_lifetime.saturating_add(fee);
+            Ok::<_, WalletError>(())
+        })
+        .await?;
+
+    // Announce publicly in the invoking channel.
+    let mut announce = format!(
+        "💸 <@{sender_id}> paid **{amount} {TAB_EMOJI}** to <@{recipient_id}> (plus **{fee} {TAB_EMOJI}** fee).",
+    );
+    if let Some(note) = note {
+        announce.push_str(&format!("\n> {note}"));
+    }
+    ctx.send(
+        poise::CreateReply::default()
+            .content(announce)
+            .allowed_mentions(
+                serenity::CreateAllowedMentions::default()
+                    .users(vec![sender_id, recipient_id])
+                    .everyone(false)
+                    .all_roles(false),
+            ),
+    )
+    .await?;
+
+    ctx.data()
+        .publish(
+            ctx.http(),
+            DomainEvent::PaymentSent {
+                guild_id,
+                sender_id,
+                recipient_id,
+                channel_id: ctx.channel_id(),
+                amount,
+            },
+        )
+        .await;
+    Ok(())
+}
+
+/// The `/pay` fee for sending `amount` tabs: `percent` of it, rounded up.
+///
+/// Worked out per whole hundred and on the remainder, so it can't overflow
+/// for any `amount` while `percent` stays within `/config`'s 0–100.
+fn pay_fee(amount: i64, percent: i64) -> i64 {
+    amount / 100 * percent + (amount % 100 * percent + 99) / 100
+}
+
+/// Ask the sender to confirm a large payment with buttons. Nothing has been
+/// charged yet; returns whether they pressed "Pay".
+///
+/// The prompt is ephemeral and is replaced with the outcome once answered or
+/// after [`PAY_CONFIRM_TIMEOUT_SECS`].
+async fn confirm_pay(
+    ctx: Context<'_>,
+    recipient_id: serenity::UserId,
+    amount: i64,
+    fee: i64,
+) -> std::result::Result<bool, serenity::Error> {
+    let confirm_id = format!("{}:pay_confirm", ctx.id());
+    let cancel_id = format!("{}:pay_cancel", ctx.id());
+
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&confirm_id)
+            .label("Pay")
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(&cancel_id)
+            .label("Cancel")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+    let prompt = ctx
+        .send(
+            poise::CreateReply::default()
+                .content(format!(
+                    "Send **{amount} {TAB_EMOJI}** to <@{recipient_id}>? The fee is **{fee} {TAB_EMOJI}**, so you'll pay **{} {TAB_EMOJI}** in total.",
+                    amount.saturating_add(fee),
+                ))
+                .components(vec![buttons])
+                .ephemeral(true),
+        )
+        .await?;
+
+    let ids = (confirm_id.clone(), cancel_id);
+    let press = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id == ids.0 || i.data.custom_id == ids.1)
+        .timeout(Duration::from_secs(PAY_CONFIRM_TIMEOUT_SECS))
+        .await;
+
+    let (confirmed, outcome) = match &press {
+        Some(p) if p.data.custom_id == confirm_id => (true, "Confirmed — sending…"),
+        Some(_) => (false, "Payment cancelled."),
+        None => (false, "Payment timed out — nothing was sent."),
+    };
+    let cleared = poise::CreateReply::default()
+        .content(outcome)
+        .components(Vec::new());
+    match press {
+        Some(p) => {
+            p.create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(outcome)
+                        .components(Vec::new()),
+                ),
+            )
+            .await?;
+        }
+        None => prompt.edit(ctx, cleared).await?,
+    }
+    Ok(confirmed)
+}
+
+/// Wallet commands — see your tab history.
+#[poise::command(slash_command, subcommands("history"))]
+pub async fn wallet(_ctx: Context<'_>) -> Result {
+    Ok(())
+}
+
+/// Show every tab you've earned or spent in this server, newest first.
+///
+/// Ten entries per page. Response is ephemeral — only you can see it.
+#[poise::command(slash_command, guild_only)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "Page to show (starts at 1)"]
+    #[min = 1]
+    page: Option<usize>,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let user_id = ctx.author().id;
+    let page = page.unwrap_or(1);
+
+    let (entries, total) = ctx
+        .data()
+        .ledger_page(guild_id, |e| e.user_id == user_id, page - 1, LEDGER_PAGE_SIZE)
+        .await;
+
+    let description = if total == 0 {
+        "No tab movements yet! Use `/daily` to get started.".to_string()
+    } else {
+        render_page(&entries, page, total, false)
+    };
+
+    let embed = utils::create_embed_builder("Wallet History", description, EmbedType::Neutral);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Page through the whole server's ledger (moderator-only, prefix command).
+///
+/// Usage: `!audit [reason] [page]`, e.g. `!audit Admin` or `!audit Gift 2`.
+#[poise::command(prefix_command, guild_only, check = "checks::moderator")]
+pub async fn audit(
+    ctx: Context<'_>,
+    reason: Option<LedgerReason>,
+    page: Option<usize>,
+) -> Result {
+    let guild_id = ctx.guild_id().unwrap();
+    let page = page.unwrap_or(1).max(1);
+
+    let (entries, total) = ctx
+        .data()
+        .ledger_page(
+            guild_id,
+            |e| reason.is_none_or(|r| e.reason == r),
+            page - 1,
+            LEDGER_PAGE_SIZE,
+        )
+        .await;
+
+    let title = match reason {
+        Some(r) => format!("Ledger Audit — {}", r.name()),
+        None => "Ledger Audit".to_string(),
+    };
+    let description = if total == 0 {
+        "No matching entries.".to_string()
+    } else {
+        render_page(&entries, page, total, true)
+    };
+
+    let embed = utils::create_embed_builder(title, description, EmbedType::Neutral);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Rendering helpers
+// ---------------------------------------------------------------------------
+
+/// Render one page of entries with a "page X of Y" header. `show_user`
+/// prefixes each line with the member it belongs to.
+fn render_page(entries: &[LedgerEntry], page: usize, total: usize, show_user: bool) -> String {
+    let pages = total.div_ceil(LEDGER_PAGE_SIZE);
+    let mut description = format!("**Page {page} / {pages}** · {total} entries\n\n");
+    if entries.is_empty() {
+        description.push_str("*Nothing on this page.*");
+    }
+    for entry in entries {
+        if show_user {
+            description.push_str(&format!("<@{}> ", entry.user_id));
+        }
+        description.push_str(&render_entry(entry));
+        description.push('\n');
+    }
+    description
+}
+
+/// `<relative time> **+10** 🪙 · Daily · <@other> · `item``
+fn render_entry(entry: &LedgerEntry) -> String {
+    let mut line = format!(
+        "<t:{}:R> **{:+}** {TAB_EMOJI} · {}",
+        entry.at.timestamp(),
+        entry.delta,
+        entry.reason.name(),
+    );
+    if let Some(other) = entry.counterparty {
+        line.push_str(&format!(" · <@{other}>"));
+    }
+    if let Some(item) = &entry.item_id {
+        line.push_str(&format!(" · `{item}`"));
+    }
+    line
+}
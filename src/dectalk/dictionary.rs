@@ -0,0 +1,280 @@
+//! Pronunciation overrides compiled into a DECtalk user dictionary.
+//!
+//! `TextToSpeechLoadUserDictionary` reads the same binary layout as the
+//! built-in `dtalk_*.dic` files, all integers little-endian:
+//!
+//! | Field | Contents |
+//! |---|---|
+//! | `u32` | number of entries |
+//! | `u32` | size of the entry data in bytes |
+//! | `u32` × entries | offset of each entry into the data |
+//! | entry data | the entries, sorted by their upper-cased word |
+//!
+//! Each entry is a `u32` form class, the word NUL-terminated, then its
+//! phoneme codes NUL-terminated, zero-padded to a multiple of four bytes.
+//!
+//! Pronunciations are written in DECtalk's two-letter arpabet, as used in
+//! `[...]` phoneme brackets: `n g 'ow z iy` or `ng'owziy`. Only the US
+//! English phoneme set is supported; see [`PHONEMES`].
+
+use super::{DectalkError, Language};
+
+/// Longest word an entry can be for, in bytes.
+pub const MAX_WORD_LEN: usize = 40;
+
+/// Most phonemes and stress marks in one pronunciation.
+pub const MAX_PHONEMES: usize = 60;
+
+/// US English arpabet names and their codes, from `l_all_ph.h`.
+///
+/// The allophones DECtalk only uses internally (`US_DZ`, `US_DF` and
+/// above `US_JH`) aren't accepted.
+const PHONEMES: &[(&str, u8)] = &[
+    ("iy", 1),
+    ("ih", 2),
+    ("ey", 3),
+    ("eh", 4),
+    ("ae", 5),
+    ("aa", 6),
+    ("ay", 7),
+    ("aw", 8),
+    ("ah", 9),
+    ("ao", 10),
+    ("ow", 11),
+    ("oy", 12),
+    ("uh", 13),
+    ("uw", 14),
+    ("rr", 15),
+    ("yu", 16),
+    ("ax", 17),
+    ("ix", 18),
+    ("ir", 19),
+    ("er", 20),
+    ("ar", 21),
+    ("or", 22),
+    ("ur", 23),
+    ("w", 24),
+    ("y", 25),
+    ("r", 26),
+    ("l", 27),
+    ("hx", 28),
+    ("rx", 29),
+    ("lx", 30),
+    ("m", 31),
+    ("n", 32),
+    ("nx", 33),
+    ("el", 34),
+    ("en", 36),
+    ("f", 37),
+    ("v", 38),
+    ("th", 39),
+    ("dh", 40),
+    ("s", 41),
+    ("z", 42),
+    ("sh", 43),
+    ("zh", 44),
+    ("p", 45),
+    ("b", 46),
+    ("t", 47),
+    ("d", 48),
+    ("k", 49),
+    ("g", 50),
+    ("dx", 51),
+    ("tx", 52),
+    ("q", 53),
+    ("ch", 54),
+    ("jh", 55),
+];
+
+/// Secondary stress, written `` ` `` before the stressed vowel.
+const SECONDARY_STRESS: u8 = 0x66;
+
+/// Primary stress, written `'` before the stressed vowel.
+const PRIMARY_STRESS: u8 = 0x67;
+
+/// The form class the built-in dictionary gives most entries (nouns), which
+/// fits the names and jargon user entries are for.
+const FORM_CLASS: u32 = 0x0400;
+
+/// Whether a user dictionary can be loaded into an engine speaking
+/// `language` — US English, DECtalk's default, is the only one whose phoneme
+/// codes [`PHONEMES`] has.
+pub fn applies_to(language: Option<Language>) -> bool {
+    matches!(language, None | Some(Language::EnglishUs))
+}
+
+/// `word` as it's stored and compiled: trimmed and lower-cased.
+///
+/// Words are one token of ASCII letters and digits, plus `'`, `-` and `.`
+/// inside them, as DECtalk splits text into words before looking them up.
+pub fn normalise_word(word: &str) -> Result<String, DectalkError> {
+    let word = word.trim().to_ascii_lowercase();
+    let valid = !word.is_empty()
+        && word.len() <= MAX_WORD_LEN
+        && word
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'\'' | b'-' | b'.'))
+        && word.starts_with(|c: char| c.is_ascii_alphanumeric());
+    if valid {
+        Ok(word)
+    } else {
+        Err(DectalkError::InvalidWord(word))
+    }
+}
+
+/// The phoneme codes for an arpabet `pronunciation`.
+///
+/// Two-letter names win over one-letter ones, as in DECtalk itself, so
+/// `dh` is one phoneme; separate them with a space to mean `d` then `h…`.
+pub fn phonemes(pronunciation: &str) -> Result<Vec<u8>, DectalkError> {
+    let text = pronunciation.trim().to_ascii_lowercase();
+    let invalid = || DectalkError::InvalidPronunciation(pronunciation.trim().to_string());
+    let mut codes = Vec::new();
+    let mut rest = text.as_str();
+    while let Some(c) = rest.chars().next() {
+        let (code, len) = match c {
+            ' ' => {
+                rest = &rest[1..];
+                continue;
+            }
+            '\'' => (PRIMARY_STRESS, 1),
+            '`' => (SECONDARY_STRESS, 1),
+            _ => [2, 1]
+                .into_iter()
+                .filter_map(|len| rest.get(..len))
+                .find_map(|name| {
+                    PHONEMES
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|&(_, code)| (code, name.len()))
+                })
+                .ok_or_else(invalid)?,
+        };
+        codes.push(code);
+        rest = &rest[len..];
+    }
+    if codes.is_empty() || codes.len() > MAX_PHONEMES || codes.iter().all(is_stress) {
+        return Err(invalid());
+    }
+    Ok(codes)
+}
+
+fn is_stress(code: &u8) -> bool {
+    matches!(*code, PRIMARY_STRESS | SECONDARY_STRESS)
+}
+
+/// Compile `(word, pronunciation)` pairs into a user dictionary file.
+///
+/// Words are normalised with [`normalise_word`]; if one appears twice the
+/// last pronunciation wins.
+pub fn compile<'a>(
+    entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<Vec<u8>, DectalkError> {
+    let mut compiled = entries
+        .into_iter()
+        .map(|(word, pronunciation)| Ok((normalise_word(word)?, phonemes(pronunciation)?)))
+        .collect::<Result<Vec<_>, DectalkError>>()?;
+    // DECtalk binary-searches the entries by their upper-cased word.
+    compiled.reverse();
+    compiled.sort_by_cached_key(|(word, _)| word.to_ascii_uppercase());
+    compiled.dedup_by(|a, b| a.0 == b.0);
+
+    let mut data = Vec::new();
+    let mut offsets = Vec::with_capacity(compiled.len());
+    for (word, codes) in &compiled {
+        offsets.push(data.len() as u32);
+        data.extend_from_slice(&FORM_CLASS.to_le_bytes());
+        data.extend_from_slice(word.as_bytes());
+        data.push(0);
+        data.extend_from_slice(codes);
+        data.push(0);
+        data.resize(data.len().next_multiple_of(4), 0);
+    }
+
+    let mut file = Vec::with_capacity(8 + 4 * offsets.len() + data.len());
+    file.extend_from_slice(&(compiled.len() as u32).to_le_bytes());
+    file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    for offset in offsets {
+        file.extend_from_slice(&offset.to_le_bytes());
+    }
+    file.extend_from_slice(&data);
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_letter_phonemes_win() {
+        assert_eq!(phonemes("dh").unwrap(), [40]);
+        assert_eq!(phonemes("th").unwrap(), [39]);
+        assert_eq!(phonemes("t hx").unwrap(), [47, 28]);
+        assert_eq!(phonemes("ziy").unwrap(), [42, 1]);
+    }
+
+    #[test]
+    fn spaces_stress_and_case() {
+        let spaced = phonemes(" n g 'ow z iy ").unwrap();
+        assert_eq!(spaced, [32, 50, PRIMARY_STRESS, 11, 42, 1]);
+        assert_eq!(phonemes("NG'OWZIY").unwrap(), spaced);
+        assert_eq!(phonemes("`aa").unwrap(), [SECONDARY_STRESS, 6]);
+    }
+
+    #[test]
+    fn rejects_empty_stress_only_and_unknown() {
+        for bad in ["", "   ", "'", "' `", "xx", "d h"] {
+            assert!(
+                matches!(phonemes(bad), Err(DectalkError::InvalidPronunciation(_))),
+                "{bad:?}"
+            );
+        }
+        assert!(phonemes(&"p".repeat(MAX_PHONEMES)).is_ok());
+        assert!(phonemes(&"p".repeat(MAX_PHONEMES + 1)).is_err());
+    }
+
+    #[test]
+    fn words_are_trimmed_and_lower_cased() {
+        assert_eq!(normalise_word("  Hello ").unwrap(), "hello");
+        assert_eq!(normalise_word("O'Neil").unwrap(), "o'neil");
+        assert_eq!(normalise_word("e.g.").unwrap(), "e.g.");
+        assert_eq!(normalise_word("mp3").unwrap(), "mp3");
+    }
+
+    #[test]
+    fn rejects_words_dectalk_would_split() {
+        let too_long = "a".repeat(MAX_WORD_LEN + 1);
+        for bad in ["", "  ", "two words", "-dash", "'tis", "café", &too_long] {
+            assert!(
+                matches!(normalise_word(bad), Err(DectalkError::InvalidWord(_))),
+                "{bad:?}"
+            );
+        }
+        assert!(normalise_word(&"a".repeat(MAX_WORD_LEN)).is_ok());
+    }
+
+    #[test]
+    fn compiles_sorted_padded_entries_behind_their_offsets() {
+        let file = compile([("Zed", "z 'eh d"), ("abc", "ey"), ("zed", "z iy")]).unwrap();
+
+        let mut expected = Vec::new();
+        for field in [2u32, 24, 0, 12, FORM_CLASS] {
+            expected.extend_from_slice(&field.to_le_bytes());
+        }
+        expected.extend_from_slice(b"abc\0");
+        expected.extend_from_slice(&[3, 0, 0, 0]);
+        expected.extend_from_slice(&FORM_CLASS.to_le_bytes());
+        expected.extend_from_slice(b"zed\0");
+        expected.extend_from_slice(&[42, 1, 0, 0]);
+        assert_eq!(file, expected);
+    }
+
+    #[test]
+    fn compile_reports_the_first_bad_entry() {
+        assert!(matches!(
+            compile([("ok", "ow k ey"), ("bad word", "b ae d")]),
+            Err(DectalkError::InvalidWord(word)) if word == "bad word"
+        ));
+        assert_eq!(compile([]).unwrap(), [0; 8]);
+    }
+}
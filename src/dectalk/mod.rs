@@ -0,0 +1,892 @@
+//! Safe Rust wrapper around the DECtalk text-to-speech C library.
+//!
+//! DECtalk is a classic TTS engine from the 80s/90s, famous for its robotic
+//! voice. This module provides a minimal safe API over the raw C bindings
+//! generated by `bindgen` at build time (see `build.rs`).
+//!
+//! # Safety model
+//!
+//! All `unsafe` blocks are confined to this module. Each call to a C function
+//! is wrapped by [`check_mm`], which converts the Windows `MMRESULT` error
+//! code into a typed [`DectalkError::Mm`]. The [`Dectalk`] struct owns the
+//! opaque handle and shuts the library down via [`Drop`].
+//!
+//! Speech is synthesised into memory rather than to a file: DECtalk fills
+//! buffers we hand it and passes each full one back through the callback
+//! registered at startup ([`on_message`]), which copies the samples out and
+//! hands the buffer back. The buffers stay owned by Rust for the whole call.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let tts = Dectalk::new()?;
+//! let speech = tts.speak_to_buffer("Hello world", WaveFormat::DT_1M16)?;
+//! let wav = speech.wav();
+//!
+//! // Or in another language and voice:
+//! let tts = Dectalk::with_language(Language::German)?;
+//! tts.set_voice(&Voice { speaker: Some(Speaker::Betty), ..Voice::default() })?;
+//! ```
+
+#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod dictionary;
+pub mod pool;
+pub mod text;
+
+use crate::audio::wav;
+use crate::pawthos::types::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    ffi::{CString, NulError, c_int, c_void},
+    fmt,
+    ptr::{self, NonNull},
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+// Generated in build.rs as OUT_DIR/dectalk_bindings.rs
+include!(concat!(env!("OUT_DIR"), "/dectalk_bindings.rs"));
+
+// ---------------------------------------------------------------------------
+// Error type
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur when initialising or using DECtalk.
+#[derive(Debug)]
+pub enum DectalkError {
+    /// A DECtalk C function returned a non-zero `MMRESULT` error code.
+    ///
+    /// `loc` captures the call site via `#[track_caller]` so log messages
+    /// include the Rust source location of the failing call.
+    Mm {
+        error_code: MMRESULT,
+        loc: std::panic::Location<'static>,
+    },
+
+    /// `TextToSpeechStartup` returned a null handle despite reporting success.
+    ///
+    /// Should never happen in practice; indicates a serious library issue.
+    NullHandle,
+
+    /// A string passed to DECtalk contained an interior NUL byte.
+    ///
+    /// DECtalk expects C strings; [`CString::new`] rejects strings with
+    /// embedded NULs and returns a [`NulError`].
+    FfiNul(std::ffi::NulError),
+
+    /// `TextToSpeechStartLang` or `TextToSpeechSelectLang` refused the
+    /// language — its library or dictionary isn't installed.
+    LanguageUnavailable(Language),
+
+    /// A speaking rate outside [`Rate::MIN`]`..=`[`Rate::MAX`] words per minute.
+    InvalidRate(u32),
+
+    /// A volume above [`Volume::MAX`].
+    InvalidVolume(u32),
+
+    /// Every [`pool::Pool`] worker is busy and its queue is full.
+    Busy,
+
+    /// A [`pool::Pool`] job didn't finish within the pool's timeout.
+    TimedOut(std::time::Duration),
+
+    /// The synthesis panicked; the worker restarts its engine.
+    EnginePanicked,
+
+    /// The [`pool::Pool`] worker handling the job went away without
+    /// answering.
+    WorkerLost,
+
+    /// A word [`dictionary::normalise_word`] can't make an entry for.
+    InvalidWord(String),
+
+    /// A pronunciation [`dictionary::phonemes`] can't read.
+    InvalidPronunciation(String),
+
+    /// Writing the compiled user dictionary out for DECtalk to load failed.
+    DictionaryFile(std::io::Error),
+}
+
+impl std::fmt::Display for DectalkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DectalkError::Mm { error_code, loc } => {
+                write!(f, "DectalkMmError {} at {}", error_code, loc)
+            }
+            DectalkError::NullHandle => write!(f, "DectalkNullHandleError"),
+            DectalkError::FfiNul(e) => write!(f, "Nul byte found in string: {}", e),
+            DectalkError::LanguageUnavailable(lang) => {
+                write!(f, "DECtalk language {} is not available", lang.code())
+            }
+            DectalkError::InvalidRate(wpm) => write!(
+                f,
+                "Speaking rate must be {}-{} words per minute, not {}",
+                Rate::MIN,
+                Rate::MAX,
+                wpm
+            ),
+            DectalkError::InvalidVolume(v) => {
+                write!(f, "Volume must be 0-{}, not {}", Volume::MAX, v)
+            }
+            DectalkError::Busy => write!(f, "DECtalk is busy right now, try again in a moment"),
+            DectalkError::TimedOut(after) => {
+                write!(f, "DECtalk took longer than {}s", after.as_secs())
+            }
+            DectalkError::EnginePanicked => write!(f, "DECtalk crashed while speaking"),
+            DectalkError::WorkerLost => write!(f, "DECtalk worker stopped unexpectedly"),
+            DectalkError::InvalidWord(word) => write!(
+                f,
+                "`{}` can't go in the dictionary: use one word of up to {} letters, digits, ' - or .",
+                word,
+                dictionary::MAX_WORD_LEN
+            ),
+            DectalkError::InvalidPronunciation(text) => write!(
+                f,
+                "`{}` isn't a pronunciation DECtalk knows: use arpabet phonemes like `n 'ey m`",
+                text
+            ),
+            DectalkError::DictionaryFile(e) => {
+                write!(f, "Could not write the user dictionary: {}", e)
+            }
+        }
+    }
+}
+
+impl From<NulError> for DectalkError {
+    fn from(e: NulError) -> Self {
+        DectalkError::FfiNul(e)
+    }
+}
+
+impl std::error::Error for DectalkError {}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// Check a `MMRESULT` return code and convert non-zero values into an error.
+///
+/// `#[track_caller]` causes the `Location` stored in the error to point at
+/// the call site (inside [`Dectalk`] methods) rather than here.
+#[track_caller]
+fn check_mm(code: MMRESULT) -> Result<(), DectalkError> {
+    if code == 0 {
+        Ok(())
+    } else {
+        let loc = std::panic::Location::caller();
+
+        let e = DectalkError::Mm {
+            error_code: (code),
+            loc: (*loc),
+        };
+        Err(e)
+    }
+}
+
+/// `TextToSpeechSetVolume` type selecting the main volume, from `ttsapi.h`
+/// (bindgen only pulls in the `TTS_*` constants).
+const VOLUME_MAIN: c_int = 1;
+
+/// Held from selecting a language until the engine has started with it.
+///
+/// `TextToSpeechSelectLang(NULL, …)` picks the language for the *next*
+/// `TextToSpeechStartup` process-wide, so two threads starting engines in
+/// different languages at once could otherwise swap them.
+static LANGUAGE_SELECT: Mutex<()> = Mutex::new(());
+
+// ---------------------------------------------------------------------------
+// In-memory capture
+// ---------------------------------------------------------------------------
+
+/// How many buffers DECtalk gets to fill at once, and how big each is.
+/// 16 KiB holds about 0.75 s of [`WaveFormat::DT_1M16`] audio.
+const MEMORY_BUFFERS: usize = 4;
+const MEMORY_BUFFER_BYTES: usize = 16 * 1024;
+
+/// Samples collected for one engine while [`Dectalk::speak_to_buffer`] runs.
+struct Capture {
+    /// The engine's handle, as an address so the registry can be shared
+    /// between threads. Full buffers are given back to it.
+    handle: usize,
+
+    /// Raw sample bytes, in the order DECtalk returned them.
+    pcm: Vec<u8>,
+
+    /// Set once the engine is leaving memory mode; buffers returned after
+    /// that are collected but not handed back.
+    closing: bool,
+}
+
+/// Captures in progress, keyed by the instance number each engine passes to
+/// [`on_message`].
+///
+/// The callback only gets a 32-bit instance value, too small for a pointer
+/// on 64-bit targets, so captures are looked up here instead.
+static CAPTURES: Mutex<BTreeMap<u32, Capture>> = Mutex::new(BTreeMap::new());
+
+/// Source of [`Dectalk::instance`] numbers.
+static NEXT_INSTANCE: AtomicU32 = AtomicU32::new(0);
+
+fn captures() -> MutexGuard<'static, BTreeMap<u32, Capture>> {
+    // A panic mid-copy leaves nothing half-updated worth refusing over.
+    CAPTURES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The callback registered with `TextToSpeechStartup`. DECtalk may call it
+/// from its own threads.
+///
+/// Only `TTS_MSG_BUFFER` matters: `buffer` then points at one of the
+/// [`TTS_BUFFER_T`]s added by [`Dectalk::speak_to_buffer`], now full.
+unsafe extern "C" fn on_message(_: LONG, buffer: LONG, instance: DWORD, message: UINT) {
+    if message != TTS_MSG_BUFFER || buffer == 0 {
+        return;
+    }
+    let buffer = buffer as LPTTS_BUFFER_T;
+    let handle = {
+        let mut captures = captures();
+        let Some(capture) = captures.get_mut(&instance) else {
+            return;
+        };
+        // SAFETY: DECtalk only returns buffers we added, which
+        // speak_to_buffer keeps alive until the engine has left memory mode.
+        unsafe { take_samples(buffer, &mut capture.pcm) };
+        if capture.closing {
+            return;
+        }
+        capture.handle
+    };
+    // SAFETY: the handle stays valid while its capture is registered, and
+    // the buffer is still owned by speak_to_buffer.
+    let _ = unsafe { TextToSpeechAddBuffer(handle as LPTTS_HANDLE_T, buffer) };
+}
+
+/// Append the samples DECtalk wrote into `buffer` to `pcm` and mark it empty.
+///
+/// # Safety
+/// `buffer` must point at a live [`TTS_BUFFER_T`] whose `lpData` holds at
+/// least `dwBufferLength` bytes.
+unsafe fn take_samples(buffer: LPTTS_BUFFER_T, pcm: &mut Vec<u8>) {
+    let buffer = unsafe { &mut *buffer };
+    let len = buffer.dwBufferLength.min(buffer.dwMaximumBufferLength) as usize;
+    if len > 0 {
+        let data = unsafe { std::slice::from_raw_parts(buffer.lpData as *const u8, len) };
+        pcm.extend_from_slice(data);
+    }
+    buffer.dwBufferLength = 0;
+}
+
+/// Takes an engine out of memory mode and unregisters its capture, however
+/// [`Dectalk::speak_to_buffer`] exits. Must be dropped before the buffers.
+struct InMemory<'a> {
+    tts: &'a Dectalk,
+}
+
+impl InMemory<'_> {
+    /// Everything captured, once memory mode is closed.
+    fn finish(self) -> Vec<u8> {
+        self.close()
+    }
+
+    /// Stop handing buffers back, close memory mode and unregister the
+    /// capture, returning its samples. Does nothing the second time.
+    fn close(&self) -> Vec<u8> {
+        match captures().get_mut(&self.tts.instance) {
+            Some(capture) => capture.closing = true,
+            None => return Vec::new(),
+        }
+        // SAFETY: the handle is live for as long as `tts` is borrowed.
+        let _ = unsafe { TextToSpeechCloseInMemory(self.tts.handle.as_ptr()) };
+        captures()
+            .remove(&self.tts.instance)
+            .map(|c| c.pcm)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for InMemory<'_> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Voice settings
+// ---------------------------------------------------------------------------
+
+/// One of DECtalk's built-in voices, numbered as in `ttsapi.h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+pub enum Speaker {
+    /// Perfect Paul — the default voice.
+    #[name = "Paul"]
+    Paul = 0,
+    /// Beautiful Betty.
+    #[name = "Betty"]
+    Betty = 1,
+    /// Huge Harry.
+    #[name = "Harry"]
+    Harry = 2,
+    /// Frail Frank.
+    #[name = "Frank"]
+    Frank = 3,
+    /// Doctor Dennis.
+    #[name = "Dennis"]
+    Dennis = 4,
+    /// Kit the Kid.
+    #[name = "Kit"]
+    Kit = 5,
+    /// Uppity Ursula.
+    #[name = "Ursula"]
+    Ursula = 6,
+    /// Rough Rita.
+    #[name = "Rita"]
+    Rita = 7,
+    /// Whispering Wendy.
+    #[name = "Wendy"]
+    Wendy = 8,
+}
+
+impl Speaker {
+    /// The speaker `TextToSpeechGetSpeaker` reports as `raw`.
+    fn from_raw(raw: SPEAKER_T) -> Option<Self> {
+        Some(match raw {
+            0 => Speaker::Paul,
+            1 => Speaker::Betty,
+            2 => Speaker::Harry,
+            3 => Speaker::Frank,
+            4 => Speaker::Dennis,
+            5 => Speaker::Kit,
+            6 => Speaker::Ursula,
+            7 => Speaker::Rita,
+            8 => Speaker::Wendy,
+            _ => return None,
+        })
+    }
+}
+
+/// A language DECtalk can speak; `build.rs` links one library for each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+pub enum Language {
+    #[name = "English (US)"]
+    EnglishUs,
+    #[name = "English (UK)"]
+    EnglishUk,
+    #[name = "Spanish (Spain)"]
+    Spanish,
+    #[name = "Spanish (Latin America)"]
+    LatinAmericanSpanish,
+    #[name = "German"]
+    German,
+    #[name = "French"]
+    French,
+}
+
+impl Language {
+    /// The code `TextToSpeechStartLang` knows the language by, which is also
+    /// the suffix of its `tts_*` library and `dtalk_*.dic` dictionary.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::EnglishUs => "us",
+            Language::EnglishUk => "uk",
+            Language::Spanish => "sp",
+            Language::LatinAmericanSpanish => "la",
+            Language::German => "gr",
+            Language::French => "fr",
+        }
+    }
+}
+
+/// Speaking rate in words per minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct Rate(u32);
+
+impl Rate {
+    /// The slowest rate DECtalk accepts.
+    pub const MIN: u32 = 75;
+    /// The fastest rate DECtalk accepts.
+    pub const MAX: u32 = 600;
+
+    pub fn wpm(self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for Rate {
+    type Error = DectalkError;
+
+    fn try_from(wpm: u32) -> Result<Self, DectalkError> {
+        if (Self::MIN..=Self::MAX).contains(&wpm) {
+            Ok(Rate(wpm))
+        } else {
+            Err(DectalkError::InvalidRate(wpm))
+        }
+    }
+}
+
+impl From<Rate> for u32 {
+    fn from(rate: Rate) -> u32 {
+        rate.0
+    }
+}
+
+/// Main output volume, from silent (0) to [`Volume::MAX`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct Volume(u32);
+
+impl Volume {
+    pub const MAX: u32 = 100;
+
+    pub fn level(self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for Volume {
+    type Error = DectalkError;
+
+    fn try_from(level: u32) -> Result<Self, DectalkError> {
+        if level <= Self::MAX {
+            Ok(Volume(level))
+        } else {
+            Err(DectalkError::InvalidVolume(level))
+        }
+    }
+}
+
+impl From<Volume> for u32 {
+    fn from(volume: Volume) -> u32 {
+        volume.0
+    }
+}
+
+/// How to speak: any setting left `None` keeps DECtalk's own default.
+///
+/// Stored per user as their saved voice (see
+/// [`crate::pawthos::structs::vox_user::VoxUser`]) and merged with the
+/// options given to `/vox say` via [`Voice::or`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Voice {
+    #[serde(default)]
+    pub speaker: Option<Speaker>,
+    #[serde(default)]
+    pub rate: Option<Rate>,
+    #[serde(default)]
+    pub volume: Option<Volume>,
+    #[serde(default)]
+    pub language: Option<Language>,
+}
+
+impl Voice {
+    /// This voice, with any unset settings taken from `fallback`.
+    pub fn or(self, fallback: Voice) -> Voice {
+        Voice {
+            speaker: self.speaker.or(fallback.speaker),
+            rate: self.rate.or(fallback.rate),
+            volume: self.volume.or(fallback.volume),
+            language: self.language.or(fallback.language),
+        }
+    }
+}
+
+/// `Paul, 200 wpm, volume 80, German`, with "default" for unset settings.
+impl fmt::Display for Voice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use poise::ChoiceParameter;
+        let speaker = self.speaker.map_or("default voice", |s| s.name());
+        let rate = self
+            .rate
+            .map_or("default rate".to_string(), |r| format!("{} wpm", r.wpm()));
+        let volume = self.volume.map_or("default volume".to_string(), |v| {
+            format!("volume {}", v.level())
+        });
+        let language = self.language.map_or("default language", |l| l.name());
+        write!(f, "{speaker}, {rate}, {volume}, {language}")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Audio formats
+// ---------------------------------------------------------------------------
+
+/// Audio formats [`Dectalk::speak_to_buffer`] can produce — every one
+/// `TextToSpeechOpenInMemory` accepts, all mono. Discriminants are the
+/// `WAVE_FORMAT_*` values from `dtmmedefs.h`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum WaveFormat {
+    /// 8-bit unsigned PCM at 11.025 kHz — half the size of
+    /// [`WaveFormat::DT_1M16`], with audible hiss.
+    #[name = "8-bit, 11 kHz"]
+    DT_1M08 = 0x0001,
+
+    /// 16-bit PCM at 11.025 kHz, DECtalk's native rate.
+    ///
+    /// The default for `/vox say`: good quality at a small file size.
+    #[default]
+    #[name = "16-bit, 11 kHz"]
+    DT_1M16 = 0x0004,
+
+    /// 8-bit µ-law at 8 kHz, as used on telephone lines.
+    #[name = "µ-law, 8 kHz (telephone)"]
+    DT_08M08 = 0x1000,
+
+    /// 16-bit PCM at 8 kHz.
+    #[name = "16-bit, 8 kHz"]
+    DT_08M16 = 0x2000,
+}
+
+impl WaveFormat {
+    /// How samples in this format are laid out, as a WAV file describes it.
+    pub fn spec(self) -> wav::Spec {
+        let (encoding, sample_rate, bits_per_sample) = match self {
+            WaveFormat::DT_1M08 => (wav::Encoding::Pcm, 11_025, 8),
+            WaveFormat::DT_1M16 => (wav::Encoding::Pcm, 11_025, 16),
+            WaveFormat::DT_08M08 => (wav::Encoding::MuLaw, 8_000, 8),
+            WaveFormat::DT_08M16 => (wav::Encoding::Pcm, 8_000, 16),
+        };
+        wav::Spec {
+            encoding,
+            sample_rate,
+            channels: 1,
+            bits_per_sample,
+        }
+    }
+
+    /// Samples per second.
+    pub fn sample_rate(self) -> u32 {
+        self.spec().sample_rate
+    }
+}
+
+/// Speech synthesised by [`Dectalk::speak_to_buffer`]: raw audio in the
+/// format it was asked for, 16-bit samples little-endian as in a WAV file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Speech {
+    pub format: WaveFormat,
+    pub data: Vec<u8>,
+}
+
+impl Speech {
+    /// The audio as 16-bit samples, whatever its format — for resampling
+    /// and re-encoding.
+    pub fn samples(&self) -> Vec<i16> {
+        wav::samples(&self.data, self.format.spec())
+    }
+
+    /// The audio as a WAV file.
+    pub fn wav(&self) -> Vec<u8> {
+        wav::encode(&self.data, self.format.spec())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// A live DECtalk TTS engine instance.
+///
+/// Wraps the opaque `LPTTS_HANDLE_T` pointer returned by
+/// `TextToSpeechStartup`. The handle is shut down when the struct is dropped.
+///
+/// # Thread safety
+///
+/// DECtalk is not thread-safe; use one `Dectalk` per thread. The bot keeps a
+/// [`pool::Pool`] of worker threads, each owning one long-lived instance.
+#[derive(Debug)]
+pub struct Dectalk {
+    handle: NonNull<c_void>,
+
+    /// The language started for this engine by [`Dectalk::with_language`],
+    /// closed again on drop.
+    language: Option<Language>,
+
+    /// Passed to [`on_message`] to find this engine's [`Capture`].
+    instance: u32,
+
+    /// Whether a user dictionary is loaded, so it's only ever unloaded once.
+    user_dictionary: Cell<bool>,
+}
+
+impl Dectalk {
+    /// Initialise the DECtalk library and return a handle.
+    ///
+    /// Registers [`on_message`] as the callback and uses default device
+    /// options. Output is collected in memory via [`speak_to_buffer`]; there
+    /// is no real-time audio output.
+    ///
+    /// # Errors
+    /// Returns [`DectalkError::Mm`] if `TextToSpeechStartup` fails, or
+    /// [`DectalkError::NullHandle`] if it returns a null pointer despite
+    /// reporting success.
+    ///
+    /// [`speak_to_buffer`]: Dectalk::speak_to_buffer
+    pub fn new() -> Result<Self, DectalkError> {
+        log::debug!("Dectalk::new()");
+        let instance = NEXT_INSTANCE.fetch_add(1, Ordering::Relaxed);
+        let mut raw: LPTTS_HANDLE_T = ptr::null_mut();
+        // SAFETY: TextToSpeechStartup is safe because we know that LPTTS_HANDLE_T is a proper null_mut pointer
+        // before the function call, and we check if the handle is null afterwards.
+        // for more information on the TextToSpeechStarup, visit https://dectalk.github.io/dectalk/dectalk.htm
+        let rc = unsafe { TextToSpeechStartup(&mut raw, 0, 0, Some(on_message), instance as LONG) };
+        check_mm(rc)?;
+
+        let handle = NonNull::new(raw).ok_or(DectalkError::NullHandle)?;
+        Ok(Self {
+            handle,
+            language: None,
+            instance,
+            user_dictionary: Cell::new(false),
+        })
+    }
+
+    /// Initialise the DECtalk library speaking `language`.
+    ///
+    /// DECtalk fixes an engine's language when it starts up, so unlike the
+    /// other [`Voice`] settings it can't be changed afterwards.
+    ///
+    /// # Errors
+    /// Returns [`DectalkError::LanguageUnavailable`] if the language can't be
+    /// loaded, otherwise as for [`Dectalk::new`].
+    pub fn with_language(language: Language) -> Result<Self, DectalkError> {
+        log::debug!("Dectalk::with_language({})", language.code());
+        let code = CString::new(language.code())?;
+        let _selecting = LANGUAGE_SELECT
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // SAFETY: `code` is a valid NUL-terminated string that outlives the
+        // call; DECtalk only reads it.
+        let id = unsafe { TextToSpeechStartLang(code.as_ptr() as *mut i8) };
+        if id & TTS_LANG_ERROR != 0 {
+            return Err(DectalkError::LanguageUnavailable(language));
+        }
+        // SAFETY: a null handle selects the language for the next startup.
+        if unsafe { TextToSpeechSelectLang(ptr::null_mut(), id) } == 0 {
+            // SAFETY: as above; balances the successful StartLang.
+            unsafe { TextToSpeechCloseLang(code.as_ptr() as *mut i8) };
+            return Err(DectalkError::LanguageUnavailable(language));
+        }
+
+        let mut tts = Self::new().inspect_err(|_| {
+            // SAFETY: as above; balances the successful StartLang.
+            unsafe { TextToSpeechCloseLang(code.as_ptr() as *mut i8) };
+        })?;
+        tts.language = Some(language);
+        Ok(tts)
+    }
+
+    /// Initialise DECtalk in `voice`'s language and apply the rest of it.
+    pub fn with_voice(voice: &Voice) -> Result<Self, DectalkError> {
+        let tts = match voice.language {
+            Some(language) => Self::with_language(language)?,
+            None => Self::new()?,
+        };
+        tts.set_voice(voice)?;
+        Ok(tts)
+    }
+
+    /// Switch to one of the built-in voices for everything spoken next.
+    pub fn set_speaker(&self, speaker: Speaker) -> Result<(), DectalkError> {
+        // SAFETY: `handle` came from a successful startup and is only shut
+        // down on drop, so it's live while `self` is borrowed.
+        check_mm(unsafe { TextToSpeechSetSpeaker(self.handle.as_ptr(), speaker as SPEAKER_T) })
+    }
+
+    /// Set the speaking rate for everything spoken next.
+    pub fn set_rate(&self, rate: Rate) -> Result<(), DectalkError> {
+        // SAFETY: `handle` is live while `self` is; the rate is passed by
+        // value.
+        check_mm(unsafe { TextToSpeechSetRate(self.handle.as_ptr(), rate.wpm()) })
+    }
+
+    /// Set the main output volume.
+    pub fn set_volume(&self, volume: Volume) -> Result<(), DectalkError> {
+        // Volume::MAX fits comfortably in a c_int.
+        let level = volume.level() as c_int;
+        // SAFETY: `handle` is live while `self` is; the level is passed by
+        // value.
+        check_mm(unsafe { TextToSpeechSetVolume(self.handle.as_ptr(), VOLUME_MAIN, level) })
+    }
+
+    /// The speaker, rate and volume currently in effect, plus the language
+    /// the engine was started with. Settings DECtalk reports outside the
+    /// typed ranges are left `None`.
+    pub fn voice(&self) -> Result<Voice, DectalkError> {
+        let mut speaker: SPEAKER_T = 0;
+        // SAFETY: `handle` is live while `self` is. The out-pointer is a
+        // local `SPEAKER_T`, valid for the write and not kept after it.
+        check_mm(unsafe { TextToSpeechGetSpeaker(self.handle.as_ptr(), &mut speaker) })?;
+        let mut rate: DWORD = 0;
+        // SAFETY: as above, writing one `DWORD`.
+        check_mm(unsafe { TextToSpeechGetRate(self.handle.as_ptr(), &mut rate) })?;
+        let mut volume: c_int = 0;
+        // SAFETY: as above, writing one `c_int`.
+        check_mm(unsafe { TextToSpeechGetVolume(self.handle.as_ptr(), VOLUME_MAIN, &mut volume) })?;
+        Ok(Voice {
+            speaker: Speaker::from_raw(speaker),
+            rate: Rate::try_from(rate).ok(),
+            volume: u32::try_from(volume)
+                .ok()
+                .and_then(|v| Volume::try_from(v).ok()),
+            language: self.language,
+        })
+    }
+
+    /// Apply `voice`'s speaker, rate and volume, leaving any it doesn't set
+    /// as they are. Its language is ignored; see [`Dectalk::with_language`].
+    pub fn set_voice(&self, voice: &Voice) -> Result<(), DectalkError> {
+        if let Some(speaker) = voice.speaker {
+            self.set_speaker(speaker)?;
+        }
+        if let Some(rate) = voice.rate {
+            self.set_rate(rate)?;
+        }
+        if let Some(volume) = voice.volume {
+            self.set_volume(volume)?;
+        }
+        Ok(())
+    }
+
+    /// Load a user dictionary built by [`dictionary::compile`], replacing any
+    /// loaded before. Its entries take priority over DECtalk's own.
+    ///
+    /// DECtalk only loads dictionaries from a file, so it's written to the
+    /// temp directory for the call and removed again afterwards.
+    pub fn load_user_dictionary(&self, compiled: &[u8]) -> Result<(), DectalkError> {
+        self.unload_user_dictionary()?;
+        let path = std::env::temp_dir().join(format!(
+            "dectalk-{}-{}.dic",
+            std::process::id(),
+            self.instance
+        ));
+        std::fs::write(&path, compiled).map_err(DectalkError::DictionaryFile)?;
+        let cpath = CString::new(path.to_string_lossy().into_owned());
+        let loaded = cpath.map_err(DectalkError::from).and_then(|cpath| {
+            // SAFETY: `cpath` is a valid NUL-terminated string that outlives
+            // the call; DECtalk only reads it.
+            check_mm(unsafe {
+                TextToSpeechLoadUserDictionary(self.handle.as_ptr(), cpath.as_ptr() as *mut i8)
+            })
+        });
+        let _ = std::fs::remove_file(&path);
+        self.user_dictionary.set(loaded.is_ok());
+        loaded
+    }
+
+    /// Drop the loaded user dictionary, if any.
+    pub fn unload_user_dictionary(&self) -> Result<(), DectalkError> {
+        if !self.user_dictionary.replace(false) {
+            return Ok(());
+        }
+        // SAFETY: `handle` is live while `self` is, and `user_dictionary`
+        // was set, so a dictionary loaded on it hasn't been unloaded yet.
+        check_mm(unsafe { TextToSpeechUnloadUserDictionary(self.handle.as_ptr()) })
+    }
+
+    /// Synthesise `text` as speech in `format`.
+    ///
+    /// The call blocks until DECtalk has finished synthesising all queued
+    /// speech (`TextToSpeechSync`). Interior NUL bytes are stripped from
+    /// `text` before it is passed to the C library. Nothing touches the
+    /// filesystem; see [`Speech::wav`] to package the audio.
+    ///
+    /// # Errors
+    /// Returns a [`DectalkError`] if any underlying C call fails.
+    pub fn speak_to_buffer(&self, text: &str, format: WaveFormat) -> Result<Speech, DectalkError> {
+        // Speak the text in normal mode (ASCII expected by this entry point)
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.retain(|&b| b != 0); // strip interior NULs
+        let ctext = CString::new(bytes)?;
+
+        // Declared before `in_memory` so they outlive it: DECtalk may hold
+        // any of them until memory mode is closed.
+        let mut data = vec![vec![0u8; MEMORY_BUFFER_BYTES]; MEMORY_BUFFERS];
+        let mut buffers: Vec<Box<TTS_BUFFER_T>> = data
+            .iter_mut()
+            .map(|data| {
+                Box::new(TTS_BUFFER_T {
+                    lpData: data.as_mut_ptr() as LPSTR,
+                    lpPhonemeArray: ptr::null_mut(),
+                    lpIndexArray: ptr::null_mut(),
+                    dwMaximumBufferLength: MEMORY_BUFFER_BYTES as DWORD,
+                    dwMaximumNumberOfPhonemeChanges: 0,
+                    dwMaximumNumberOfIndexMarks: 0,
+                    dwBufferLength: 0,
+                    dwNumberOfPhonemeChanges: 0,
+                    dwNumberOfIndexMarks: 0,
+                    dwReserved: 0,
+                })
+            })
+            .collect();
+
+        captures().insert(
+            self.instance,
+            Capture {
+                handle: self.handle.as_ptr() as usize,
+                pcm: Vec::new(),
+                closing: false,
+            },
+        );
+        check_mm(unsafe { TextToSpeechOpenInMemory(self.handle.as_ptr(), format as DWORD) })
+            .inspect_err(|_| {
+                captures().remove(&self.instance);
+            })?;
+        let in_memory = InMemory { tts: self };
+
+        for buffer in &mut buffers {
+            check_mm(unsafe { TextToSpeechAddBuffer(self.handle.as_ptr(), &mut **buffer) })?;
+        }
+        check_mm(unsafe {
+            TextToSpeechSpeak(self.handle.as_ptr(), ctext.as_ptr() as *mut i8, TTS_NORMAL)
+        })?;
+
+        // Wait until all queued speech is done
+        check_mm(unsafe { TextToSpeechSync(self.handle.as_ptr()) })?;
+
+        // The last buffer is only partly full, so it never came back through
+        // the callback; ask for it.
+        let mut partial: LPTTS_BUFFER_T = ptr::null_mut();
+        check_mm(unsafe { TextToSpeechReturnBuffer(self.handle.as_ptr(), &mut partial) })?;
+        if !partial.is_null()
+            && let Some(capture) = captures().get_mut(&self.instance)
+        {
+            // SAFETY: `partial` is one of `buffers`, still alive.
+            unsafe { take_samples(partial, &mut capture.pcm) };
+        }
+
+        let mut audio = in_memory.finish();
+        if format.spec().bits_per_sample == 16 {
+            // DECtalk writes 16-bit samples in native byte order.
+            audio = audio
+                .chunks_exact(2)
+                .flat_map(|pair| i16::from_ne_bytes([pair[0], pair[1]]).to_le_bytes())
+                .collect();
+        }
+        Ok(Speech {
+            format,
+            data: audio,
+        })
+    }
+}
+
+/// Shut down the DECtalk engine and release its resources, including the
+/// language it was started with.
+impl Drop for Dectalk {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = TextToSpeechShutdown(self.handle.as_ptr());
+        }
+        if let Some(language) = self.language
+            && let Ok(code) = CString::new(language.code())
+        {
+            unsafe {
+                TextToSpeechCloseLang(code.as_ptr() as *mut i8);
+            }
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! A fixed set of worker threads, each owning one long-lived [`Dectalk`].
+//!
+//! Starting DECtalk takes a while and the engine isn't thread-safe, so
+//! rather than starting one per request, [`Pool::new`] starts a few threads
+//! that keep an engine each and take jobs from a shared, bounded queue.
+//!
+//! - A full queue turns a job away straight away with [`DectalkError::Busy`].
+//! - A job not answered within the pool's timeout fails with
+//!   [`DectalkError::TimedOut`]. DECtalk calls can't be interrupted, so the
+//!   worker carries on, but restarts its engine once the call returns.
+//! - A job that fails inside DECtalk, or panics, also gets its worker a
+//!   fresh engine before the next job.
+//!
+//! An engine speaks one language for its whole life (see
+//! [`Dectalk::with_language`]), so a worker restarts it when a job asks for a
+//! different one. Settings a job leaves unset are put back to the engine's
+//! defaults, so one user's voice never carries over to the next, and the
+//! same goes for user dictionaries: each job's is loaded only if it differs
+//! from the one already loaded.
+
+use super::{Dectalk, DectalkError, Language, Speech, Voice, WaveFormat, dictionary};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// One piece of text to synthesise, and where to send the audio.
+struct Job {
+    text: String,
+    voice: Voice,
+    dictionary: Option<Vec<u8>>,
+    format: WaveFormat,
+    reply: oneshot::Sender<Result<Speech, DectalkError>>,
+}
+
+/// Handle for queueing speech on the worker threads.
+///
+/// Dropping the last handle closes the queue; each worker exits after the
+/// job it's on.
+#[derive(Debug)]
+pub struct Pool {
+    jobs: SyncSender<Job>,
+    timeout: Duration,
+}
+
+impl Pool {
+    /// Start `workers` threads sharing a queue of at most `depth` waiting
+    /// jobs, each job given `timeout` to finish.
+    ///
+    /// Engines are started lazily by each worker's first job.
+    ///
+    /// # Panics
+    /// If a thread can't be spawned.
+    pub fn new(workers: usize, depth: usize, timeout: Duration) -> Self {
+        let (jobs, queue) = mpsc::sync_channel(depth);
+        let queue = Arc::new(Mutex::new(queue));
+        for n in 0..workers {
+            let queue = Arc::clone(&queue);
+            thread::Builder::new()
+                .name(format!("dectalk-{n}"))
+                .spawn(move || Worker::default().run(&queue, timeout))
+                .expect("failed to spawn DECtalk worker thread");
+        }
+        Self { jobs, timeout }
+    }
+
+    /// Synthesise `text` in `voice` as `format` audio on the next free
+    /// worker, with the user `dictionary` built by [`dictionary::compile`]
+    /// loaded.
+    ///
+    /// The dictionary's phonemes are US English, so it's only used when
+    /// `voice` speaks that; see [`dictionary::applies_to`].
+    ///
+    /// # Errors
+    /// [`DectalkError::Busy`] if the queue is full, [`DectalkError::TimedOut`]
+    /// if no answer came in time, [`DectalkError::WorkerLost`] if the worker
+    /// went away, or whatever the engine itself failed with.
+    pub async fn speak(
+        &self,
+        text: String,
+        voice: Voice,
+        dictionary: Option<Vec<u8>>,
+        format: WaveFormat,
+    ) -> Result<Speech, DectalkError> {
+        let (reply, answer) = oneshot::channel();
+        self.jobs
+            .try_send(Job {
+                text,
+                voice,
+                dictionary,
+                format,
+                reply,
+            })
+            .map_err(|e| match e {
+                TrySendError::Full(_) => DectalkError::Busy,
+                TrySendError::Disconnected(_) => DectalkError::WorkerLost,
+            })?;
+        match tokio::time::timeout(self.timeout, answer).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(DectalkError::WorkerLost),
+            Err(_) => Err(DectalkError::TimedOut(self.timeout)),
+        }
+    }
+}
+
+/// The state kept by one worker thread between jobs.
+#[derive(Default)]
+struct Worker {
+    /// The running engine, or `None` until the next job starts one.
+    engine: Option<Engine>,
+}
+
+/// A started engine and the settings it started with.
+struct Engine {
+    tts: Dectalk,
+
+    /// Applied under each job's voice.
+    defaults: Voice,
+
+    /// The user dictionary loaded into `tts`, as compiled.
+    dictionary: Option<Vec<u8>>,
+}
+
+impl Worker {
+    /// Take jobs off `queue` until every [`Pool`] handle is dropped.
+    fn run(mut self, queue: &Mutex<Receiver<Job>>, timeout: Duration) {
+        loop {
+            // Only held while waiting; the job runs with the lock released.
+            let job = match queue
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .recv()
+            {
+                Ok(job) => job,
+                Err(_) => return,
+            };
+            if job.reply.is_closed() {
+                // Timed out while it sat in the queue.
+                continue;
+            }
+
+            let started = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| self.speak(&job)))
+                .unwrap_or(Err(DectalkError::EnginePanicked));
+            let overran = started.elapsed() > timeout;
+            if overran || result.as_ref().is_err_and(restarts) {
+                log::warn!(
+                    "{}: restarting DECtalk engine after {}",
+                    thread::current().name().unwrap_or("dectalk"),
+                    match &result {
+                        Err(e) => e.to_string(),
+                        Ok(_) => format!("a job overran {}s", timeout.as_secs()),
+                    }
+                );
+                self.engine = None;
+            }
+            let _ = job.reply.send(result);
+        }
+    }
+
+    fn speak(&mut self, job: &Job) -> Result<Speech, DectalkError> {
+        let engine = self.engine_for(job.voice.language)?;
+        engine.tts.set_voice(&job.voice.or(engine.defaults))?;
+        let wanted = job
+            .dictionary
+            .as_ref()
+            .filter(|_| dictionary::applies_to(engine.tts.language));
+        if engine.dictionary.as_ref() != wanted {
+            engine.dictionary = None;
+            match wanted {
+                Some(compiled) => engine.tts.load_user_dictionary(compiled)?,
+                None => engine.tts.unload_user_dictionary()?,
+            }
+            engine.dictionary = wanted.cloned();
+        }
+        engine.tts.speak_to_buffer(&job.text, job.format)
+    }
+
+    /// The engine for `language`, restarting it if it's missing or speaks
+    /// another one.
+    fn engine_for(&mut self, language: Option<Language>) -> Result<&mut Engine, DectalkError> {
+        if let Some(engine) = &self.engine
+            && engine.tts.language != language
+        {
+            self.engine = None;
+        }
+        let engine = match self.engine.take() {
+            Some(engine) => engine,
+            None => {
+                let tts = Dectalk::with_voice(&Voice {
+                    language,
+                    ..Voice::default()
+                })?;
+                let defaults = tts.voice()?;
+                Engine {
+                    tts,
+                    defaults,
+                    dictionary: None,
+                }
+            }
+        };
+        Ok(self.engine.insert(engine))
+    }
+}
+
+/// Whether `error` may have left the engine in a bad state.
+fn restarts(error: &DectalkError) -> bool {
+    matches!(
+        error,
+        DectalkError::Mm { .. } | DectalkError::NullHandle | DectalkError::EnginePanicked
+    )
+}
@@ -0,0 +1,342 @@
+//! Cleaning up chat text before DECtalk speaks it.
+//!
+//! [`Dectalk::speak_to_buffer`](super::Dectalk::speak_to_buffer) feeds text
+//! to DECtalk's ASCII entry point, which reads Discord's mention syntax and
+//! anything outside ASCII as noise, and obeys `[:...]` commands wherever
+//! they appear. [`prepare`] runs the steps below in order; each is a plain
+//! function of its input, so none needs the C library or Discord.
+//!
+//! 1. [`resolve_mentions`] — `<@id>`, `<#id>`, `<@&id>` and custom emoji
+//!    become names.
+//! 2. [`transliterate`] — accented letters and typographic punctuation
+//!    become ASCII; anything else becomes a space.
+//! 3. [`neutralise_commands`] — optionally, `[` and `]` are removed so no
+//!    inline command or phoneme bracket takes effect.
+//! 4. [`truncate`] — the result is cut to a maximum length, at a word break
+//!    if there's one near the end.
+
+/// A Discord mention to look up a name for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mention {
+    /// `<@id>` or `<@!id>`.
+    User(u64),
+    /// `<#id>`.
+    Channel(u64),
+    /// `<@&id>`.
+    Role(u64),
+}
+
+impl Mention {
+    /// Said when the name can't be found.
+    fn fallback(self) -> &'static str {
+        match self {
+            Mention::User(_) => "someone",
+            Mention::Channel(_) => "a channel",
+            Mention::Role(_) => "a role",
+        }
+    }
+}
+
+/// How [`prepare`] treats a piece of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Longest text spoken, in characters.
+    pub max_chars: usize,
+
+    /// Whether DECtalk `[:...]` commands and phoneme brackets are obeyed.
+    pub inline_commands: bool,
+}
+
+/// Run every step on `text`, looking up mentions with `name`.
+pub fn prepare(
+    text: &str,
+    options: Options,
+    name: impl FnMut(Mention) -> Option<String>,
+) -> String {
+    let text = transliterate(&resolve_mentions(text, name));
+    let text = if options.inline_commands {
+        text
+    } else {
+        neutralise_commands(&text)
+    };
+    truncate(&text, options.max_chars).to_string()
+}
+
+/// Replace Discord mentions and custom emoji with readable names.
+///
+/// `name` is asked for users, channels and roles; when it has none the
+/// mention is read as "someone", "a channel" or "a role". Custom emoji are
+/// read by their name, with underscores as spaces. Anything else in angle
+/// brackets is left alone.
+pub fn resolve_mentions(text: &str, mut name: impl FnMut(Mention) -> Option<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        if let Some(mention) = parse_mention(tag) {
+            out.push_str(&name(mention).unwrap_or_else(|| mention.fallback().to_string()));
+        } else if let Some(emoji) = parse_emoji(tag) {
+            out.push_str(&emoji.replace('_', " "));
+        } else {
+            out.push('<');
+            rest = &rest[1..];
+            continue;
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The mention in `tag`, the text between `<` and `>`.
+fn parse_mention(tag: &str) -> Option<Mention> {
+    let id = |digits: &str| {
+        (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| digits.parse().ok())
+            .flatten()
+            .filter(|&id| id != 0)
+    };
+    if let Some(role) = tag.strip_prefix("@&") {
+        id(role).map(Mention::Role)
+    } else if let Some(user) = tag.strip_prefix('@') {
+        id(user.strip_prefix('!').unwrap_or(user)).map(Mention::User)
+    } else if let Some(channel) = tag.strip_prefix('#') {
+        id(channel).map(Mention::Channel)
+    } else {
+        None
+    }
+}
+
+/// The name of the custom emoji in `tag`: `:name:id` or `a:name:id`.
+fn parse_emoji(tag: &str) -> Option<&str> {
+    let tag = tag.strip_prefix('a').unwrap_or(tag);
+    let (name, id) = tag.strip_prefix(':')?.split_once(':')?;
+    let valid = !name.is_empty()
+        && !id.is_empty()
+        && id.bytes().all(|b| b.is_ascii_digit())
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+    valid.then_some(name)
+}
+
+/// Make `text` ASCII for DECtalk.
+///
+/// Accented Latin letters lose their accents, ligatures are spelled out and
+/// typographic quotes, dashes and spaces become their ASCII versions.
+/// Combining marks are dropped; any other character (emoji, other scripts,
+/// control characters) becomes a space, so the words around it stay apart.
+pub fn transliterate(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\n' | '\t' | ' '..='~' => out.push(c),
+            '\u{0300}'..='\u{036F}' => {}
+            _ => out.push_str(ascii_for(c).unwrap_or(" ")),
+        }
+    }
+    out
+}
+
+/// The ASCII spelling of `c`, for the characters [`transliterate`] knows.
+fn ascii_for(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì'..='Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Þ' => "Th",
+        'þ' => "th",
+        'Ù'..='Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '‘' | '’' | '‚' | '′' => "'",
+        '“' | '”' | '„' | '″' | '«' | '»' => "\"",
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => "-",
+        '…' => "...",
+        '×' => "x",
+        '\u{A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' => " ",
+        _ => return None,
+    })
+}
+
+/// Remove `[` and `]`, so DECtalk reads every `[:...]` command and
+/// `[...]` phoneme bracket as ordinary text.
+pub fn neutralise_commands(text: &str) -> String {
+    text.replace(['[', ']'], " ")
+}
+
+/// The first `max_chars` characters of `text`. Unless they end right before
+/// a word break, they're cut back to the last one in their second half, if
+/// there is one.
+pub fn truncate(text: &str, max_chars: usize) -> &str {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+    let head = &text[..cut];
+    if text[cut..].starts_with(char::is_whitespace) {
+        return head.trim_end();
+    }
+    match head.rfind(char::is_whitespace) {
+        Some(space) if head[..space].chars().count() >= max_chars / 2 => head[..space].trim_end(),
+        _ => head,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(mention: Mention) -> Option<String> {
+        match mention {
+            Mention::User(1) => Some("Ada".to_string()),
+            Mention::Channel(2) => Some("general".to_string()),
+            Mention::Role(3) => Some("Mods".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn resolves_users_channels_and_roles() {
+        assert_eq!(
+            resolve_mentions("hi <@1> and <@!1> in <#2>, ping <@&3>", names),
+            "hi Ada and Ada in general, ping Mods"
+        );
+    }
+
+    #[test]
+    fn unknown_mentions_use_fallbacks() {
+        assert_eq!(
+            resolve_mentions("<@9> <#9> <@&9>", names),
+            "someone a channel a role"
+        );
+    }
+
+    #[test]
+    fn custom_emoji_read_by_name() {
+        assert_eq!(
+            resolve_mentions("nice <:thumbs_up:123> <a:party_cat:456>", names),
+            "nice thumbs up party cat"
+        );
+    }
+
+    #[test]
+    fn leaves_other_angle_brackets_alone() {
+        assert_eq!(
+            resolve_mentions("1 < 2, <@> <#x> <@0> <t:123:R> <@1", names),
+            "1 < 2, <@> <#x> <@0> <t:123:R> <@1"
+        );
+        assert_eq!(resolve_mentions("<<@1>>", names), "<Ada>");
+    }
+
+    #[test]
+    fn transliterates_accents_and_punctuation() {
+        assert_eq!(
+            transliterate("Crème brûlée — “naïve” Œuvre… it’s Straße"),
+            "Creme brulee - \"naive\" OEuvre... it's Strasse"
+        );
+    }
+
+    #[test]
+    fn drops_combining_marks() {
+        assert_eq!(transliterate("e\u{301}te\u{301}"), "ete");
+    }
+
+    #[test]
+    fn replaces_unknown_characters_with_spaces() {
+        assert_eq!(transliterate("hi😀there 你好\0!"), "hi there    !");
+        assert!(transliterate("ünïcødé 🎉 text").is_ascii());
+    }
+
+    #[test]
+    fn neutralises_inline_commands() {
+        let text = neutralise_commands("[:rate 600][:dv ap 500] hello [hx'ehlow<999,30>]");
+        assert!(!text.contains(['[', ']']));
+        assert!(text.contains("hello"));
+    }
+
+    #[test]
+    fn truncate_keeps_short_text() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly10!", 10), "exactly10!");
+    }
+
+    #[test]
+    fn truncate_cuts_at_word_break() {
+        assert_eq!(truncate("the quick brown fox", 12), "the quick");
+        assert_eq!(truncate("the quick brown fox", 9), "the quick");
+    }
+
+    #[test]
+    fn truncate_cuts_mid_word_without_late_break() {
+        assert_eq!(truncate("a supercalifragilistic", 10), "a supercal");
+    }
+
+    #[test]
+    fn truncate_respects_char_boundaries() {
+        assert_eq!(truncate("ééééé", 3), "ééé");
+    }
+
+    #[test]
+    fn prepare_runs_every_step() {
+        let options = Options {
+            max_chars: 20,
+            inline_commands: false,
+        };
+        assert_eq!(
+            prepare(
+                "[:dv ap 900]<@1> says héllo to everyone here",
+                options,
+                names
+            ),
+            " :dv ap 900 Ada says"
+        );
+        let options = Options {
+            max_chars: 100,
+            inline_commands: true,
+        };
+        assert_eq!(
+            prepare("[:rate 200]<@1> dit “salut”", options, names),
+            "[:rate 200]Ada dit \"salut\""
+        );
+    }
+}
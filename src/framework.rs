@@ -2,38 +2,60 @@
 //!
 //! This module does the bulk of the bot's startup work:
 //!
-//! 1. **Load the user database** from `user.json` (or start fresh).
+//! 1. **Load the user database** from the [`crate::store::UserStore`] chosen
+//!    with `--store` (or start fresh), and run startup migrations (e.g.
+//!    folding legacy global wallets into `--home-guild`). Then load the shop
+//!    catalog from `--catalog-path`.
 //! 2. **Spawn the persistence task** — a `tokio::spawn` loop that receives
-//!    [`PersistentData`] messages and writes them to disk.  Routing all I/O
-//!    through a single channel ensures that concurrent commands never race on
-//!    file writes.
-//! 3. **Spawn the schedule reminder task** — an outer loop receives
-//!    `(UserId, ScheduleEvent)` pairs and spawns per-event `tokio::time::sleep`
-//!    tasks that DM the user when the event time arrives.
-//! 4. **Re-queue persisted events** — on every startup, all events currently
-//!    in the database are sent to the reminder task so reminders survive bot
-//!    restarts.
-//! 5. **Build and return the [`poise::Framework`]**.
+//!    [`PersistentData`] messages and hands them to the store.  Routing all
+//!    I/O through a single channel ensures that concurrent commands never race
+//!    on writes.
+//! 3. **Spawn the schedule reminder task** — a single scheduler holds every
+//!    event's next reminder in a [`ReminderQueue`], taking
+//!    [`ReminderCommand`]s to schedule, reschedule or cancel them by event
+//!    ID. When a reminder is due it DMs the user (or, for a group event
+//!    owned by that user, posts in its channel and DMs its RSVPs), then
+//!    re-queues the next reminder or the next occurrence of a repeating
+//!    event.
+//! 4. **Re-queue persisted events** — on every startup, every event in the
+//!    database with a reminder still to send is sent to the reminder task so
+//!    reminders survive bot restarts without repeating ones already sent.
+//! 5. **Spawn the activity log** — a subscriber on the internal
+//!    [`EventBus`] that writes every domain event to the debug log.
+//! 6. **Build and return the [`poise::Framework`]**.
 
+use crate::Args;
 use crate::commands;
+use crate::dectalk::pool::Pool;
 use crate::handlers;
-use crate::pawthos::consts::FAUCET_EXPIRY_SECS;
-use crate::pawthos::enums::persistent_data::PersistentData;
+use crate::pawthos::consts::{
+    DECTALK_JOB_TIMEOUT_SECS, DECTALK_QUEUE_DEPTH, DECTALK_WORKERS, FAUCET_EXPIRY_SECS,
+    SCHEDULE_MISSED_GRACE_SECS,
+};
+use crate::pawthos::enums::domain_event::DomainEvent;
 use crate::pawthos::enums::persistent_data::UserDailyClaimed;
+use crate::pawthos::enums::persistent_data::{PersistentData, RowKey};
+use crate::pawthos::enums::reminder_command::ReminderCommand;
+use crate::pawthos::structs::catalog_file;
 use crate::pawthos::structs::data::{BountyState, Data};
-use crate::pawthos::structs::schedule_event::ScheduleEvent;
+use crate::pawthos::structs::event_bus::EventBus;
+use crate::pawthos::structs::lead_time::LeadTime;
+use crate::pawthos::structs::reminder_queue::ReminderQueue;
+use crate::pawthos::structs::schedule_event::{Reminder, ScheduleEvent};
+use crate::pawthos::structs::schedule_user::Fired;
 use crate::pawthos::structs::user_db::UserDB;
-use crate::pawthos::types::{Error, Result};
-use crate::utils;
+use crate::pawthos::types::Error;
+use crate::store;
+use crate::utils::{self, ResultExt};
+use crate::voice::player::Players;
 use chrono::Utc;
 use poise::serenity_prelude as serenity;
-use poise::serenity_prelude::{ChannelId, MessageId, UserId};
-use serde::Deserialize;
-use serde::Serialize;
+use poise::serenity_prelude::{ChannelId, GuildId, MessageId, UserId};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::sync::broadcast::error::RecvError;
 
 /// Internal channel buffer size for the persistence task.
 ///
@@ -42,133 +64,72 @@ use tokio::sync::RwLock;
 const BUFFER_SIZE: usize = 8;
 
 // ---------------------------------------------------------------------------
-// User DB persistence
+// Startup migrations
 // ---------------------------------------------------------------------------
 
-/// Write `db` to `user.json` atomically (write to `.tmp`, then rename).
-///
-/// The atomic rename prevents a partially-written file from corrupting the
-/// database if the process is killed mid-write.
-fn save_user_db(db: UserDB) -> Result {
-    let db_json = poise::serenity_prelude::json::to_string(&db)?;
-    std::fs::write("user.json.tmp", &db_json)?;
-    std::fs::rename("user.json.tmp", "user.json")?;
-    log::debug!("user.json saved :3c");
-    Ok(())
-}
-
-/// Load the user database from `user.json`.
-///
-/// Falls back to an empty [`UserDB`] if the file is absent or malformed,
-/// logging a warning/error accordingly so the operator knows what happened.
-fn load_user_db() -> UserDB {
-    let user_db = std::fs::read_to_string("user.json").map(serenity::json::from_str::<UserDB>);
-
-    match user_db {
-        Ok(Ok(db)) => {
-            log::info!("user.json found, importing db..");
-            db
-        }
-        Ok(Err(e)) => {
-            log::error!("user.json exists but deserialization failed: {e}. Starting with empty DB.");
-            Default::default()
-        }
-        Err(_) => {
-            log::warn!("user.json NOT found, making new db..");
-            Default::default()
-        }
-    }
-}
-
 /// Run idempotent startup migrations against the in-memory [`UserDB`].
 ///
-/// Called once right after [`load_user_db`]. Every rule checks its "is this
-/// already migrated?" condition first so re-running on every startup is safe.
+/// Called once right after the store is loaded. Every rule checks its "is
+/// this already migrated?" condition first so re-running on every startup is
+/// safe. Returns `true` if anything changed, so the caller knows to write
+/// the result back to the store.
 ///
-/// Currently a no-op — the prior banner/colorway unlock grandfathering was
-/// removed when those features moved to a per-set charge model. Stale
-/// `unlocked_custom_banner` / `unlocked_custom_colorway` / `owned_banners` /
-/// `active_banner_id` fields in old `user.json` snapshots are silently
-/// dropped by serde (no `deny_unknown_fields` on these structs).
-fn run_migrations(_user_db: &mut UserDB) {}
-
-// ---------------------------------------------------------------------------
-// Daily claim log
-// ---------------------------------------------------------------------------
-
-/// The daily-claim tracking record.
+/// Stale `unlocked_custom_banner` / `unlocked_custom_colorway` /
+/// `owned_banners` / `active_banner_id` fields in old `user.json` snapshots
+/// are silently dropped by serde (no `deny_unknown_fields` on these structs).
 ///
-/// Persisted as `wallet_list.json` (filename retained for compatibility with
-/// existing deployments). Resets automatically when [`DailyClaimLog::date`]
-/// falls behind the current local date — no cron job or scheduled reset is
-/// needed.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-struct DailyClaimLog {
-    /// The date for which `list` was last updated.
-    date: chrono::NaiveDate,
-
-    /// Raw user IDs of users who have already claimed today.
-    list: Vec<u64>,
-}
-
-/// Write the daily-claim log to disk atomically.
-fn save_daily_claim_log(claim_log: DailyClaimLog) -> Result {
-    const FILE_PATH: &str = "wallet_list.json";
-    let claim_log_json = poise::serenity_prelude::json::to_string(&claim_log)?;
-    let tmp_path = format!("{FILE_PATH}.tmp");
-    std::fs::write(&tmp_path, &claim_log_json)?;
-    std::fs::rename(&tmp_path, FILE_PATH)?;
-    log::debug!("{} saved :3c", FILE_PATH);
-    Ok(())
-}
+/// # Rules
+/// - **Guild-scoped economy.** Global `wallet` / `inventory` records from
+///   before economies were split per guild are moved into `home_guild`.
+///   A user who already has an entry in the home guild gets the legacy
+///   record merged into it: tabs added, owned items and achievements
+///   combined, the higher streak and stats kept. Without a home guild the
+///   legacy records are left in place (and keep being written back) until
+///   one is configured.
+fn run_migrations(user_db: &mut UserDB, home_guild: Option<GuildId>) -> bool {
+    let pending = user_db
+        .db
+        .values()
+        .filter(|u| u.legacy_wallet.is_some() || u.legacy_inventory.is_some())
+        .count();
+    if pending == 0 {
+        return false;
+    }
 
-/// Load the daily-claim log from disk, returning an empty log on
-/// missing/corrupt file.
-fn load_daily_claim_log() -> Result<DailyClaimLog, Error> {
-    const FILE_PATH: &str = "wallet_list.json";
-    let claim_log =
-        std::fs::read_to_string(FILE_PATH).map(serenity::json::from_str::<DailyClaimLog>);
+    let Some(home_guild) = home_guild else {
+        log::warn!(
+            "{pending} user(s) still have a global wallet/inventory; pass --home-guild to migrate them."
+        );
+        return false;
+    };
 
-    match claim_log {
-        Ok(Ok(db)) => {
-            log::info!("{} found, importing..", FILE_PATH);
-            Ok(db)
+    let guild = user_db.guilds.entry(home_guild).or_default();
+    let mut merged = 0;
+    for (id, user) in user_db.db.iter_mut() {
+        let wallet = user.legacy_wallet.take();
+        let inventory = user.legacy_inventory.take();
+        if wallet.is_none() && inventory.is_none() {
+            continue;
         }
-        Ok(Err(e)) => {
-            log::error!("{FILE_PATH} exists but deserialization failed: {e}. Starting fresh.");
-            Ok(Default::default())
-        }
-        Err(_) => {
-            log::warn!("{} NOT found, making new..", FILE_PATH);
-            Ok(Default::default())
+        let existed = guild.db.contains_key(id);
+        let member = guild.get_user_mut(*id);
+        if existed {
+            merged += 1;
+            if let Some(wallet) = wallet {
+                member.wallet.merge(wallet);
+            }
+            if let Some(inventory) = inventory {
+                member.inventory.merge(inventory);
+            }
+        } else {
+            member.wallet = wallet.unwrap_or_default();
+            member.inventory = inventory.unwrap_or_default();
         }
     }
-}
-
-/// Check whether user `id` has already claimed their daily reward today, and
-/// if not, mark them as having claimed it.
-///
-/// The log resets when its stored `date` is earlier than today. All I/O is
-/// synchronous because this runs inside the single-threaded persistence task
-/// loop (no async needed, no risk of concurrent access).
-fn daily_check(id: u64) -> Result<UserDailyClaimed, Error> {
-    let mut claim_log = load_daily_claim_log()?;
-
-    let today = chrono::Local::now().date_naive();
-    if claim_log.date < today {
-        claim_log.list.clear();
-        claim_log.date = today;
-    }
-
-    let result = if claim_log.list.contains(&id) {
-        UserDailyClaimed::Claimed
-    } else {
-        claim_log.list.push(id);
-        UserDailyClaimed::Unclaimed
-    };
-
-    save_daily_claim_log(claim_log)?;
-    Ok(result)
+    log::info!(
+        "Migrated {pending} global economy record(s) into guild {home_guild}, {merged} of them merged into an existing member."
+    );
+    true
 }
 
 // ---------------------------------------------------------------------------
@@ -178,30 +139,50 @@ fn daily_check(id: u64) -> Result<UserDailyClaimed, Error> {
 /// Build and return the configured [`poise::Framework`].
 ///
 /// This is the primary entry point called from [`crate::setup`]. See the
-/// module-level documentation for the full startup sequence.
-pub fn setup_framework() -> poise::Framework<Data, Error> {
-    let mut user_db = load_user_db();
-    run_migrations(&mut user_db);
+/// module-level documentation for the full startup sequence. `owners` (from
+/// `s.json`) become the framework's owner set, which Poise extends with the
+/// application owner at startup; [`crate::checks::owner`] reads it.
+///
+/// # Panics
+/// Panics (via [`ResultExt::unwrap_or_log`]) if the storage backend selected
+/// by `--store` cannot be opened or loaded, or if the catalog file at
+/// `--catalog-path` is invalid. A missing or corrupt `user.json`
+/// is *not* fatal — the JSON backend starts with an empty database instead.
+pub fn setup_framework(args: &Args, owners: Vec<UserId>) -> poise::Framework<Data, Error> {
+    let mut store =
+        store::open(args.store, &args.sqlite_path).unwrap_or_log("framework::store::open");
+    let mut user_db = store.load().unwrap_or_log("framework::store::load");
+    catalog_file::load(&args.catalog_path).unwrap_or_log("framework::catalog_file::load");
+    let catalog_path = args.catalog_path.clone();
+    if run_migrations(&mut user_db, args.home_guild.map(GuildId::new)) {
+        store
+            .save_all(&user_db)
+            .unwrap_or_log("framework::store::save_all");
+    }
 
     // --- Persistence task ---------------------------------------------------
-    // All DB snapshots and daily-check requests flow through this channel.
+    // All changed rows and daily-check requests flow through this channel.
     // The task runs forever (until the process exits) and handles one message
-    // at a time, serialising all file I/O.
+    // at a time, so the store never sees two calls at once.
     let (send, mut recv) = tokio::sync::mpsc::channel(BUFFER_SIZE);
     tokio::spawn(async move {
         while let Some(update) = recv.recv().await {
             log::debug!("update received! type: {:?}", update);
             match update {
-                PersistentData::UserDB(user_db_snapshot) => {
-                    if let Err(e) = save_user_db(user_db_snapshot) {
-                        log::error!("Failed to save UserDB: {:?}", e);
+                PersistentData::Rows(rows) => {
+                    if let Err(e) = store.save_rows(rows) {
+                        log::error!("Failed to save UserDB rows: {:?}", e);
                     }
                 }
-                PersistentData::DailyCheck { user_id, sender } => {
-                    let user_daily_claimed_status = match daily_check(user_id) {
+                PersistentData::DailyCheck {
+                    guild_id,
+                    user_id,
+                    sender,
+                } => {
+                    let user_daily_claimed_status = match store.daily_check(guild_id, user_id) {
                         Ok(user_daily_claimed) => user_daily_claimed,
                         Err(e) => {
-                            log::error!("Failed to save wallet_list!!: {:?}", e);
+                            log::error!("Failed to record daily claim!!: {:?}", e);
 
                             // if there's an error saving.. just assume that the user did not claim
                             // their daily..
@@ -216,6 +197,11 @@ pub fn setup_framework() -> poise::Framework<Data, Error> {
         }
     });
 
+    // Shared with the reminder task, which moves repeating events on after
+    // they fire.
+    let startup_events = user_db.get_events();
+    let user_db = Arc::new(RwLock::new(user_db));
+
     poise::Framework::builder()
         .options(poise::FrameworkOptions {
             commands: commands::return_commands(),
@@ -225,12 +211,14 @@ pub fn setup_framework() -> poise::Framework<Data, Error> {
                 ..Default::default()
             },
             event_handler: handlers::event_handler,
+            owners: owners.into_iter().collect(),
             ..Default::default()
         })
         .setup(|ctx, _ready, framework| {
-            // We need three handles to `ctx.http` for the spawned async tasks
-            // below: the cleanup task, the schedule reminder task, and the
-            // per-event sleep task. Each `.clone()` is just an Arc bump.
+            // The spawned tasks below each need a handle to `ctx.http`: the
+            // cleanup task and the schedule reminder task, which passes its
+            // own clone to every reminder it sends. Each `.clone()` is just
+            // an Arc bump.
             let http = ctx.http.clone();
 
             // --- Faucet state + cleanup task --------------------------------
@@ -256,47 +244,85 @@ pub fn setup_framework() -> poise::Framework<Data, Error> {
             }
 
             // --- Schedule reminder task -------------------------------------
-            // The outer loop receives (UserId, ScheduleEvent) pairs and spawns
-            // a dedicated sleep task for each one. The three clones of `http`
-            // satisfy Tokio's `'static` requirement for spawned futures without
-            // copying any real data (just an Arc bump).
+            // One scheduler task owns the queue of every event's next
+            // reminder. It sleeps until the earliest is due or a command
+            // arrives, whichever is first, and spawns a short `remind` task
+            // for each reminder that comes due.
+            //
+            // The event bus is created here, ahead of `Data`, so the
+            // reminder task can publish `ReminderFired` on it.
+            let events = EventBus::default();
             let (send_tasks, mut recv_tasks) =
-                tokio::sync::mpsc::unbounded_channel::<(UserId, ScheduleEvent)>();
+                tokio::sync::mpsc::unbounded_channel::<ReminderCommand>();
             tokio::spawn({
                 let http = http.clone();
+                let events = events.clone();
+                let user_db = user_db.clone();
+                let persist = send.clone();
+                let requeue = send_tasks.clone();
                 async move {
-                    while let Some((id, event)) = recv_tasks.recv().await {
-                        let http = http.clone();
-                        tokio::spawn(async move {
-                            let now = chrono::Utc::now();
-                            let Ok(time_delta) = event.when.signed_duration_since(now).to_std()
-                            else {
-                                log::warn!("Event in past: {:#?}", event);
-                                return;
-                            };
+                    let mut queue = ReminderQueue::default();
+                    loop {
+                        let next_due = queue.next_due();
+                        let sleep = async {
+                            match next_due {
+                                Some(at) => {
+                                    let wait = at.signed_duration_since(Utc::now());
+                                    tokio::time::sleep(wait.to_std().unwrap_or_default()).await
+                                }
+                                None => std::future::pending().await,
+                            }
+                        };
+                        tokio::select! {
+                            command = recv_tasks.recv() => match command {
+                                Some(ReminderCommand::Schedule(user_id, event)) => {
+                                    queue.schedule(user_id, *event)
+                                }
+                                Some(ReminderCommand::Cancel(user_id, id)) => {
+                                    queue.cancel(user_id, id);
+                                }
+                                None => break,
+                            },
+                            _ = sleep => {
+                                for (user_id, event) in queue.pop_due(Utc::now()) {
+                                    tokio::spawn(remind(
+                                        http.clone(),
+                                        events.clone(),
+                                        user_db.clone(),
+                                        persist.clone(),
+                                        requeue.clone(),
+                                        user_id,
+                                        event,
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            });
 
-                            tokio::time::sleep(time_delta).await;
-                            // send the user a message.
-                            if let Ok(dm) = id.create_dm_channel(&http).await {
-                                let _ = dm
-                                    .say(
-                                        &http,
-                                        format!(
-                                            "⏰ Reminder: **{}** is happening **now!**",
-                                            event.name
-                                        ),
-                                    )
-                                    .await;
+            // --- Activity log -----------------------------------------------
+            // First subscriber on the event bus besides the built-in ones in
+            // `Data::publish`.
+            tokio::spawn({
+                let mut events = events.subscribe();
+                async move {
+                    loop {
+                        match events.recv().await {
+                            Ok(event) => log::debug!("activity: {event}"),
+                            Err(RecvError::Lagged(missed)) => {
+                                log::warn!("Activity log fell behind; {missed} event(s) skipped.");
                             }
-                        });
+                            Err(RecvError::Closed) => break,
+                        }
                     }
                 }
             });
 
             // Re-queue all events that survived a bot restart.
             let send2 = send_tasks.clone();
-            user_db.get_events().into_iter().for_each(|pair| {
-                if let Err(e) = send2.send(pair) {
+            startup_events.into_iter().for_each(|(user_id, event)| {
+                if let Err(e) = send2.send(ReminderCommand::Schedule(user_id, Box::new(event))) {
                     log::error!("Failed to queue startup reminder event: {e}");
                 }
             });
@@ -304,11 +330,19 @@ pub fn setup_framework() -> poise::Framework<Data, Error> {
             Box::pin(async move {
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 Ok(Data {
-                    user_db: RwLock::new(user_db),
+                    user_db,
                     persistent_data_channel: send,
                     schedule_events_channel: send_tasks,
                     faucet_bounties,
                     faucet_last_spawn,
+                    catalog_path,
+                    events,
+                    voice: Players::default(),
+                    tts: Pool::new(
+                        DECTALK_WORKERS,
+                        DECTALK_QUEUE_DEPTH,
+                        Duration::from_secs(DECTALK_JOB_TIMEOUT_SECS),
+                    ),
                 })
             })
         })
@@ -322,8 +356,10 @@ pub fn setup_framework() -> poise::Framework<Data, Error> {
 /// Remove bot reactions from expired faucet bounties and drop them from the map.
 ///
 /// Called on a timer (every `FAUCET_EXPIRY_SECS / 10` seconds, so with the
-/// default 600 s expiry we run every minute). Reactions on deleted messages
-/// return a Discord error which we log at debug and ignore.
+/// default 600 s expiry we run every minute). Guilds that shorten
+/// `faucet_expiry_secs` (60 s at the least) may see a bounty outlive its
+/// expiry by up to one sweep. Reactions on deleted messages return a Discord
+/// error which we log at debug and ignore.
 async fn cleanup_expired_bounties(
     bounties: &RwLock<HashMap<MessageId, BountyState>>,
     http: &serenity::Http,
@@ -354,3 +390,177 @@ async fn cleanup_expired_bounties(
         b.remove(&msg_id);
     }
 }
+
+// ---------------------------------------------------------------------------
+// Schedule reminders
+// ---------------------------------------------------------------------------
+
+/// Send the reminder of `event` that the scheduler found due, for its owner
+/// `user_id` (or everyone on a group event), and queue whatever comes next:
+/// the same occurrence's next reminder, or the next occurrence of a
+/// repeating event.
+///
+/// The stored event is checked first, so a reminder for an event that was
+/// changed in the meantime is dropped, and a group event is announced with
+/// its current channel and RSVPs. Which reminders were sent is saved with
+/// the event, so a restart picks up after the last one. Reminders whose time
+/// passed while the bot was offline aren't all sent late: only the latest
+/// missed lead-time reminder goes out, and only if the event hasn't started.
+/// A missed start is dropped with a warning for a one-off, and skipped for a
+/// repeating event. A snooze is always sent, however late.
+///
+/// The owner's DM has snooze and done buttons, handled in
+/// [`crate::handlers`]; a group event's channel post and RSVP DMs don't.
+async fn remind(
+    http: Arc<serenity::Http>,
+    events: EventBus,
+    user_db: Arc<RwLock<UserDB>>,
+    persist: tokio::sync::mpsc::Sender<PersistentData>,
+    requeue: tokio::sync::mpsc::UnboundedSender<ReminderCommand>,
+    user_id: UserId,
+    event: ScheduleEvent,
+) {
+    let at_start = Reminder::Lead(LeadTime::AT_START);
+    let Some((reminder, at)) = event.next_reminder() else {
+        return;
+    };
+    let missed = Utc::now().signed_duration_since(at)
+        > chrono::Duration::seconds(SCHEDULE_MISSED_GRACE_SECS);
+    if missed && reminder == at_start && event.recurrence.is_none() {
+        log::warn!("Event in past: {:#?}", event);
+        return;
+    }
+
+    let (fired, rows) = {
+        let mut db = user_db.write().await;
+        let Some(user) = db.db.get_mut(&user_id) else {
+            return;
+        };
+        let fired = user.schedule.fire(&event, Utc::now());
+        let rows: Vec<_> = match fired {
+            Some(_) => db.row(RowKey::User(user_id)).into_iter().collect(),
+            None => Vec::new(),
+        };
+        (fired, rows)
+    };
+    // `event` becomes the stored copy, in case a group event has changed
+    // channel or gained RSVPs since it was queued.
+    let Some(Fired {
+        event,
+        reminder,
+        next,
+    }) = fired
+    else {
+        return;
+    };
+    if let Err(e) = persist.send(PersistentData::Rows(rows)).await {
+        log::error!("Failed to queue DB save: {:?}", e);
+    }
+
+    let now = Utc::now();
+    let late_but_useful = match reminder {
+        Reminder::Snooze(_) => true,
+        Reminder::Lead(lead) => {
+            lead != LeadTime::AT_START
+                && event.when > now
+                && next
+                    .as_ref()
+                    .and_then(|n| n.pending_reminder())
+                    .is_none_or(|(_, at)| at > now)
+        }
+    };
+    if missed && !late_but_useful {
+        log::info!(
+            "Skipped missed reminder ({reminder:?}) of {:?} for {user_id}",
+            event.name
+        );
+    } else {
+        let ts = event.when.timestamp();
+        let (mut message, occurrence) = match reminder {
+            Reminder::Lead(LeadTime::AT_START) => (
+                format!("⏰ Reminder: **{}** is happening **now!**", event.name),
+                event.when,
+            ),
+            Reminder::Lead(_) => (
+                format!(
+                    "⏰ Reminder: **{}** starts <t:{ts}:R> (<t:{ts}:F>).",
+                    event.name
+                ),
+                event.when,
+            ),
+            Reminder::Snooze(snooze) => (
+                format!(
+                    "⏰ Snoozed reminder: **{}** (<t:{}:F>).",
+                    event.name,
+                    snooze.occurrence.timestamp()
+                ),
+                snooze.occurrence,
+            ),
+        };
+        let next_occurrence = next.as_ref().filter(|n| n.when != event.when);
+        if let Some(next) = next_occurrence {
+            message.push_str(&format!("\nNext: <t:{}:F>", next.when.timestamp()));
+        }
+        match &event.group {
+            Some(group) => {
+                let mention = group
+                    .role_id
+                    .map(|role| format!(" <@&{role}>"))
+                    .unwrap_or_default();
+                let post = serenity::CreateMessage::new()
+                    .content(format!("{message}{mention}"))
+                    .allowed_mentions(serenity::CreateAllowedMentions::new().roles(group.role_id));
+                if let Err(e) = group.channel_id.send_message(&http, post).await {
+                    log::warn!("Couldn't post reminder for {:?}: {e}", event.name);
+                }
+                utils::dm_rsvps(
+                    &http,
+                    &event,
+                    None,
+                    &format!("{message}\nin <#{}>", group.channel_id),
+                )
+                .await;
+                // Show the next occurrence once this one starts, or close
+                // the RSVPs after the last.
+                match next_occurrence {
+                    Some(next) => {
+                        utils::update_group_announcement(
+                            &http,
+                            next,
+                            user_id,
+                            utils::Announcement::Open,
+                        )
+                        .await
+                    }
+                    None if next.is_none() && reminder == at_start => {
+                        utils::update_group_announcement(
+                            &http,
+                            &event,
+                            user_id,
+                            utils::Announcement::Started,
+                        )
+                        .await
+                    }
+                    None => {}
+                }
+            }
+            None => {
+                if let Ok(dm) = user_id.create_dm_channel(&http).await {
+                    let _ = dm
+                        .send_message(&http, utils::reminder_dm(&event, occurrence, &message))
+                        .await;
+                }
+            }
+        }
+        events.emit(DomainEvent::ReminderFired {
+            user_id,
+            event_name: event.name,
+        });
+    }
+
+    if let Some(next) = next
+        && let Err(e) = requeue.send(ReminderCommand::Schedule(user_id, Box::new(next)))
+    {
+        log::error!("Failed to queue next reminder: {e}");
+    }
+}
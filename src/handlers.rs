@@ -6,25 +6,33 @@
 //!   Shows a Discord embed to the user for command errors, and auto-corrects
 //!   the "auto-mode but no active mimic" edge case.
 //!
-//! - [`event_handler`] — called for every Discord gateway event. Only
-//!   [`serenity::FullEvent::Message`] events are acted upon: if the message
-//!   author has mimic auto-mode enabled, the message is re-sent via webhook
-//!   as the active mimic persona and the original is deleted.
+//! - [`event_handler`] — called for every Discord gateway event. Messages
+//!   drive mimic auto-mode (the message is re-sent via webhook as the active
+//!   mimic persona and the original deleted) and the tab faucet, reactions
+//!   claim faucet bounties, button presses on group event announcements
+//!   update their RSVP lists, and the buttons on reminder DMs snooze the
+//!   reminder or mark it done.
 
-use crate::pawthos::consts::{
-    FAUCET_EXPIRY_SECS, FAUCET_GLOBAL_COOLDOWN_SECS, FAUCET_REWARD, FAUCET_TRIGGER_CHANCE,
-};
+use crate::pawthos::consts::FAUCET_GLOBAL_COOLDOWN_SECS;
 use crate::pawthos::enums::pawthos_errors::PawthosError;
-use crate::pawthos::enums::{embed_type::EmbedType, mimic_errors::MimicError};
+use crate::pawthos::enums::{
+    domain_event::DomainEvent, embed_type::EmbedType, ledger_reason::LedgerReason,
+    mimic_errors::MimicError, wallet_errors::WalletError,
+};
 use crate::pawthos::structs::data::{BountyState, Data};
+use crate::pawthos::structs::group_event::Rsvp;
+use crate::pawthos::structs::ledger_entry::TabMemo;
+use crate::pawthos::structs::snooze::ReminderButton;
 use crate::pawthos::types::Error;
 use crate::pawthos::types::Reply;
-use crate::utils;
+use crate::utils::{self, Announcement};
 use chrono::{Duration as ChronoDuration, Utc};
 use poise::FrameworkError;
 use poise::serenity_prelude as serenity;
 use rand::Rng;
-use serenity::{ExecuteWebhook, FullEvent, Message, Reaction};
+use serenity::{
+    ComponentInteraction, ExecuteWebhook, FullEvent, GuildId, Interaction, Message, Reaction,
+};
 use std::pin::Pin;
 
 /// Handle errors produced by commands or event callbacks.
@@ -38,6 +46,8 @@ use std::pin::Pin;
 /// | Error variant | Action |
 /// |---|---|
 /// | `Command { error, ctx }` | Send a red "ERROR" embed to the invoking channel |
+/// | `CommandCheckFailed { Permission, ctx }` | Send an ephemeral "Permission Denied" embed |
+/// | `CommandCheckFailed { other, ctx }` | Send an ephemeral "ERROR" embed |
 /// | `EventHandler { Message, NoActiveMimic }` | Disable auto-mode and notify the user |
 /// | `EventHandler { Message, other }` | Log at ERROR level |
 /// | Any other framework error | Log at ERROR level |
@@ -55,6 +65,33 @@ pub fn error_handler(
 
                 let _ = ctx.send(Reply::default().embed(embed)).await;
             }
+            // Raised by the checks in `crate::checks` when the caller's level
+            // is too low. `error` is `None` only if a check returns
+            // `Ok(false)`, which ours never do.
+            poise::FrameworkError::CommandCheckFailed { error, ctx, .. } => {
+                let embed = match error {
+                    Some(PawthosError::Permission(e)) => {
+                        log::info!(
+                            "{} denied /{}: {e}",
+                            ctx.author().name,
+                            ctx.command().qualified_name
+                        );
+                        utils::create_embed_builder("Permission Denied", e.to_string(), EmbedType::Bad)
+                    }
+                    Some(other) => utils::create_embed_builder(
+                        "ERROR",
+                        format!("Error in command check: {other}"),
+                        EmbedType::Bad,
+                    ),
+                    None => utils::create_embed_builder(
+                        "Permission Denied",
+                        "You can't use this command here.",
+                        EmbedType::Bad,
+                    ),
+                };
+
+                let _ = ctx.send(Reply::default().embed(embed).ephemeral(true)).await;
+            }
             poise::FrameworkError::EventHandler {
                 error,
                 ctx,
@@ -105,7 +142,8 @@ pub fn error_handler(
 
 /// React to Discord gateway events.
 ///
-/// Currently only handles [`FullEvent::Message`]; all other events are
+/// Handles [`FullEvent::Message`], [`FullEvent::ReactionAdd`] and component
+/// presses from [`FullEvent::InteractionCreate`]; all other events are
 /// silently ignored (debug-logged).
 ///
 /// # Auto-mode flow
@@ -133,6 +171,13 @@ pub fn event_handler<'a>(
             FullEvent::ReactionAdd { add_reaction } => {
                 handle_reaction_add(ctx, data, add_reaction).await
             }
+            // Each button handler ignores buttons that aren't its own.
+            FullEvent::InteractionCreate {
+                interaction: Interaction::Component(press),
+            } => {
+                handle_rsvp(ctx, data, press).await?;
+                handle_reminder_button(ctx, data, press).await
+            }
             _ => {
                 log::debug!("event: {}", event.snake_case_name());
                 Ok(())
@@ -150,7 +195,8 @@ pub fn event_handler<'a>(
 /// Runs three orthogonal sub-steps on every guild message from a non-bot
 /// author:
 ///
-/// 1. Increment the user's `messages_sent` stat (drives achievements).
+/// 1. Publish [`DomainEvent::MessageSent`] (bumps `messages_sent`, drives
+///    achievements).
 /// 2. Maybe spawn a faucet bounty (random + cooldown gated).
 /// 3. Execute mimic auto-mode if the user has it enabled.
 ///
@@ -168,18 +214,19 @@ async fn handle_message(
     let user_id = new_message.author.id;
     let channel_id = new_message.channel_id;
 
-    // Phase 7 hook: track guild-message count for achievements.
-    if new_message.guild_id.is_some() {
-        let _ = data
-            .with_inventory_user_write(user_id, |inv| {
-                inv.messages_sent = inv.messages_sent.saturating_add(1);
-                Ok(())
-            })
-            .await;
-        data.check_achievements(user_id, channel_id, &ctx.http).await;
+    if let Some(guild_id) = new_message.guild_id {
+        data.publish(
+            &ctx.http,
+            DomainEvent::MessageSent {
+                guild_id,
+                user_id,
+                channel_id,
+            },
+        )
+        .await;
 
         // Phase 5: chance to drop a faucet bounty on this message.
-        try_spawn_faucet_bounty(ctx, data, new_message).await;
+        try_spawn_faucet_bounty(ctx, data, guild_id, new_message).await;
     }
 
     // --- Mimic auto-mode path (existing behaviour) -------------------------
@@ -232,18 +279,23 @@ async fn handle_message(
 // ---------------------------------------------------------------------------
 
 /// Probabilistically attach a tab reaction to `new_message` and record a
-/// bounty. Gated by [`FAUCET_TRIGGER_CHANCE`] and [`FAUCET_GLOBAL_COOLDOWN_SECS`].
+/// bounty. Gated by the guild's `faucet_trigger_chance` and the bot-wide
+/// [`FAUCET_GLOBAL_COOLDOWN_SECS`].
 ///
 /// On success, a future [`FullEvent::ReactionAdd`] by *any* user (including
-/// the author) clicking the tab emoji will award them [`FAUCET_REWARD`] tabs.
+/// the author) clicking the tab emoji will award them the guild's
+/// `faucet_reward` tabs.
 async fn try_spawn_faucet_bounty(
     ctx: &serenity::Context,
     data: &Data,
+    guild_id: GuildId,
     new_message: &Message,
 ) {
-    // Roll first — most messages bail here without touching any locks.
+    // Roll first — most messages bail here without touching the faucet
+    // locks.
+    let config = data.guild_config(guild_id).await;
     let roll: f64 = rand::thread_rng().r#gen();
-    if roll >= FAUCET_TRIGGER_CHANCE {
+    if roll >= config.faucet_trigger_chance() {
         return;
     }
 
@@ -269,14 +321,15 @@ async fn try_spawn_faucet_bounty(
         return;
     }
 
-    let expires_at = now + ChronoDuration::seconds(FAUCET_EXPIRY_SECS);
+    let expires_at = now + ChronoDuration::seconds(config.faucet_expiry_secs());
     {
         let mut bounties = data.faucet_bounties.write().await;
         bounties.insert(
             new_message.id,
             BountyState {
+                guild_id,
                 channel_id: new_message.channel_id,
-                amount: FAUCET_REWARD,
+                amount: config.faucet_reward(),
                 expires_at,
             },
         );
@@ -326,18 +379,19 @@ async fn handle_reaction_add(
         return Ok(());
     };
 
-    // Award tabs + bump the claim counter.
-    data.with_wallet_user_write(reactor_id, |w| {
-        w.add_tabs(bounty.amount);
-        Ok(())
+    // Award tabs + bump the claim counter in the guild the bounty spawned in.
+    data.transact(|tx| {
+        tx.credit(
+            bounty.guild_id,
+            reactor_id,
+            bounty.amount,
+            TabMemo::new(LedgerReason::Faucet),
+        );
+        let inv = tx.inventory(bounty.guild_id, reactor_id);
+        inv.faucet_claims = inv.faucet_claims.saturating_add(1);
+        Ok::<_, WalletError>(())
     })
     .await?;
-    let _ = data
-        .with_inventory_user_write(reactor_id, |inv| {
-            inv.faucet_claims = inv.faucet_claims.saturating_add(1);
-            Ok(())
-        })
-        .await;
 
     // Remove both reactions. Removing the claimer's may fail without
     // MANAGE_MESSAGES — log & swallow so the payout still happens.
@@ -368,8 +422,147 @@ async fn handle_reaction_add(
         add_reaction.message_id,
     );
 
-    // Achievement check — "Quick Fingers" unlocks here.
-    data.check_achievements(reactor_id, bounty.channel_id, &ctx.http)
+    data.publish(
+        &ctx.http,
+        DomainEvent::FaucetClaimed {
+            guild_id: bounty.guild_id,
+            user_id: reactor_id,
+            channel_id: bounty.channel_id,
+            amount: bounty.amount,
+        },
+    )
+    .await;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Group event RSVP branch
+// ---------------------------------------------------------------------------
+
+/// Handle a press of a join/leave button on a group event announcement.
+///
+/// Other buttons (the `/pay` and `/schedule add` confirmations) are left to
+/// their collectors. The event is found in the creator's schedule, named in
+/// the button's custom ID, by the announcement's message ID. On success the
+/// announcement is re-rendered in place; otherwise the presser gets an
+/// ephemeral note saying why (already going, event gone, …).
+async fn handle_rsvp(
+    ctx: &serenity::Context,
+    data: &Data,
+    press: &ComponentInteraction,
+) -> std::result::Result<(), Error> {
+    let Some((rsvp, creator)) = Rsvp::parse(&press.data.custom_id) else {
+        return Ok(());
+    };
+
+    let result = data
+        .with_schedule_user_write(creator, |user| {
+            user.rsvp(press.message.id, press.user.id, rsvp)
+        })
+        .await;
+    let response = match result {
+        Ok(event) => {
+            let group = event
+                .group
+                .as_ref()
+                .expect("RSVPs are only kept on group events");
+            let (embed, buttons) = utils::group_announcement(
+                &event,
+                group.role_id,
+                &group.rsvps,
+                creator,
+                Announcement::Open,
+            );
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(buttons),
+            )
+        }
+        Err(e) => serenity::CreateInteractionResponse::Message(
+            serenity::CreateInteractionResponseMessage::new()
+                .content(e.to_string())
+                .ephemeral(true),
+        ),
+    };
+    press.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Reminder DM button branch
+// ---------------------------------------------------------------------------
+
+/// Handle a press of a snooze or done button on a reminder DM.
+///
+/// Only the owner gets these DMs, so the event is looked up by ID in the
+/// presser's own schedule. A snooze is queued straight away and a repeating
+/// event marked done moves on to its next occurrence. On success the DM
+/// says what happened and loses its buttons; otherwise the presser gets an
+/// ephemeral note saying why.
+async fn handle_reminder_button(
+    ctx: &serenity::Context,
+    data: &Data,
+    press: &ComponentInteraction,
+) -> std::result::Result<(), Error> {
+    let Some((button, id, occurrence)) = ReminderButton::parse(&press.data.custom_id) else {
+        return Ok(());
+    };
+    let user_id = press.user.id;
+    let now = Utc::now();
+
+    let result = data
+        .with_schedule_user_write(user_id, |user| {
+            match button.snooze_until(now, user.timezone) {
+                Some(at) => user.snooze(id, occurrence, at),
+                None => user.complete(id, occurrence, now),
+            }
+        })
+        .await;
+    let response = match &result {
+        Ok(event) => {
+            data.queue_reminder(user_id, event.clone());
+            let note = match event.snooze {
+                Some(snooze) if button != ReminderButton::Done => {
+                    format!("💤 Snoozed until <t:{}:F>.", snooze.at.timestamp())
+                }
+                _ => "✅ Marked done.".to_string(),
+            };
+            let embeds = press
+                .message
+                .embeds
+                .iter()
+                .map(|embed| {
+                    let description = embed.description.clone().unwrap_or_default();
+                    serenity::CreateEmbed::from(embed.clone())
+                        .description(format!("{description}\n\n{note}"))
+                })
+                .collect();
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embeds(embeds)
+                    .components(Vec::new()),
+            )
+        }
+        Err(e) => serenity::CreateInteractionResponse::Message(
+            serenity::CreateInteractionResponseMessage::new()
+                .content(e.to_string())
+                .ephemeral(true),
+        ),
+    };
+    press.create_response(&ctx.http, response).await?;
+
+    if let Ok(event) = result
+        && button == ReminderButton::Done
+    {
+        data.publish(
+            &ctx.http,
+            DomainEvent::ReminderCompleted {
+                user_id,
+                event_name: event.name,
+            },
+        )
         .await;
+    }
     Ok(())
 }
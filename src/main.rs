@@ -2,12 +2,18 @@
 //!
 //! Parses CLI arguments, initialises logging, builds the Poise/Serenity client,
 //! and starts the bot. The only startup I/O this module performs is reading the
-//! log-level flag; everything else is delegated to [`setup`].
+//! command-line flags; everything else is delegated to [`setup`].
 
 use clap::Parser;
 use log::LevelFilter;
 use poise::serenity_prelude as serenity;
+use std::path::PathBuf;
+use store::StoreKind;
 use utils::ResultExt;
+#[cfg(feature = "voice")]
+use songbird::SerenityInit;
+mod audio;
+mod checks;
 mod commands;
 mod dectalk;
 mod framework;
@@ -15,32 +21,66 @@ mod handlers;
 mod logging;
 mod pawthos;
 mod setup;
+mod store;
 mod utils;
+mod voice;
 
 /// Command-line arguments for logosV3.
 ///
 /// Pass `--log-level debug` (or `-l debug`) for verbose output during
-/// development. Defaults to `info` in production.
+/// development. Defaults to `info` in production. Pass `--store sqlite` to
+/// keep user data in an embedded SQLite database instead of `user.json`.
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 pub struct Args {
     /// Log level (error, warn, info, debug, trace)
     #[arg(short, long, default_value_t = LevelFilter::Info)]
     pub log_level: LevelFilter,
+
+    /// Guild that inherits wallets and inventories saved before economies
+    /// were scoped per guild. Only needed once, when upgrading an old
+    /// `user.json`.
+    #[arg(long)]
+    pub home_guild: Option<u64>,
+
+    /// Where user data is stored (json, sqlite)
+    #[arg(long, value_enum, default_value_t = StoreKind::Json)]
+    pub store: StoreKind,
+
+    /// SQLite database file, used with `--store sqlite` and `--import-json`
+    #[arg(long, default_value = "logos.db")]
+    pub sqlite_path: PathBuf,
+
+    /// Shop catalog file (TOML, or JSON if it ends in .json). Created with
+    /// the default catalog if missing
+    #[arg(long, default_value = "catalog.toml")]
+    pub catalog_path: PathBuf,
+
+    /// Copy user.json and wallet_list.json into the SQLite database at
+    /// `--sqlite-path`, then exit
+    #[arg(long)]
+    pub import_json: bool,
 }
 
 #[tokio::main]
 async fn main() {
-    setup::setup_logging(Args::parse().log_level);
+    let args = Args::parse();
+    setup::setup_logging(args.log_level);
+
+    if args.import_json {
+        store::import_json(&args.sqlite_path).unwrap_or_log("main::import_json");
+        return;
+    }
 
     //FIXME: change this maybe? i'd like to obscure this setup.
 
-    let framework = setup::setup_framework();
+    let api_key = setup::load_api_key();
+    let framework = setup::setup_framework(&args, api_key.owners);
 
-    let mut client = serenity::ClientBuilder::new(setup::get_api_token(), setup::INTENTS)
-        .framework(framework)
-        .await
-        .unwrap_or_log("main::client");
+    let builder = serenity::ClientBuilder::new(api_key.token, setup::INTENTS).framework(framework);
+    #[cfg(feature = "voice")]
+    let builder = builder.register_songbird();
+    let mut client = builder.await.unwrap_or_log("main::client");
 
     // lovely jubly!
     client.start().await.unwrap_or_log("main::start");
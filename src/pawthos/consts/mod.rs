@@ -3,6 +3,14 @@
 //! All magic numbers and strings live here so they are easy to find and
 //! change in one place. Import what you need with
 //! `use crate::pawthos::consts::*;` or pick individual names.
+//!
+//! Economy tuning values (rewards, prices, fees, odds, `/pay` limits, the
+//! faucet) are only **defaults**: each guild can override them with
+//! `/config`, and call sites read the effective value from
+//! [`crate::pawthos::structs::guild_config::GuildConfig`]. The rest — like
+//! [`FAUCET_GLOBAL_COOLDOWN_SECS`], which protects the bot as a whole, or
+//! the profile limits, which apply to a profile shared by every guild — stay
+//! bot-wide.
 
 use poise::serenity_prelude as serenity;
 use serenity::Color;
@@ -15,11 +23,21 @@ use serenity::GatewayIntents;
 /// - `MESSAGE_CONTENT` — receive the actual text of messages (required for mimic auto-mode).
 /// - `GUILD_MESSAGE_REACTIONS` — receive reaction-add events (required for the
 ///   tab-reaction faucet in [`crate::handlers`]).
+/// - `GUILDS` — keep guilds, channels and roles in the cache (group event
+///   permission checks, `/time convert`).
+/// - `GUILD_MEMBERS` — keep members in the cache, so `/time convert` can
+///   tell who's in a channel. Privileged: enable "Server Members Intent"
+///   in the developer portal.
+/// - `GUILD_VOICE_STATES` — know who's in which voice channel, so `/vox say`
+///   can join the caller's, and let the voice driver follow its own.
 pub const INTENTS: GatewayIntents = {
     let mut r = GatewayIntents::GUILD_MESSAGES;
     r = GatewayIntents::union(r, GatewayIntents::DIRECT_MESSAGES);
     r = GatewayIntents::union(r, GatewayIntents::MESSAGE_CONTENT);
     r = GatewayIntents::union(r, GatewayIntents::GUILD_MESSAGE_REACTIONS);
+    r = GatewayIntents::union(r, GatewayIntents::GUILDS);
+    r = GatewayIntents::union(r, GatewayIntents::GUILD_MEMBERS);
+    r = GatewayIntents::union(r, GatewayIntents::GUILD_VOICE_STATES);
     r
 };
 
@@ -29,11 +47,6 @@ pub const LOGOS_GREEN: Color = Color::from_rgb(102, 204, 102);
 /// The bot's "error" embed accent colour — a bright red.
 pub const LOGOS_RED: Color = Color::from_rgb(255, 0, 0);
 
-/// Discord user ID of the bot owner (Fizzy).
-///
-/// Several admin-only prefix commands gate on this ID to prevent abuse.
-pub const FIZZ_ID: serenity::UserId = serenity::UserId::new(326389428593623040);
-
 /// Number of tabs awarded by the `/daily` command.
 pub const DAILY_REWARD: i64 = 10;
 
@@ -81,6 +94,9 @@ pub const MAX_STREAK_BONUS: i64 = 5;
 /// Number of entries shown on the `/leaderboard` embed.
 pub const LEADERBOARD_SIZE: usize = 10;
 
+/// Number of ledger entries per page of `/wallet history` and `!audit`.
+pub const LEDGER_PAGE_SIZE: usize = 10;
+
 // ---------------------------------------------------------------------------
 // Shop / inventory
 // ---------------------------------------------------------------------------
@@ -94,6 +110,98 @@ pub const MAX_ACTIVE_BADGES: usize = 3;
 /// Maximum character length for a user-supplied custom title.
 pub const MAX_CUSTOM_TITLE_LEN: usize = 32;
 
+// ---------------------------------------------------------------------------
+// Tab transfers (`/pay`)
+// ---------------------------------------------------------------------------
+
+/// Transfer fee as a percentage of the amount sent, rounded up — so every
+/// payment costs at least 1 tab in fees. Paid by the sender on top of the
+/// amount and not received by anyone.
+pub const PAY_FEE_PERCENT: i64 = 5;
+
+/// Maximum tabs a member may send with `/pay` per local day in one guild,
+/// fee excluded.
+pub const PAY_DAILY_CAP: i64 = 500;
+
+/// Payments of at least this many tabs ask for confirmation with a button
+/// before anything is charged.
+pub const PAY_CONFIRM_THRESHOLD: i64 = 100;
+
+/// Seconds the `/pay` confirmation buttons stay live before the payment is
+/// abandoned.
+pub const PAY_CONFIRM_TIMEOUT_SECS: u64 = 30;
+
+/// Maximum character length of the optional `/pay` note.
+pub const MAX_PAY_NOTE_LEN: usize = 100;
+
+// ---------------------------------------------------------------------------
+// Schedule
+// ---------------------------------------------------------------------------
+
+/// Seconds the `/schedule add` confirmation buttons stay live before the
+/// event is abandoned.
+pub const SCHEDULE_CONFIRM_TIMEOUT_SECS: u64 = 60;
+
+/// RSVPs listed by name on a group event announcement; the rest are counted.
+pub const GROUP_EVENT_RSVPS_SHOWN: usize = 50;
+
+/// Most reminders one event can have.
+pub const SCHEDULE_MAX_LEAD_TIMES: usize = 5;
+
+/// Furthest ahead of an event a reminder can go out, in days.
+pub const SCHEDULE_MAX_LEAD_DAYS: i64 = 28;
+
+/// Seconds late a reminder can go out and still count as on time. Anything
+/// later was missed while the bot was offline.
+pub const SCHEDULE_MISSED_GRACE_SECS: i64 = 60;
+
+/// Largest `.ics` file `/schedule import` will read, in bytes.
+pub const SCHEDULE_IMPORT_MAX_BYTES: u32 = 256 * 1024;
+
+/// Skipped entries listed by `/schedule import`; the rest are counted.
+pub const SCHEDULE_IMPORT_SKIPPED_SHOWN: usize = 15;
+
+/// Local hour a reminder snoozed "until tomorrow" comes back at.
+pub const SNOOZE_TOMORROW_HOUR: u32 = 9;
+
+/// Timezones listed by `/time convert`; the rest are counted.
+pub const TIME_CONVERT_ZONES_SHOWN: usize = 15;
+
+/// Members mentioned per timezone, and in the unset list, by `/time
+/// convert`; the rest are counted. Together with
+/// [`TIME_CONVERT_ZONES_SHOWN`] this keeps the reply well inside an embed
+/// description's 4096 characters.
+pub const TIME_CONVERT_MENTIONS_SHOWN: usize = 6;
+
+/// Seconds the bot stays in a voice channel with nothing left to say before
+/// leaving it.
+pub const VOX_IDLE_DISCONNECT_SECS: u64 = 300;
+
+/// DECtalk worker threads, each keeping one engine running.
+pub const DECTALK_WORKERS: usize = 2;
+
+/// `/vox say` requests that can wait for a free DECtalk worker before more
+/// are turned away as busy.
+pub const DECTALK_QUEUE_DEPTH: usize = 8;
+
+/// Seconds a `/vox say` request gets to be synthesised, including time
+/// spent queued.
+pub const DECTALK_JOB_TIMEOUT_SECS: u64 = 30;
+
+/// Words a guild's `/vox dict` can hold.
+pub const VOX_DICT_MAX_ENTRIES: usize = 200;
+
+/// Default for the `vox_max_length` setting: characters of text `/vox say`
+/// speaks, after mentions and emoji are spelled out.
+pub const VOX_MAX_LENGTH: usize = 500;
+
+/// Default for the `vox_inline_commands` setting: whether `/vox say` obeys
+/// DECtalk `[:...]` commands.
+pub const VOX_INLINE_COMMANDS: bool = true;
+
+/// Words listed by `/vox dict list`; the rest are counted.
+pub const VOX_DICT_LIST_SHOWN: usize = 50;
+
 // ---------------------------------------------------------------------------
 // Tab reaction faucet (Phase 5)
 // ---------------------------------------------------------------------------
@@ -0,0 +1,27 @@
+//! Error type for loading the shop catalog in
+//! [`crate::pawthos::structs::catalog_file`].
+
+/// Errors that can occur while loading or reloading the catalog file.
+///
+/// A failed reload leaves the previously loaded catalog in place.
+#[derive(thiserror::Error, Debug)]
+pub enum CatalogError {
+    /// Reading the catalog file or writing the retired-items archive failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// A `.toml` catalog file is not valid TOML or doesn't have the expected
+    /// shape.
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+
+    /// A `.json` catalog file (or the retired-items archive) is not valid
+    /// JSON or doesn't have the expected shape.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The file parsed, but some entries are invalid. Every problem found is
+    /// listed, one per line.
+    #[error("The catalog has {} problem(s):\n{}", .0.len(), .0.join("\n"))]
+    Invalid(Vec<String>),
+}
@@ -0,0 +1,22 @@
+//! Error type for per-guild configuration (`/config`).
+
+/// Errors that can occur when changing a guild's
+/// [`crate::pawthos::structs::guild_config::GuildConfig`].
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    /// The value doesn't parse as the key's type (e.g. text for a number).
+    #[error("`{value}` isn't a valid value for `{key}`.")]
+    Invalid { key: &'static str, value: String },
+
+    /// The value parsed but is outside what the key accepts.
+    #[error("`{key}` must be between {min} and {max}.")]
+    OutOfRange {
+        key: &'static str,
+        min: String,
+        max: String,
+    },
+
+    /// The change would set every lootbox rarity weight to zero.
+    #[error("At least one lootbox chance must be above zero.")]
+    NoLootboxOdds,
+}
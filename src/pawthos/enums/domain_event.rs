@@ -0,0 +1,238 @@
+//! Things that happen in the bot, as published on the event bus.
+//!
+//! Commands and handlers describe *what happened* with a [`DomainEvent`] and
+//! hand it to [`crate::pawthos::structs::data::Data::publish`]. Everything
+//! that reacts to activity — achievements today, anything else tomorrow —
+//! subscribes to these instead of being called by hand from each command.
+//!
+//! The tab ledger is the exception. Its entries are written by
+//! [`crate::pawthos::structs::transaction::Transaction::commit`], not by a
+//! subscriber. An entry has to land under the same write lock and in the
+//! same persistence batch as the balance change it explains. It must also
+//! never exist for a movement that rolled back. Events are published only
+//! after the commit and the lock are gone, so a subscriber could record a
+//! transfer late, or lose it if the process stopped in between.
+
+use crate::pawthos::enums::ledger_reason::LedgerReason;
+use crate::pawthos::structs::achievement_rule::{Stat, Touched};
+use poise::serenity_prelude::{ChannelId, GuildId, UserId};
+use std::fmt;
+
+/// One domain event. Guild events carry the channel they happened in, which
+/// is where any achievement they unlock is announced.
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    /// Tabs credited outside a purchase flow: `/daily`, `!give_tabs`.
+    TabsEarned {
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+        amount: i64,
+        reason: LedgerReason,
+    },
+    /// A shop purchase or per-use charge. `item_id` is the catalog ID, or a
+    /// pseudo-ID such as `lootbox`, `banner` or `rolecolor`.
+    ItemPurchased {
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+        item_id: String,
+        cost: i64,
+    },
+    /// A `/shop gift`, after the recipient received the item.
+    GiftSent {
+        guild_id: GuildId,
+        sender_id: UserId,
+        recipient_id: UserId,
+        channel_id: ChannelId,
+        item_id: String,
+    },
+    /// A completed `/pay`.
+    PaymentSent {
+        guild_id: GuildId,
+        sender_id: UserId,
+        recipient_id: UserId,
+        channel_id: ChannelId,
+        amount: i64,
+    },
+    /// A non-bot message in a guild channel.
+    MessageSent {
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+    },
+    /// A tab-reaction faucet bounty was claimed.
+    FaucetClaimed {
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+        amount: i64,
+    },
+    /// A mimic was saved. `guild_id` is `None` when the command ran in DMs.
+    MimicSaved {
+        guild_id: Option<GuildId>,
+        user_id: UserId,
+        channel_id: ChannelId,
+    },
+    /// An event was added to a user's schedule. `guild_id` is `None` when the
+    /// command ran in DMs.
+    EventScheduled {
+        guild_id: Option<GuildId>,
+        user_id: UserId,
+        channel_id: ChannelId,
+    },
+    /// A schedule reminder was delivered. Reminders are DMs, so there is no
+    /// guild or channel.
+    ReminderFired { user_id: UserId, event_name: String },
+    /// A reminder was marked done from its DM.
+    ReminderCompleted { user_id: UserId, event_name: String },
+}
+
+impl DomainEvent {
+    /// The channel the event happened in, if any.
+    pub fn channel_id(&self) -> Option<ChannelId> {
+        match self {
+            DomainEvent::TabsEarned { channel_id, .. }
+            | DomainEvent::ItemPurchased { channel_id, .. }
+            | DomainEvent::GiftSent { channel_id, .. }
+            | DomainEvent::PaymentSent { channel_id, .. }
+            | DomainEvent::MessageSent { channel_id, .. }
+            | DomainEvent::FaucetClaimed { channel_id, .. }
+            | DomainEvent::MimicSaved { channel_id, .. }
+            | DomainEvent::EventScheduled { channel_id, .. } => Some(*channel_id),
+            DomainEvent::ReminderFired { .. } | DomainEvent::ReminderCompleted { .. } => None,
+        }
+    }
+
+    /// Every guild member whose stats this event may have changed, and which
+    /// stats. Achievement evaluation only looks at rules that depend on
+    /// these.
+    pub fn touches(&self) -> Vec<(GuildId, UserId, Touched)> {
+        use Stat::*;
+        match self {
+            DomainEvent::TabsEarned {
+                guild_id, user_id, reason, ..
+            } => {
+                let stats: &[Stat] = match reason {
+                    LedgerReason::Daily => &[Tabs, CurrentStreak],
+                    _ => &[Tabs],
+                };
+                vec![(*guild_id, *user_id, Touched::stats(stats))]
+            }
+            DomainEvent::ItemPurchased {
+                guild_id, user_id, ..
+            } => vec![(
+                *guild_id,
+                *user_id,
+                Touched::owning(&[
+                    Tabs,
+                    TabsSpent,
+                    TitlesOwned,
+                    ColorwaysOwned,
+                    BadgesOwned,
+                    LootboxesOpened,
+                ]),
+            )],
+            DomainEvent::GiftSent {
+                guild_id,
+                sender_id,
+                recipient_id,
+                ..
+            } => vec![
+                (*guild_id, *sender_id, Touched::stats(&[Tabs, TabsSpent, GiftsSent])),
+                (
+                    *guild_id,
+                    *recipient_id,
+                    Touched::owning(&[GiftsReceived, TitlesOwned, ColorwaysOwned]),
+                ),
+            ],
+            DomainEvent::PaymentSent {
+                guild_id,
+                sender_id,
+                recipient_id,
+                ..
+            } => vec![
+                (*guild_id, *sender_id, Touched::stats(&[Tabs, TabsSpent, TransfersSent])),
+                (*guild_id, *recipient_id, Touched::stats(&[Tabs])),
+            ],
+            DomainEvent::MessageSent {
+                guild_id, user_id, ..
+            } => vec![(*guild_id, *user_id, Touched::stats(&[MessagesSent]))],
+            DomainEvent::FaucetClaimed {
+                guild_id, user_id, ..
+            } => vec![(*guild_id, *user_id, Touched::stats(&[Tabs, FaucetClaims]))],
+            DomainEvent::MimicSaved {
+                guild_id: Some(guild_id),
+                user_id,
+                ..
+            } => vec![(*guild_id, *user_id, Touched::stats(&[Mimics]))],
+            DomainEvent::EventScheduled {
+                guild_id: Some(guild_id),
+                user_id,
+                ..
+            } => vec![(*guild_id, *user_id, Touched::stats(&[ScheduleEvents]))],
+            DomainEvent::MimicSaved { guild_id: None, .. }
+            | DomainEvent::EventScheduled { guild_id: None, .. }
+            | DomainEvent::ReminderFired { .. }
+            | DomainEvent::ReminderCompleted { .. } => Vec::new(),
+        }
+    }
+}
+
+/// One-line summary for the activity log, e.g.
+/// `guild 123: 456 bought title_x for 10 tabs`.
+impl fmt::Display for DomainEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainEvent::TabsEarned {
+                guild_id,
+                user_id,
+                amount,
+                reason,
+                ..
+            } => write!(f, "guild {guild_id}: {user_id} earned {amount} tabs ({reason:?})"),
+            DomainEvent::ItemPurchased {
+                guild_id,
+                user_id,
+                item_id,
+                cost,
+                ..
+            } => write!(f, "guild {guild_id}: {user_id} bought {item_id} for {cost} tabs"),
+            DomainEvent::GiftSent {
+                guild_id,
+                sender_id,
+                recipient_id,
+                item_id,
+                ..
+            } => write!(f, "guild {guild_id}: {sender_id} gifted {item_id} to {recipient_id}"),
+            DomainEvent::PaymentSent {
+                guild_id,
+                sender_id,
+                recipient_id,
+                amount,
+                ..
+            } => write!(f, "guild {guild_id}: {sender_id} paid {amount} tabs to {recipient_id}"),
+            DomainEvent::MessageSent {
+                guild_id, user_id, ..
+            } => write!(f, "guild {guild_id}: {user_id} sent a message"),
+            DomainEvent::FaucetClaimed {
+                guild_id,
+                user_id,
+                amount,
+                ..
+            } => write!(f, "guild {guild_id}: {user_id} claimed a {amount}-tab faucet drop"),
+            DomainEvent::MimicSaved { user_id, .. } => write!(f, "{user_id} saved a mimic"),
+            DomainEvent::EventScheduled { user_id, .. } => {
+                write!(f, "{user_id} scheduled an event")
+            }
+            DomainEvent::ReminderFired {
+                user_id,
+                event_name,
+            } => write!(f, "reminder \"{event_name}\" fired for {user_id}"),
+            DomainEvent::ReminderCompleted {
+                user_id,
+                event_name,
+            } => write!(f, "{user_id} marked \"{event_name}\" done"),
+        }
+    }
+}
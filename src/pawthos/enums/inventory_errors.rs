@@ -13,6 +13,11 @@ pub enum InventoryError {
     #[error("Unknown item id: `{0}`.")]
     UnknownItem(String),
 
+    /// The item was removed from the catalog file. Owners keep it, but it
+    /// can no longer be bought or gifted.
+    #[error("**{0}** has been retired from the shop.")]
+    Retired(String),
+
     /// The user already owns this item.
     #[error("You already own **{0}**.")]
     AlreadyOwned(String),
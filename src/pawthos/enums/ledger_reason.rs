@@ -0,0 +1,37 @@
+//! Why a tab movement happened, as recorded in the ledger.
+
+use serde::{Deserialize, Serialize};
+
+/// The cause of one [`crate::pawthos::structs::ledger_entry::LedgerEntry`].
+///
+/// Doubles as a slash/prefix choice so `!audit` can filter by it.
+/// Serialised by variant name, so renaming a variant breaks existing ledgers
+/// — add new variants instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+pub enum LedgerReason {
+    /// `/daily` reward, streak bonus included.
+    #[name = "Daily"]
+    Daily,
+    /// A claimed tab-reaction faucet bounty.
+    #[name = "Faucet"]
+    Faucet,
+    /// A shop purchase or per-use cosmetic charge.
+    #[name = "Purchase"]
+    Purchase,
+    /// The cost of a lootbox pull.
+    #[name = "Lootbox"]
+    Lootbox,
+    /// Tabs refunded for a duplicate lootbox pull.
+    #[name = "Salvage"]
+    Salvage,
+    /// The sender's charge for a `/shop gift`.
+    #[name = "Gift"]
+    Gift,
+    /// A `/pay` between members: the sender's amount plus fee, or the
+    /// recipient's amount.
+    #[name = "Transfer"]
+    Transfer,
+    /// A manual adjustment by a guild admin (`!give_tabs`).
+    #[name = "Admin"]
+    Admin,
+}
@@ -8,21 +8,39 @@
 //!
 //! | Module | Purpose |
 //! |---|---|
+//! | [`catalog_errors`] | Errors from loading the shop catalog file |
 //! | [`color_errors`] | Errors from hex-colour parsing and image generation |
+//! | [`config_errors`] | Errors from changing a guild's configuration |
+//! | [`domain_event`] | Events published on the internal event bus |
 //! | [`embed_type`] | Controls the accent colour of Discord embeds |
 //! | [`inventory_errors`] | Errors from the shop / inventory sub-system |
+//! | [`ledger_reason`] | Why a tab movement happened, for the ledger |
 //! | [`mimic_errors`] | Errors from the mimic sub-system |
 //! | [`pawthos_errors`] | Top-level error enum; wraps all others |
+//! | [`permission_errors`] | Errors from permission checks and staff role changes |
+//! | [`permission_level`] | Member / moderator / admin / owner levels |
 //! | [`persistent_data`] | Messages sent over the persistence channel |
+//! | [`reminder_command`] | Messages sent to the reminder scheduler |
 //! | [`schedule_errors`] | Errors from the schedule sub-system |
+//! | [`store_errors`] | Errors from the storage backends |
+//! | [`vox_errors`] | Errors from the vox sub-system |
 //! | [`wallet_errors`] | Errors from the wallet/tab sub-system |
 
+pub mod catalog_errors;
 pub mod color_errors;
+pub mod config_errors;
+pub mod domain_event;
 pub mod embed_type;
 pub mod inventory_errors;
+pub mod ledger_reason;
 pub mod mimic_errors;
 pub mod pawthos_errors;
+pub mod permission_errors;
+pub mod permission_level;
 pub mod persistent_data;
 pub mod profile_errors;
+pub mod reminder_command;
 pub mod schedule_errors;
+pub mod store_errors;
+pub mod vox_errors;
 pub mod wallet_errors;
@@ -11,11 +11,16 @@
 //! - `From<image::ImageError>` → wraps via `ColorError::ImageError`
 
 use crate::dectalk::DectalkError;
+use crate::pawthos::enums::catalog_errors::CatalogError;
 use crate::pawthos::enums::color_errors::ColorError;
+use crate::pawthos::enums::config_errors::ConfigError;
 use crate::pawthos::enums::inventory_errors::InventoryError;
 use crate::pawthos::enums::mimic_errors::MimicError;
+use crate::pawthos::enums::permission_errors::PermissionError;
 use crate::pawthos::enums::profile_errors::ProfileError;
 use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::enums::store_errors::StoreError;
+use crate::pawthos::enums::vox_errors::VoxError;
 use crate::pawthos::enums::wallet_errors::WalletError;
 
 /// The single error type returned by all bot operations.
@@ -57,6 +62,10 @@ pub enum PawthosError {
     #[error("ScheduleError: {0}")]
     Schedule(#[from] ScheduleError),
 
+    /// An error from the vox sub-system.
+    #[error("VoxError: {0}")]
+    Vox(#[from] VoxError),
+
     /// An error from the wallet sub-system.
     #[error("WalletError: {0}")]
     Wallet(#[from] WalletError),
@@ -72,6 +81,23 @@ pub enum PawthosError {
     /// An error from the shop / inventory sub-system.
     #[error("InventoryError: {0}")]
     Inventory(#[from] InventoryError),
+
+    /// An error from the storage backend.
+    #[error("StoreError: {0}")]
+    Store(#[from] StoreError),
+
+    /// An error from loading the shop catalog.
+    #[error("CatalogError: {0}")]
+    Catalog(#[from] CatalogError),
+
+    /// An error from changing a guild's configuration.
+    #[error("ConfigError: {0}")]
+    Config(#[from] ConfigError),
+
+    /// The caller isn't allowed to run the command, or a staff role change
+    /// was rejected.
+    #[error("PermissionError: {0}")]
+    Permission(#[from] PermissionError),
 }
 
 /// Convert a `chrono::ParseError` directly into a `PawthosError` by routing
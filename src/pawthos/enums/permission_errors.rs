@@ -0,0 +1,25 @@
+//! Error type for the permission sub-system.
+
+use crate::pawthos::enums::permission_level::PermissionLevel;
+use poise::serenity_prelude::RoleId;
+
+/// Errors from permission checks and from managing a guild's staff roles.
+#[derive(thiserror::Error, Debug)]
+pub enum PermissionError {
+    /// The caller is below the level the command requires. Returned by the
+    /// checks in [`crate::checks`] and rendered by
+    /// [`crate::handlers::error_handler`].
+    #[error("This command needs {required} permission.")]
+    Denied { required: PermissionLevel },
+
+    /// `/permissions grant` for a role that already has that level.
+    #[error("<@&{role}> is already a {level} role.")]
+    AlreadyGranted {
+        role: RoleId,
+        level: PermissionLevel,
+    },
+
+    /// `/permissions revoke` for a role that isn't a staff role.
+    #[error("<@&{0}> isn't an admin or moderator role.")]
+    NotGranted(RoleId),
+}
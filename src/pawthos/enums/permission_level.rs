@@ -0,0 +1,52 @@
+//! Who may run which commands.
+
+use std::fmt;
+
+/// How much a member is trusted with, lowest first, so levels compare with
+/// `>=`.
+///
+/// Resolved per command invocation by [`crate::checks::permission_level`]:
+/// bot owners come from `s.json`, staff from the guild's
+/// [`crate::pawthos::structs::guild_permissions::GuildPermissions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PermissionLevel {
+    /// Anyone.
+    Member,
+    /// Holds one of the guild's moderator roles.
+    Moderator,
+    /// Holds one of the guild's admin roles, has Discord's Administrator or
+    /// Manage Server permission, or owns the guild.
+    Admin,
+    /// Listed in `owners` in `s.json`, or owns the bot's Discord application.
+    Owner,
+}
+
+impl fmt::Display for PermissionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PermissionLevel::Member => "member",
+            PermissionLevel::Moderator => "moderator",
+            PermissionLevel::Admin => "admin",
+            PermissionLevel::Owner => "bot owner",
+        })
+    }
+}
+
+/// The levels a guild can hand out through a role, as a slash choice for
+/// `/permissions grant`. Owners are never granted per guild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum StaffLevel {
+    #[name = "Admin"]
+    Admin,
+    #[name = "Moderator"]
+    Moderator,
+}
+
+impl From<StaffLevel> for PermissionLevel {
+    fn from(value: StaffLevel) -> Self {
+        match value {
+            StaffLevel::Admin => PermissionLevel::Admin,
+            StaffLevel::Moderator => PermissionLevel::Moderator,
+        }
+    }
+}
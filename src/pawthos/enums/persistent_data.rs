@@ -4,7 +4,12 @@
 //! `tokio::sync::mpsc` channel to the persistence task in [`crate::framework`].
 //! This keeps blocking file I/O off the async executor threads.
 
-use crate::pawthos::structs::user_db::UserDB;
+use crate::pawthos::structs::{
+    guild_config::GuildConfig, guild_dictionary::GuildDictionary,
+    guild_permissions::GuildPermissions, guild_user::GuildUser, ledger_entry::LedgerEntry,
+    user::User,
+};
+use poise::serenity_prelude::{GuildId, UserId};
 
 /// A message sent from a command handler to the persistence task.
 ///
@@ -13,18 +18,23 @@ use crate::pawthos::structs::user_db::UserDB;
 /// file I/O.
 #[derive(Debug)]
 pub enum PersistentData {
-    /// A full snapshot of the user database to be serialised and written to
-    /// `user.json`. Sent automatically after every write through
-    /// [`crate::pawthos::structs::data::Data::with_db_user_write`].
-    UserDB(UserDB),
+    /// Every record changed by one write, handed to the active
+    /// [`crate::store::UserStore`] to be saved together. Sent automatically
+    /// after every write through
+    /// [`crate::pawthos::structs::data::Data::with_db_user_write`] (one row)
+    /// and [`crate::pawthos::structs::data::Data::transact`] (one row per
+    /// record the transaction touched).
+    Rows(Vec<DbRow>),
 
     /// A request to check (and mark) whether a user has already claimed their
-    /// daily reward today.
+    /// daily reward today in a given guild.
     ///
     /// The `sender` half of a one-shot channel is included so the persistence
     /// task can send the [`UserDailyClaimed`] result back to the calling
     /// command handler.
     DailyCheck {
+        /// The raw guild ID (as `u64`) the claim is being made in.
+        guild_id: u64,
         /// The raw user ID (as `u64`) of the user attempting to claim.
         user_id: u64,
         /// One-shot sender; the persistence task sends the claim status back
@@ -33,6 +43,67 @@ pub enum PersistentData {
     },
 }
 
+/// A single record of the user database, as handed to a
+/// [`crate::store::UserStore`].
+///
+/// Rows are cloned out of the in-memory [`crate::pawthos::structs::user_db::UserDB`]
+/// while the write lock is held, so the store always sees a consistent record.
+#[derive(Debug, Clone)]
+pub enum DbRow {
+    /// A user's guild-independent record.
+    User {
+        user_id: UserId,
+        user: Box<User>,
+    },
+
+    /// A user's record inside one guild's economy.
+    GuildUser {
+        guild_id: GuildId,
+        user_id: UserId,
+        user: Box<GuildUser>,
+    },
+
+    /// A new line for one guild's tab ledger. Unlike the other variants this
+    /// is appended, never upserted.
+    Ledger {
+        guild_id: GuildId,
+        entry: Box<LedgerEntry>,
+    },
+
+    /// One guild's `/config` settings.
+    GuildConfig {
+        guild_id: GuildId,
+        config: Box<GuildConfig>,
+    },
+
+    /// One guild's `/permissions` staff roles.
+    GuildPermissions {
+        guild_id: GuildId,
+        permissions: Box<GuildPermissions>,
+    },
+
+    /// One guild's `/vox dict` pronunciations.
+    GuildDictionary {
+        guild_id: GuildId,
+        dictionary: Box<GuildDictionary>,
+    },
+}
+
+/// Identifies one record of the user database — the key half of a [`DbRow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowKey {
+    /// A user's guild-independent record.
+    User(UserId),
+    /// A user's record inside one guild's economy.
+    GuildUser(GuildId, UserId),
+    /// One guild's settings.
+    GuildConfig(GuildId),
+    /// One guild's staff roles.
+    GuildPermissions(GuildId),
+    /// One guild's pronunciation dictionary.
+    GuildDictionary(GuildId),
+}
+
 /// Whether a user has already claimed their daily reward for the current day.
 ///
 /// Returned by the persistence task in response to a [`PersistentData::DailyCheck`]
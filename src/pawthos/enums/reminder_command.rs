@@ -0,0 +1,20 @@
+//! Messages sent to the reminder scheduler.
+
+use crate::pawthos::structs::schedule_event::{EventId, ScheduleEvent};
+use poise::serenity_prelude::UserId;
+
+/// A change to the reminder scheduler's queue, sent over
+/// [`crate::pawthos::structs::data::Data::schedule_events_channel`].
+///
+/// Timers are keyed by owner and [`EventId`], so scheduling an event that's
+/// already queued moves its timer instead of adding a second one.
+#[derive(Debug)]
+pub enum ReminderCommand {
+    /// Queue the next unsent reminder of this event, replacing any timer
+    /// the event already has. An event with nothing left to send just loses
+    /// its timer.
+    Schedule(UserId, Box<ScheduleEvent>),
+
+    /// Drop the event's timer, if it has one.
+    Cancel(UserId, EventId),
+}
@@ -1,5 +1,9 @@
 //! Error type for the schedule sub-system.
 
+use crate::pawthos::consts::{
+    SCHEDULE_IMPORT_MAX_BYTES, SCHEDULE_MAX_LEAD_DAYS, SCHEDULE_MAX_LEAD_TIMES,
+};
+
 /// Errors that can occur when working with a user's schedule.
 #[derive(thiserror::Error, Debug)]
 pub enum ScheduleError {
@@ -29,4 +33,101 @@ pub enum ScheduleError {
     /// so it cannot be unambiguously localised to the user's timezone.
     #[error("That time is ambiguous or invalid (e.g. falls in a DST gap). Try a different time.")]
     AmbiguousOrInvalidTime,
+
+    /// `weekdays` for a weekly repeat didn't parse.
+    #[error("Couldn't read `{0}` as weekdays. Use short names like `mon,wed,fri`.")]
+    InvalidWeekdays(String),
+
+    /// A repeat option was given without `repeat`, or doesn't go with the
+    /// chosen kind of repeat.
+    #[error("`{0}` only applies to a repeating event of the matching kind.")]
+    UnusedRepeatOption(&'static str),
+
+    /// A repeating event's `until` date is before its first occurrence.
+    #[error("The repeat ends before the first occurrence.")]
+    EndsBeforeStart,
+
+    /// `/schedule add` couldn't make sense of (part of) the time it was given.
+    #[error(
+        "Couldn't understand `{0}`. Try `in 20 minutes`, `2h30m`, `tomorrow 9am`, `next friday 17:30` or `2026-05-01 14:00`."
+    )]
+    UnrecognisedTime(String),
+
+    /// Looks like a time of day but isn't one, e.g. `25:00` or `13pm`.
+    #[error("`{0}` isn't a valid time of day.")]
+    InvalidClockTime(String),
+
+    /// A day was given without a time of day, e.g. just `tomorrow`.
+    #[error("What time? Add one, e.g. `tomorrow 9am`.")]
+    MissingTime,
+
+    /// The time resolved to now or earlier.
+    #[error("That time has already passed.")]
+    TimeInPast,
+
+    /// A relative time too far in the future to represent.
+    #[error("That's too far in the future.")]
+    TimeOutOfRange,
+
+    /// `/schedule group edit|cancel` was pointed at a private reminder.
+    #[error("That's a private reminder, not a group event. Use `/schedule delete` instead.")]
+    NotAGroupEvent,
+
+    /// Join pressed by someone already on the RSVP list.
+    #[error("You're already going!")]
+    AlreadyGoing,
+
+    /// Leave pressed by someone not on the RSVP list.
+    #[error("You weren't going.")]
+    NotGoing,
+
+    /// The creator can't send messages in the channel they picked.
+    #[error("You can't post in <#{0}>.")]
+    CannotPostIn(poise::serenity_prelude::ChannelId),
+
+    /// A role that can't normally be mentioned, picked by a non-moderator.
+    #[error("Only moderators can have a reminder mention <@&{0}>.")]
+    RoleNotMentionable(poise::serenity_prelude::RoleId),
+
+    /// A lead time that isn't a duration, or is too long.
+    #[error(
+        "Couldn't read `{}` as a lead time. Use durations up to {} days, e.g. `1d, 1h, 10m, now`.",
+        .0,
+        SCHEDULE_MAX_LEAD_DAYS
+    )]
+    InvalidLeadTime(String),
+
+    /// More lead times than one event can have.
+    #[error(
+        "That's too many reminders for one event (at most {}).",
+        SCHEDULE_MAX_LEAD_TIMES
+    )]
+    TooManyLeadTimes,
+
+    /// `/schedule export` with nothing to export.
+    #[error("Your schedule is empty — there's nothing to export.")]
+    NothingToExport,
+
+    /// `/schedule import` was given something other than an iCalendar file.
+    #[error("That doesn't look like an iCalendar (`.ics`) file.")]
+    NotACalendar,
+
+    /// `/schedule import` was given a file over the size limit.
+    #[error(
+        "That calendar is too big to import (at most {} KiB).",
+        SCHEDULE_IMPORT_MAX_BYTES / 1024
+    )]
+    CalendarTooLarge,
+
+    /// `/time convert`'s `members` didn't mention anyone.
+    #[error("Mention the members to show it for, e.g. `@alice @bob`.")]
+    NoMembersMentioned,
+
+    /// `/time now` for someone who hasn't picked a timezone.
+    #[error("<@{0}> hasn't set a timezone with `/schedule set_tz`.")]
+    NoTimezone(poise::serenity_prelude::UserId),
+
+    /// Snooze or done pressed on a reminder already marked done.
+    #[error("You've already marked that done.")]
+    AlreadyDone,
 }
@@ -0,0 +1,17 @@
+//! Error type for the storage backends in [`crate::store`].
+
+/// Errors that can occur while loading or persisting the user database.
+#[derive(thiserror::Error, Debug)]
+pub enum StoreError {
+    /// Reading or writing a JSON file failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// A record could not be (de)serialised.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The SQLite database returned an error.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+}
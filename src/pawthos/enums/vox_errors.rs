@@ -0,0 +1,53 @@
+//! Error type for the vox sub-system.
+
+use poise::serenity_prelude::ChannelId;
+
+/// Errors that can occur when working with a user's saved voice or speaking
+/// in a voice channel.
+#[derive(thiserror::Error, Debug)]
+pub enum VoxError {
+    /// The calling user has no entry in the vox database.
+    ///
+    /// This is a normal state for users who have never saved a voice.
+    #[error("No Vox User found in User Database!")]
+    NoUserFound,
+
+    /// `/vox say live` was used by someone who isn't in a voice channel.
+    #[error("Join a voice channel first, then I can speak in it.")]
+    NotInVoice,
+
+    /// The bot is already speaking in another voice channel of this server.
+    #[error("I'm already speaking in <#{0}>; join that channel or wait until I'm done.")]
+    BusyElsewhere(ChannelId),
+
+    /// `/vox skip` or `/vox stop` with nothing playing in this server.
+    #[error("I'm not speaking in a voice channel here.")]
+    NotPlaying,
+
+    /// This build of the bot has no voice support (see the `voice` feature).
+    #[error("Speaking in voice channels isn't available on this bot.")]
+    VoiceUnsupported,
+
+    /// `/vox dict add` for a new word with the guild's dictionary full.
+    #[error("This server's dictionary is full ({0} words); remove some first.")]
+    DictionaryFull(usize),
+
+    /// `/vox dict remove` for a word that isn't in the guild's dictionary.
+    #[error("`{0}` isn't in this server's dictionary.")]
+    NotInDictionary(String),
+
+    /// This build of the bot can't encode Opus (see the `opus` feature).
+    #[cfg(not(feature = "opus"))]
+    #[error("Voice clips aren't available on this bot; leave out `voice_clip` for a WAV.")]
+    OpusUnsupported,
+
+    /// Encoding speech as Opus failed.
+    #[cfg(feature = "opus")]
+    #[error("Opus encoding failed: {0}")]
+    Opus(String),
+
+    /// Joining the voice channel or playing into it failed.
+    #[cfg(feature = "voice")]
+    #[error("Voice connection failed: {0}")]
+    Voice(String),
+}
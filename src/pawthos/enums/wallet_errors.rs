@@ -22,6 +22,18 @@ pub enum WalletError {
     /// midnight local time. The display formats this as `Xh Ym`.
     DailyOnCooldown { remaining_secs: i64 },
 
+    /// The user tried to `/pay` themselves.
+    PayToSelf,
+
+    /// The user tried to `/pay` a bot account.
+    PayToBot,
+
+    /// The payment would take the user past the daily outbound `/pay` cap.
+    ///
+    /// `cap` is the full daily limit; `remaining` is what the user can still
+    /// send today.
+    PayCapExceeded { cap: i64, remaining: i64 },
+
     /// An internal one-shot channel for the daily-check request/response
     /// cycle failed. This should never happen in normal operation.
     RecvError,
@@ -46,6 +58,12 @@ impl std::fmt::Display for WalletError {
                     "You already claimed your daily <:tab:1459045305084547123>. Try again in **{hrs}h {mins}m**."
                 )
             }
+            WalletError::PayToSelf => write!(f, "You can't pay yourself!"),
+            WalletError::PayToBot => write!(f, "Bots don't need tabs!"),
+            WalletError::PayCapExceeded { cap, remaining } => write!(
+                f,
+                "That's over the daily limit of **{cap}** <:tab:1459045305084547123> sent with `/pay`. You can send **{remaining}** more today."
+            ),
             WalletError::RecvError => write!(f, "RecvError!! tell fizz to check logs!!!!"),
         }
     }
@@ -0,0 +1,404 @@
+//! The achievement rule language.
+//!
+//! An achievement unlocks when its [`Rule`] holds for a member. Rules are
+//! written in the catalog file (see [`super::catalog_file`]) and are made of
+//! three kinds of condition:
+//!
+//! | Rule | Catalog file | Holds when |
+//! |---|---|---|
+//! | [`Rule::Stat`] | `{ stat = "messages_sent", at_least = 100 }` | the [`Stat`] is at least the target |
+//! | [`Rule::OwnsAll`] | `{ owns_all = ["title_x", "box_y"] }` | the member owns every listed item |
+//! | [`Rule::All`] / [`Rule::Any`] | `{ all = [ … ] }` / `{ any = [ … ] }` | every / at least one nested rule holds |
+//!
+//! Rules are evaluated against a [`RuleSubject`]: the member's whole
+//! [`User`] record plus their [`GuildUser`] in the guild being checked, so a
+//! rule can mix economy stats with mimics or schedule events.
+
+use crate::pawthos::structs::{guild_user::GuildUser, user::User};
+
+/// A number a [`Rule::Stat`] condition can test.
+///
+/// Written in `snake_case` in the catalog file (`stat = "gifts_sent"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stat {
+    /// Current tab balance in this guild.
+    Tabs,
+    /// Consecutive days `/daily` has been claimed.
+    CurrentStreak,
+    MessagesSent,
+    GiftsSent,
+    GiftsReceived,
+    /// Completed `/pay` transfers sent.
+    TransfersSent,
+    LootboxesOpened,
+    FaucetClaims,
+    /// Tabs spent in the shop, lifetime.
+    TabsSpent,
+    TitlesOwned,
+    ColorwaysOwned,
+    /// Lootbox and achievement badges owned.
+    BadgesOwned,
+    AchievementsUnlocked,
+    /// Saved mimics (any guild).
+    Mimics,
+    /// Scheduled events (any guild).
+    ScheduleEvents,
+}
+
+impl Stat {
+    /// Every stat, in the order the catalog file docs list them.
+    pub const ALL: &[Stat] = &[
+        Stat::Tabs,
+        Stat::CurrentStreak,
+        Stat::MessagesSent,
+        Stat::GiftsSent,
+        Stat::GiftsReceived,
+        Stat::TransfersSent,
+        Stat::LootboxesOpened,
+        Stat::FaucetClaims,
+        Stat::TabsSpent,
+        Stat::TitlesOwned,
+        Stat::ColorwaysOwned,
+        Stat::BadgesOwned,
+        Stat::AchievementsUnlocked,
+        Stat::Mimics,
+        Stat::ScheduleEvents,
+    ];
+
+    /// Parse the name used in the catalog file.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|s| s.as_str() == name)
+    }
+
+    /// The name used in the catalog file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Stat::Tabs => "tabs",
+            Stat::CurrentStreak => "current_streak",
+            Stat::MessagesSent => "messages_sent",
+            Stat::GiftsSent => "gifts_sent",
+            Stat::GiftsReceived => "gifts_received",
+            Stat::TransfersSent => "transfers_sent",
+            Stat::LootboxesOpened => "lootboxes_opened",
+            Stat::FaucetClaims => "faucet_claims",
+            Stat::TabsSpent => "tabs_spent",
+            Stat::TitlesOwned => "titles_owned",
+            Stat::ColorwaysOwned => "colorways_owned",
+            Stat::BadgesOwned => "badges_owned",
+            Stat::AchievementsUnlocked => "achievements_unlocked",
+            Stat::Mimics => "mimics",
+            Stat::ScheduleEvents => "schedule_events",
+        }
+    }
+
+    /// What the number counts, for progress lines ("37/100 messages").
+    pub fn unit(self) -> &'static str {
+        match self {
+            Stat::Tabs | Stat::TabsSpent => "tabs",
+            Stat::CurrentStreak => "days",
+            Stat::MessagesSent => "messages",
+            Stat::GiftsSent | Stat::GiftsReceived => "gifts",
+            Stat::TransfersSent => "payments",
+            Stat::LootboxesOpened => "lootboxes",
+            Stat::FaucetClaims => "drops",
+            Stat::TitlesOwned => "titles",
+            Stat::ColorwaysOwned => "colorways",
+            Stat::BadgesOwned => "badges",
+            Stat::AchievementsUnlocked => "achievements",
+            Stat::Mimics => "mimics",
+            Stat::ScheduleEvents => "events",
+        }
+    }
+
+    /// This stat's current value for `subject`.
+    pub fn value(self, subject: &RuleSubject) -> i64 {
+        let wallet = &subject.member.wallet;
+        let inv = &subject.member.inventory;
+        let count = |n: usize| n as i64;
+        match self {
+            Stat::Tabs => wallet.tabs,
+            Stat::CurrentStreak => wallet.current_streak.into(),
+            Stat::MessagesSent => inv.messages_sent.try_into().unwrap_or(i64::MAX),
+            Stat::GiftsSent => inv.gifts_sent.into(),
+            Stat::GiftsReceived => inv.gifts_received.into(),
+            Stat::TransfersSent => inv.transfers_sent.into(),
+            Stat::LootboxesOpened => inv.lootboxes_opened.into(),
+            Stat::FaucetClaims => inv.faucet_claims.into(),
+            Stat::TabsSpent => inv.tabs_spent_lifetime,
+            Stat::TitlesOwned => count(inv.owned_titles.len()),
+            Stat::ColorwaysOwned => count(inv.owned_colorways.len()),
+            Stat::BadgesOwned => count(inv.owned_badges.len()),
+            Stat::AchievementsUnlocked => count(inv.unlocked_achievements.len()),
+            Stat::Mimics => count(subject.user.mimic.mimics.len()),
+            Stat::ScheduleEvents => count(subject.user.schedule.events.len()),
+        }
+    }
+}
+
+/// An unlock condition. See the module docs for the catalog file syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// `stat >= at_least`.
+    Stat { stat: Stat, at_least: i64 },
+    /// The member owns every listed title, colorway, badge or unlock.
+    OwnsAll(Vec<String>),
+    /// Every nested rule holds.
+    All(Vec<Rule>),
+    /// At least one nested rule holds.
+    Any(Vec<Rule>),
+}
+
+/// Who a [`Rule`] is evaluated against: one member in one guild.
+pub struct RuleSubject<'a> {
+    /// Guild-independent state (mimics, schedule, profile).
+    pub user: &'a User,
+    /// This guild's wallet and inventory.
+    pub member: &'a GuildUser,
+}
+
+impl RuleSubject<'_> {
+    /// Whether the member owns the item `id`, whatever its category.
+    fn owns(&self, id: &str) -> bool {
+        let inv = &self.member.inventory;
+        match id {
+            "unlock_custom_title" => inv.unlocked_custom_title,
+            _ => [&inv.owned_titles, &inv.owned_colorways, &inv.owned_badges]
+                .into_iter()
+                .any(|owned| owned.iter().any(|o| o == id)),
+        }
+    }
+}
+
+/// What changed for one member, so only rules that could have flipped are
+/// re-evaluated. Built from
+/// [`crate::pawthos::enums::domain_event::DomainEvent::touches`].
+#[derive(Debug, Clone, Copy)]
+pub struct Touched {
+    /// Stats whose value may have changed.
+    pub stats: &'static [Stat],
+    /// Whether the member may now own something new.
+    pub ownership: bool,
+}
+
+impl Touched {
+    /// Only `stats` changed.
+    pub fn stats(stats: &'static [Stat]) -> Self {
+        Self {
+            stats,
+            ownership: false,
+        }
+    }
+
+    /// `stats` changed and the member may own something new.
+    pub fn owning(stats: &'static [Stat]) -> Self {
+        Self {
+            stats,
+            ownership: true,
+        }
+    }
+}
+
+/// How close a member is to satisfying a [`Rule`], e.g. 37 of 100 messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Clamped to `0..=target`.
+    pub current: i64,
+    pub target: i64,
+    pub unit: &'static str,
+}
+
+impl Progress {
+    fn new(current: i64, target: i64, unit: &'static str) -> Self {
+        let target = target.max(1);
+        Self {
+            current: current.clamp(0, target),
+            target,
+            unit,
+        }
+    }
+
+    /// Completed fraction in `0.0..=1.0`.
+    fn ratio(&self) -> f64 {
+        self.current as f64 / self.target as f64
+    }
+}
+
+impl std::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} {}", self.current, self.target, self.unit)
+    }
+}
+
+impl Rule {
+    /// Whether the rule holds for `subject`.
+    pub fn is_met(&self, subject: &RuleSubject) -> bool {
+        match self {
+            Rule::Stat { stat, at_least } => stat.value(subject) >= *at_least,
+            Rule::OwnsAll(ids) => ids.iter().all(|id| subject.owns(id)),
+            Rule::All(rules) => rules.iter().all(|r| r.is_met(subject)),
+            Rule::Any(rules) => rules.iter().any(|r| r.is_met(subject)),
+        }
+    }
+
+    /// Whether the outcome of this rule can have changed given `touched`.
+    pub fn is_touched_by(&self, touched: &Touched) -> bool {
+        match self {
+            Rule::Stat { stat, .. } => touched.stats.contains(stat),
+            Rule::OwnsAll(_) => touched.ownership,
+            Rule::All(rules) | Rule::Any(rules) => rules.iter().any(|r| r.is_touched_by(touched)),
+        }
+    }
+
+    /// How far `subject` is towards satisfying the rule.
+    ///
+    /// `All` of several rules counts how many are met; `Any` reports the
+    /// nested rule closest to completion. A single nested rule reports its
+    /// own progress either way.
+    pub fn progress(&self, subject: &RuleSubject) -> Progress {
+        match self {
+            Rule::Stat { stat, at_least } => {
+                Progress::new(stat.value(subject), *at_least, stat.unit())
+            }
+            Rule::OwnsAll(ids) => {
+                let owned = ids.iter().filter(|id| subject.owns(id)).count();
+                Progress::new(owned as i64, ids.len() as i64, "items")
+            }
+            Rule::All(rules) | Rule::Any(rules) if rules.len() == 1 => rules[0].progress(subject),
+            Rule::All(rules) => {
+                let met = rules.iter().filter(|r| r.is_met(subject)).count();
+                Progress::new(met as i64, rules.len() as i64, "goals")
+            }
+            Rule::Any(rules) => rules
+                .iter()
+                .map(|r| r.progress(subject))
+                .max_by(|a, b| a.ratio().total_cmp(&b.ratio()))
+                .unwrap_or(Progress::new(0, 1, "goals")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A member with 37 messages, 120 tabs and a 3-day streak who owns
+    /// `title_a` and `box_b`.
+    fn member() -> GuildUser {
+        let mut member = GuildUser::default();
+        member.wallet.tabs = 120;
+        member.wallet.current_streak = 3;
+        member.inventory.messages_sent = 37;
+        member.inventory.owned_titles.push("title_a".into());
+        member.inventory.owned_badges.push("box_b".into());
+        member
+    }
+
+    fn stat(stat: Stat, at_least: i64) -> Rule {
+        Rule::Stat { stat, at_least }
+    }
+
+    fn owns(ids: &[&str]) -> Rule {
+        Rule::OwnsAll(ids.iter().map(|id| id.to_string()).collect())
+    }
+
+    /// Evaluate `rule` against [`member`].
+    fn check(rule: &Rule) -> (bool, String) {
+        let (user, member) = (User::default(), member());
+        let subject = RuleSubject {
+            user: &user,
+            member: &member,
+        };
+        (rule.is_met(&subject), rule.progress(&subject).to_string())
+    }
+
+    #[test]
+    fn stat_names_round_trip() {
+        for &s in Stat::ALL {
+            assert_eq!(Stat::parse(s.as_str()), Some(s));
+        }
+        for name in ["", "Tabs", "messages", "messages_sent ", "tab"] {
+            assert_eq!(Stat::parse(name), None, "{name:?}");
+        }
+    }
+
+    #[test]
+    fn stat_progress_is_clamped_to_the_target() {
+        assert_eq!(
+            check(&stat(Stat::MessagesSent, 100)),
+            (false, "37/100 messages".into())
+        );
+        assert_eq!(
+            check(&stat(Stat::MessagesSent, 37)),
+            (true, "37/37 messages".into())
+        );
+        assert_eq!(check(&stat(Stat::Tabs, 50)), (true, "50/50 tabs".into()));
+        assert_eq!(
+            check(&stat(Stat::GiftsSent, 5)),
+            (false, "0/5 gifts".into())
+        );
+    }
+
+    #[test]
+    fn owns_all_counts_owned_items() {
+        assert_eq!(
+            check(&owns(&["title_a", "box_b"])),
+            (true, "2/2 items".into())
+        );
+        assert_eq!(
+            check(&owns(&["title_a", "colorway_c", "unlock_custom_title"])),
+            (false, "1/3 items".into())
+        );
+    }
+
+    #[test]
+    fn all_counts_goals_met() {
+        let rule = Rule::All(vec![
+            stat(Stat::Tabs, 100),
+            stat(Stat::MessagesSent, 100),
+            owns(&["title_a"]),
+        ]);
+        assert_eq!(check(&rule), (false, "2/3 goals".into()));
+
+        let met = Rule::All(vec![stat(Stat::Tabs, 100), owns(&["title_a"])]);
+        assert_eq!(check(&met), (true, "2/2 goals".into()));
+    }
+
+    #[test]
+    fn any_reports_the_closest_rule() {
+        let rule = Rule::Any(vec![
+            stat(Stat::MessagesSent, 100),
+            stat(Stat::CurrentStreak, 5),
+            owns(&["colorway_c"]),
+        ]);
+        assert_eq!(check(&rule), (false, "3/5 days".into()));
+
+        let met = Rule::Any(vec![stat(Stat::MessagesSent, 100), stat(Stat::Tabs, 100)]);
+        assert_eq!(check(&met), (true, "100/100 tabs".into()));
+    }
+
+    #[test]
+    fn a_single_nested_rule_reports_its_own_progress() {
+        let inner = stat(Stat::MessagesSent, 100);
+        for rule in [Rule::All(vec![inner.clone()]), Rule::Any(vec![inner])] {
+            assert_eq!(check(&rule), (false, "37/100 messages".into()));
+        }
+    }
+
+    #[test]
+    fn touched_only_by_what_the_rule_reads() {
+        let messages = Touched::stats(&[Stat::MessagesSent]);
+        let bought = Touched::owning(&[Stat::TabsSpent]);
+
+        assert!(stat(Stat::MessagesSent, 1).is_touched_by(&messages));
+        assert!(!stat(Stat::Tabs, 1).is_touched_by(&messages));
+        assert!(!owns(&["title_a"]).is_touched_by(&messages));
+        assert!(owns(&["title_a"]).is_touched_by(&bought));
+
+        let nested = Rule::All(vec![
+            stat(Stat::Tabs, 1),
+            Rule::Any(vec![stat(Stat::MessagesSent, 1)]),
+        ]);
+        assert!(nested.is_touched_by(&messages));
+        assert!(!nested.is_touched_by(&bought));
+    }
+}
@@ -0,0 +1,907 @@
+//! The shop catalog file and how it becomes a [`Catalog`].
+//!
+//! The file is TOML by default (`catalog.toml`, shipped in the repository
+//! root); a path ending in `.json` is read as JSON with the same shape. If the
+//! file doesn't exist, the default catalog compiled into the binary is written
+//! there first, so a fresh deployment starts with the usual shop.
+//!
+//! Achievements live in the same file, each with a rule in the syntax
+//! described in [`super::achievement_rule`].
+//!
+//! [`load`] validates every entry, carries forward items that disappeared
+//! from the file as retired, and installs the result with
+//! [`shop_catalog::install`]. Retired items are also written to
+//! `catalog_retired.json`, so they survive a restart even though the
+//! operator's file no longer mentions them, and the active ones to
+//! `catalog_installed.json`, so an item deleted from the file while the bot
+//! was offline is still retired at the next startup.
+
+use crate::pawthos::enums::catalog_errors::CatalogError;
+use crate::pawthos::structs::achievement_rule::{Rule, Stat};
+use crate::pawthos::structs::shop_catalog::{
+    self, Achievement, BadgeDef, Catalog, ColorwayDef, Rarity, ShopItem, TitleDef,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Bot-managed archive of retired items, in the working directory.
+const RETIRED_ARCHIVE_PATH: &str = "catalog_retired.json";
+
+/// Bot-managed snapshot of the active items last installed, in the working
+/// directory. At startup the catalog in use is empty, so this is what the
+/// new file is compared against.
+const INSTALLED_SNAPSHOT_PATH: &str = "catalog_installed.json";
+
+/// Written to the catalog path when no file exists there yet.
+const DEFAULT_CATALOG: &str = include_str!("../../../catalog.toml");
+
+/// Unlock IDs the code knows how to apply. An unlock in the file with any
+/// other ID would take tabs and do nothing, so it is rejected.
+pub const KNOWN_UNLOCKS: &[&str] = &["unlock_custom_title"];
+
+// ---------------------------------------------------------------------------
+// File format
+// ---------------------------------------------------------------------------
+
+/// The catalog file as written by hand. One array of tables per category.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    titles: Vec<Entry>,
+    #[serde(default)]
+    colorways: Vec<Entry>,
+    #[serde(default)]
+    badges: Vec<Entry>,
+    #[serde(default)]
+    unlocks: Vec<Entry>,
+    #[serde(default)]
+    achievements: Vec<AchievementEntry>,
+}
+
+/// One item in the catalog file. `hex` is required for colorways and
+/// `emoji` for badges; neither is allowed anywhere else.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    cost: i64,
+    #[serde(default = "default_rarity")]
+    rarity: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    emoji: Option<String>,
+}
+
+/// One achievement in the catalog file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AchievementEntry {
+    id: String,
+    name: String,
+    emoji: String,
+    #[serde(default)]
+    description: String,
+    rule: RuleEntry,
+}
+
+/// A [`Rule`] as written in the file. Told apart by which key is present;
+/// stat names stay strings until [`Validator::rule`] checks them, so a typo
+/// gets a proper message instead of a shape error.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum RuleEntry {
+    Stat { stat: String, at_least: i64 },
+    OwnsAll { owns_all: Vec<String> },
+    All { all: Vec<RuleEntry> },
+    Any { any: Vec<RuleEntry> },
+}
+
+impl RuleEntry {
+    fn from_rule(rule: &Rule) -> Self {
+        match rule {
+            Rule::Stat { stat, at_least } => RuleEntry::Stat {
+                stat: stat.as_str().to_string(),
+                at_least: *at_least,
+            },
+            Rule::OwnsAll(ids) => RuleEntry::OwnsAll {
+                owns_all: ids.clone(),
+            },
+            Rule::All(rules) => RuleEntry::All {
+                all: rules.iter().map(RuleEntry::from_rule).collect(),
+            },
+            Rule::Any(rules) => RuleEntry::Any {
+                any: rules.iter().map(RuleEntry::from_rule).collect(),
+            },
+        }
+    }
+}
+
+/// Item ID prefixes an `owns_all` rule may name.
+const OWNABLE_PREFIXES: &[&str] = &["title_", "colorway_", "box_", "ach_", "unlock_"];
+
+fn default_rarity() -> String {
+    Rarity::Common.as_str().to_string()
+}
+
+impl Entry {
+    fn from_item(item: &ShopItem) -> Self {
+        Self {
+            id: item.id.clone(),
+            name: item.name.clone(),
+            description: item.description.clone(),
+            cost: item.cost,
+            rarity: item.rarity.as_str().to_string(),
+            hex: None,
+            emoji: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+/// What a successful [`load`] changed, for the log and the owner's reply.
+#[derive(Debug, Default)]
+pub struct LoadSummary {
+    /// Active items of each category now in the catalog.
+    pub titles: usize,
+    pub colorways: usize,
+    pub badges: usize,
+    pub unlocks: usize,
+    pub achievements: usize,
+    /// IDs that were active before and are now retired.
+    pub retired: Vec<String>,
+    /// IDs that were retired before and are back in the file.
+    pub restored: Vec<String>,
+}
+
+/// Read, validate and install the catalog at `path`.
+///
+/// On any error the catalog in use is left untouched. Called once at startup
+/// and again by `!reload_catalog`.
+pub fn load(path: &Path) -> Result<LoadSummary, CatalogError> {
+    let (catalog, summary) = read_catalog(path, Path::new(""), &shop_catalog::current())?;
+    shop_catalog::install(catalog);
+    Ok(summary)
+}
+
+/// Everything [`load`] does short of installing the result. `installed` is
+/// the catalog in use, and the bot-managed files live in `state_dir`.
+fn read_catalog(
+    path: &Path,
+    state_dir: &Path,
+    installed: &Catalog,
+) -> Result<(Catalog, LoadSummary), CatalogError> {
+    let is_json = path.extension().is_some_and(|e| e == "json");
+    if !path.exists() {
+        log::warn!("{} NOT found, writing the default catalog..", path.display());
+        if is_json {
+            let default: CatalogFile = toml::from_str(DEFAULT_CATALOG)?;
+            std::fs::write(path, serde_json::to_string_pretty(&default)?)?;
+        } else {
+            std::fs::write(path, DEFAULT_CATALOG)?;
+        }
+    }
+
+    let text = std::fs::read_to_string(path)?;
+    let file: CatalogFile = if is_json {
+        serde_json::from_str(&text)?
+    } else {
+        toml::from_str(&text)?
+    };
+    let mut catalog = validate(file, false)?;
+
+    let previous = previous_catalog(installed, state_dir);
+    let mut summary = carry_retired(&mut catalog, &previous);
+    save_state(&catalog, state_dir)?;
+
+    summary.titles = catalog.active_titles().count();
+    summary.colorways = catalog.active_colorways().count();
+    summary.badges = catalog.active_badges().count();
+    summary.unlocks = catalog.active_unlocks().count();
+    summary.achievements = catalog.active_achievements().count();
+    log::info!(
+        "Catalog loaded from {}: {} title(s), {} colorway(s), {} badge(s), {} unlock(s), {} achievement(s); {} newly retired, {} restored.",
+        path.display(),
+        summary.titles,
+        summary.colorways,
+        summary.badges,
+        summary.unlocks,
+        summary.achievements,
+        summary.retired.len(),
+        summary.restored.len(),
+    );
+    Ok((catalog, summary))
+}
+
+/// Everything known before this load: the catalog in use (empty at
+/// startup), then the snapshot of the last one installed, then the retired
+/// archive.
+fn previous_catalog(installed: &Catalog, state_dir: &Path) -> Catalog {
+    let mut previous = installed.clone();
+    for (name, retired) in [
+        (INSTALLED_SNAPSHOT_PATH, false),
+        (RETIRED_ARCHIVE_PATH, true),
+    ] {
+        match read_state_file(&state_dir.join(name), retired) {
+            Ok(saved) => absorb(&mut previous, saved),
+            Err(e) => log::error!("{name} could not be read: {e}. Ignoring it."),
+        }
+    }
+    previous
+}
+
+/// Append the entries of `saved` whose IDs `previous` doesn't have yet.
+fn absorb(previous: &mut Catalog, saved: Catalog) {
+    let known: HashSet<String> = previous.ids().map(str::to_string).collect();
+    let fresh = |item: &ShopItem| !known.contains(&item.id);
+    previous
+        .titles
+        .extend(saved.titles.into_iter().filter(|t| fresh(&t.item)));
+    previous
+        .colorways
+        .extend(saved.colorways.into_iter().filter(|c| fresh(&c.item)));
+    previous
+        .lootbox_pool
+        .extend(saved.lootbox_pool.into_iter().filter(|b| fresh(&b.item)));
+    previous
+        .unlocks
+        .extend(saved.unlocks.into_iter().filter(|u| fresh(u)));
+    previous.achievements.extend(
+        saved
+            .achievements
+            .into_iter()
+            .filter(|a| !known.contains(&a.id)),
+    );
+}
+
+/// Append every item of `previous` whose ID is missing from `catalog` to
+/// `catalog` as retired, and report what changed.
+fn carry_retired(catalog: &mut Catalog, previous: &Catalog) -> LoadSummary {
+    let mut summary = LoadSummary::default();
+    let present: HashSet<String> = catalog.ids().map(str::to_string).collect();
+
+    summary.restored = previous
+        .items()
+        .filter(|i| i.retired && present.contains(&i.id))
+        .map(|i| i.id.clone())
+        .chain(
+            previous
+                .achievements
+                .iter()
+                .filter(|a| a.retired && present.contains(&a.id))
+                .map(|a| a.id.clone()),
+        )
+        .collect();
+
+    let mut retire = |item: &ShopItem| -> Option<ShopItem> {
+        if present.contains(&item.id) {
+            return None;
+        }
+        if !item.retired {
+            summary.retired.push(item.id.clone());
+        }
+        Some(ShopItem {
+            retired: true,
+            ..item.clone()
+        })
+    };
+    for t in &previous.titles {
+        if let Some(item) = retire(&t.item) {
+            catalog.titles.push(TitleDef { item });
+        }
+    }
+    for c in &previous.colorways {
+        if let Some(item) = retire(&c.item) {
+            catalog.colorways.push(ColorwayDef { item, hex: c.hex });
+        }
+    }
+    for b in &previous.lootbox_pool {
+        if let Some(item) = retire(&b.item) {
+            catalog.lootbox_pool.push(BadgeDef {
+                item,
+                emoji: b.emoji.clone(),
+            });
+        }
+    }
+    for u in &previous.unlocks {
+        if let Some(item) = retire(u) {
+            catalog.unlocks.push(item);
+        }
+    }
+    for a in &previous.achievements {
+        if present.contains(&a.id) {
+            continue;
+        }
+        if !a.retired {
+            summary.retired.push(a.id.clone());
+        }
+        catalog.achievements.push(Achievement {
+            retired: true,
+            ..a.clone()
+        });
+    }
+    summary
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Turn the parsed file into a [`Catalog`], collecting every problem rather
+/// than stopping at the first.
+///
+/// Checks: IDs are non-empty, carry their category's prefix and are unique
+/// across the whole catalog; names are non-empty; costs are not negative;
+/// rarities are known; colorways have a valid 6-digit hex colour; badges
+/// have an emoji; unlocks are ones the code can apply; achievement rules
+/// name known stats and well-formed item IDs, and nested lists aren't
+/// empty.
+fn validate(file: CatalogFile, retired: bool) -> Result<Catalog, CatalogError> {
+    let mut v = Validator {
+        problems: Vec::new(),
+        seen: HashSet::new(),
+        retired,
+    };
+    let mut catalog = Catalog::default();
+
+    for (i, e) in file.titles.iter().enumerate() {
+        let at = format!("titles[{i}]");
+        v.no_extras(&at, e, false, false);
+        if let Some(item) = v.item(&at, "title_", e) {
+            catalog.titles.push(TitleDef { item });
+        }
+    }
+    for (i, e) in file.colorways.iter().enumerate() {
+        let at = format!("colorways[{i}]");
+        v.no_extras(&at, e, true, false);
+        let hex = match e.hex.as_deref() {
+            None => {
+                v.problem(&at, e, "needs a `hex` colour".into());
+                None
+            }
+            Some(raw) => {
+                let hex = parse_hex(raw);
+                if hex.is_none() {
+                    v.problem(&at, e, format!("hex \"{raw}\" is not a 6-digit hex colour"));
+                }
+                hex
+            }
+        };
+        if let (Some(item), Some(hex)) = (v.item(&at, "colorway_", e), hex) {
+            catalog.colorways.push(ColorwayDef { item, hex });
+        }
+    }
+    for (i, e) in file.badges.iter().enumerate() {
+        let at = format!("badges[{i}]");
+        v.no_extras(&at, e, false, true);
+        let emoji = e.emoji.clone().filter(|s| !s.trim().is_empty());
+        if emoji.is_none() {
+            v.problem(&at, e, "needs an `emoji`".into());
+        }
+        if let (Some(item), Some(emoji)) = (v.item(&at, "box_", e), emoji) {
+            catalog.lootbox_pool.push(BadgeDef { item, emoji });
+        }
+    }
+    for (i, e) in file.unlocks.iter().enumerate() {
+        let at = format!("unlocks[{i}]");
+        v.no_extras(&at, e, false, false);
+        if !KNOWN_UNLOCKS.contains(&e.id.as_str()) {
+            v.problem(
+                &at,
+                e,
+                format!("is not a known unlock (expected one of {})", KNOWN_UNLOCKS.join(", ")),
+            );
+        }
+        if let Some(item) = v.item(&at, "unlock_", e) {
+            catalog.unlocks.push(item);
+        }
+    }
+
+    for (i, a) in file.achievements.iter().enumerate() {
+        let at = format!("achievements[{i}]");
+        if let Some(achievement) = v.achievement(&at, a) {
+            catalog.achievements.push(achievement);
+        }
+    }
+
+    if v.problems.is_empty() {
+        Ok(catalog)
+    } else {
+        Err(CatalogError::Invalid(v.problems))
+    }
+}
+
+/// Accumulates problems and seen IDs across one [`validate`] call.
+struct Validator {
+    problems: Vec<String>,
+    seen: HashSet<String>,
+    retired: bool,
+}
+
+impl Validator {
+    fn problem(&mut self, at: &str, entry: &Entry, what: String) {
+        self.problems.push(format!("{at} `{}`: {what}", entry.id));
+    }
+
+    /// Check the fields shared by every category and build the header.
+    /// Returns `None` if any of them is invalid.
+    fn item(&mut self, at: &str, prefix: &str, e: &Entry) -> Option<ShopItem> {
+        let before = self.problems.len();
+        if !e.id.starts_with(prefix) || e.id.len() == prefix.len() {
+            self.problem(at, e, format!("id must start with `{prefix}`"));
+        }
+        if !self.seen.insert(e.id.clone()) {
+            self.problem(at, e, "id is used more than once".into());
+        }
+        if e.name.trim().is_empty() {
+            self.problem(at, e, "name is empty".into());
+        }
+        if e.cost < 0 {
+            self.problem(at, e, format!("cost {} is negative", e.cost));
+        }
+        let rarity = Rarity::parse(&e.rarity);
+        if rarity.is_none() {
+            self.problem(
+                at,
+                e,
+                format!(
+                    "unknown rarity \"{}\" (expected common, uncommon, rare or legendary)",
+                    e.rarity
+                ),
+            );
+        }
+
+        (self.problems.len() == before).then(|| ShopItem {
+            id: e.id.clone(),
+            name: e.name.clone(),
+            description: e.description.clone(),
+            cost: e.cost,
+            rarity: rarity.unwrap_or(Rarity::Common),
+            retired: self.retired,
+        })
+    }
+
+    /// Check an achievement and build it. Returns `None` if anything about
+    /// it is invalid.
+    fn achievement(&mut self, at: &str, a: &AchievementEntry) -> Option<Achievement> {
+        let before = self.problems.len();
+        let mut problem = |what: String| format!("{at} `{}`: {what}", a.id);
+        if !a.id.starts_with("ach_") || a.id.len() == "ach_".len() {
+            self.problems.push(problem("id must start with `ach_`".into()));
+        }
+        if !self.seen.insert(a.id.clone()) {
+            self.problems.push(problem("id is used more than once".into()));
+        }
+        if a.name.trim().is_empty() {
+            self.problems.push(problem("name is empty".into()));
+        }
+        if a.emoji.trim().is_empty() {
+            self.problems.push(problem("emoji is empty".into()));
+        }
+        let mut rule_problems = Vec::new();
+        let rule = Self::rule(&a.rule, "rule", &mut rule_problems);
+        self.problems
+            .extend(rule_problems.into_iter().map(&mut problem));
+
+        (self.problems.len() == before).then(|| Achievement {
+            id: a.id.clone(),
+            name: a.name.clone(),
+            emoji: a.emoji.clone(),
+            description: a.description.clone(),
+            rule: rule.expect("no problems means the rule parsed"),
+            retired: self.retired,
+        })
+    }
+
+    /// Turn a [`RuleEntry`] into a [`Rule`], pushing a message for every
+    /// problem found. `at` is the path within the rule, e.g. `rule.all[1]`.
+    fn rule(entry: &RuleEntry, at: &str, problems: &mut Vec<String>) -> Option<Rule> {
+        match entry {
+            RuleEntry::Stat { stat, at_least } => match Stat::parse(stat) {
+                Some(stat) => Some(Rule::Stat {
+                    stat,
+                    at_least: *at_least,
+                }),
+                None => {
+                    let known: Vec<&str> = Stat::ALL.iter().map(|s| s.as_str()).collect();
+                    problems.push(format!(
+                        "{at}: unknown stat \"{stat}\" (expected one of {})",
+                        known.join(", ")
+                    ));
+                    None
+                }
+            },
+            RuleEntry::OwnsAll { owns_all } => {
+                if owns_all.is_empty() {
+                    problems.push(format!("{at}: `owns_all` lists no items"));
+                }
+                for id in owns_all {
+                    if !OWNABLE_PREFIXES.iter().any(|p| id.starts_with(p)) {
+                        problems.push(format!(
+                            "{at}: `{id}` is not an item id (expected one of {})",
+                            OWNABLE_PREFIXES.join(", ")
+                        ));
+                    }
+                }
+                Some(Rule::OwnsAll(owns_all.clone()))
+            }
+            RuleEntry::All { all: rules } | RuleEntry::Any { any: rules } => {
+                let key = if matches!(entry, RuleEntry::All { .. }) { "all" } else { "any" };
+                if rules.is_empty() {
+                    problems.push(format!("{at}: `{key}` lists no rules"));
+                }
+                let nested: Vec<Option<Rule>> = rules
+                    .iter()
+                    .enumerate()
+                    .map(|(i, r)| Self::rule(r, &format!("{at}.{key}[{i}]"), problems))
+                    .collect();
+                let nested: Option<Vec<Rule>> = nested.into_iter().collect();
+                let nested = nested?;
+                Some(if key == "all" { Rule::All(nested) } else { Rule::Any(nested) })
+            }
+        }
+    }
+
+    /// Reject `hex` / `emoji` on categories that don't use them, so a badge
+    /// pasted into the wrong section doesn't silently lose its emoji.
+    fn no_extras(&mut self, at: &str, e: &Entry, hex_ok: bool, emoji_ok: bool) {
+        if !hex_ok && e.hex.is_some() {
+            self.problem(at, e, "`hex` is only valid on colorways".into());
+        }
+        if !emoji_ok && e.emoji.is_some() {
+            self.problem(at, e, "`emoji` is only valid on badges".into());
+        }
+    }
+}
+
+/// Parse `RRGGBB`, `#RRGGBB` or `0xRRGGBB`.
+fn parse_hex(raw: &str) -> Option<u32> {
+    let digits = raw
+        .strip_prefix('#')
+        .or_else(|| raw.strip_prefix("0x"))
+        .unwrap_or(raw);
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
+}
+
+// ---------------------------------------------------------------------------
+// Bot-managed files
+// ---------------------------------------------------------------------------
+
+/// Write the bot-managed files to `state_dir`: the active items of
+/// `catalog` as the installed snapshot, and the retired ones as the archive.
+fn save_state(catalog: &Catalog, state_dir: &Path) -> Result<(), CatalogError> {
+    write_state_file(&state_dir.join(INSTALLED_SNAPSHOT_PATH), catalog, false)?;
+    write_state_file(&state_dir.join(RETIRED_ARCHIVE_PATH), catalog, true)
+}
+
+/// Write the items of `catalog` that are (or aren't) `retired` to `path`
+/// atomically (write to `.tmp`, then rename).
+fn write_state_file(path: &Path, catalog: &Catalog, retired: bool) -> Result<(), CatalogError> {
+    let file = CatalogFile {
+        titles: catalog
+            .titles
+            .iter()
+            .filter(|t| t.item.retired == retired)
+            .map(|t| Entry::from_item(&t.item))
+            .collect(),
+        colorways: catalog
+            .colorways
+            .iter()
+            .filter(|c| c.item.retired == retired)
+            .map(|c| Entry {
+                hex: Some(format!("{:06X}", c.hex)),
+                ..Entry::from_item(&c.item)
+            })
+            .collect(),
+        badges: catalog
+            .lootbox_pool
+            .iter()
+            .filter(|b| b.item.retired == retired)
+            .map(|b| Entry {
+                emoji: Some(b.emoji.clone()),
+                ..Entry::from_item(&b.item)
+            })
+            .collect(),
+        unlocks: catalog
+            .unlocks
+            .iter()
+            .filter(|u| u.retired == retired)
+            .map(Entry::from_item)
+            .collect(),
+        achievements: catalog
+            .achievements
+            .iter()
+            .filter(|a| a.retired == retired)
+            .map(|a| AchievementEntry {
+                id: a.id.clone(),
+                name: a.name.clone(),
+                emoji: a.emoji.clone(),
+                description: a.description.clone(),
+                rule: RuleEntry::from_rule(&a.rule),
+            })
+            .collect(),
+    };
+    let json = serde_json::to_string_pretty(&file)?;
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Read a file written by [`write_state_file`] as a [`Catalog`] whose items
+/// are all `retired` or all not. A missing file is an empty catalog.
+fn read_state_file(path: &Path, retired: bool) -> Result<Catalog, CatalogError> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => validate(serde_json::from_str(&text)?, retired),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Catalog::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(toml: &str) -> CatalogFile {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn problems(toml: &str) -> Vec<String> {
+        match validate(file(toml), false) {
+            Err(CatalogError::Invalid(problems)) => problems,
+            other => panic!("expected problems, got {other:?}"),
+        }
+    }
+
+    const SMALL: &str = r##"
+        [[titles]]
+        id = "title_a"
+        name = "A"
+        cost = 10
+
+        [[titles]]
+        id = "title_b"
+        name = "B"
+        rarity = "rare"
+
+        [[colorways]]
+        id = "colorway_red"
+        name = "Red"
+        hex = "#FF0000"
+
+        [[badges]]
+        id = "box_star"
+        name = "Star"
+        emoji = "⭐"
+        rarity = "legendary"
+
+        [[achievements]]
+        id = "ach_rich"
+        name = "Rich"
+        emoji = "💰"
+        rule = { stat = "tabs", at_least = 100 }
+    "##;
+
+    /// [`SMALL`] with `title_b` and `ach_rich` deleted.
+    const WITHOUT_B_AND_RICH: &str = r#"
+        [[titles]]
+        id = "title_a"
+        name = "A"
+
+        [[colorways]]
+        id = "colorway_red"
+        name = "Red"
+        hex = "FF0000"
+
+        [[badges]]
+        id = "box_star"
+        name = "Star"
+        emoji = "⭐"
+    "#;
+
+    /// A directory of its own for one test's files, removed afterwards.
+    struct Scratch(std::path::PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("logos-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn default_catalog_is_valid() {
+        let catalog = validate(file(DEFAULT_CATALOG), false).unwrap();
+        assert!(catalog.active_titles().count() > 0);
+        assert!(catalog.active_achievements().count() > 0);
+    }
+
+    #[test]
+    fn validates_a_small_catalog() {
+        let catalog = validate(file(SMALL), false).unwrap();
+        assert_eq!(catalog.titles.len(), 2);
+        assert_eq!(catalog.titles[1].item.rarity, Rarity::Rare);
+        assert_eq!(catalog.colorways[0].hex, 0xFF0000);
+        assert_eq!(catalog.lootbox_pool[0].emoji, "⭐");
+        assert_eq!(catalog.achievements[0].id, "ach_rich");
+        assert!(catalog.items().all(|i| !i.retired));
+    }
+
+    #[test]
+    fn ids_are_unique_across_categories() {
+        let problems = problems(
+            r#"
+            [[titles]]
+            id = "title_a"
+            name = "A"
+
+            [[titles]]
+            id = "title_a"
+            name = "Again"
+
+            [[achievements]]
+            id = "ach_a"
+            name = "First"
+            emoji = "1️⃣"
+            rule = { stat = "tabs", at_least = 1 }
+
+            [[achievements]]
+            id = "ach_a"
+            name = "Second"
+            emoji = "2️⃣"
+            rule = { stat = "tabs", at_least = 2 }
+            "#,
+        );
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].starts_with("titles[1] `title_a`"));
+        assert!(problems[1].starts_with("achievements[1] `ach_a`"));
+        for problem in &problems {
+            assert!(problem.ends_with("id is used more than once"));
+        }
+    }
+
+    #[test]
+    fn rarity_must_be_known() {
+        let problems = problems(
+            r#"
+            [[titles]]
+            id = "title_a"
+            name = "A"
+            rarity = "mythic"
+            "#,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("unknown rarity \"mythic\""));
+    }
+
+    #[test]
+    fn colorways_need_a_valid_hex() {
+        let problems = problems(
+            r##"
+            [[colorways]]
+            id = "colorway_short"
+            name = "Short"
+            hex = "#FFF"
+
+            [[colorways]]
+            id = "colorway_bad"
+            name = "Bad"
+            hex = "GG0000"
+
+            [[colorways]]
+            id = "colorway_none"
+            name = "None"
+            "##,
+        );
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].contains("\"#FFF\" is not a 6-digit hex colour"));
+        assert!(problems[1].contains("\"GG0000\" is not a 6-digit hex colour"));
+        assert!(problems[2].contains("needs a `hex` colour"));
+
+        assert_eq!(parse_hex("A1b2C3"), Some(0xA1B2C3));
+        assert_eq!(parse_hex("#a1b2c3"), Some(0xA1B2C3));
+        assert_eq!(parse_hex("0xA1B2C3"), Some(0xA1B2C3));
+        assert_eq!(parse_hex("#A1B2C3D"), None);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let problems = problems(
+            r#"
+            [[titles]]
+            id = "a"
+            name = " "
+            cost = -1
+
+            [[titles]]
+            id = "title_b"
+            name = "B"
+            emoji = "❌"
+
+            [[achievements]]
+            id = "ach_a"
+            name = "A"
+            emoji = "🅰️"
+            rule = { all = [{ stat = "tab", at_least = 1 }, { owns_all = [] }] }
+            "#,
+        );
+        assert_eq!(problems.len(), 6, "{problems:?}");
+    }
+
+    #[test]
+    fn removed_items_are_retired_then_restored() {
+        let previous = validate(file(SMALL), false).unwrap();
+        let mut current = validate(file(WITHOUT_B_AND_RICH), false).unwrap();
+        let summary = carry_retired(&mut current, &previous);
+        assert_eq!(summary.retired, ["title_b", "ach_rich"]);
+        assert!(summary.restored.is_empty());
+        let title_b = current.titles.iter().find(|t| t.item.id == "title_b");
+        assert!(title_b.is_some_and(|t| t.item.retired && t.item.rarity == Rarity::Rare));
+        assert!(current.achievements.iter().all(|a| a.retired));
+        assert_eq!(current.active_titles().count(), 1);
+
+        // Still missing: carried again, but not reported as newly retired.
+        let mut again = validate(file(WITHOUT_B_AND_RICH), false).unwrap();
+        let summary = carry_retired(&mut again, &current);
+        assert!(summary.retired.is_empty());
+        assert!(again.titles.iter().any(|t| t.item.id == "title_b"));
+
+        // Back in the file: active again, and not carried as a duplicate.
+        let mut restored = validate(file(SMALL), false).unwrap();
+        let summary = carry_retired(&mut restored, &again);
+        assert_eq!(summary.restored, ["title_b", "ach_rich"]);
+        assert!(summary.retired.is_empty());
+        assert_eq!(restored.titles.len(), 2);
+        assert_eq!(restored.active_titles().count(), 2);
+        assert_eq!(restored.active_achievements().count(), 1);
+    }
+
+    #[test]
+    fn items_removed_while_offline_are_retired_at_startup() {
+        let dir = Scratch::new("catalog-restart");
+        let path = dir.0.join("catalog.toml");
+        std::fs::write(&path, SMALL).unwrap();
+        let (first, _) = read_catalog(&path, &dir.0, &Catalog::default()).unwrap();
+        assert_eq!(first.active_titles().count(), 2);
+
+        // Edited while the bot was down: nothing is installed at startup.
+        std::fs::write(&path, WITHOUT_B_AND_RICH).unwrap();
+        let (second, summary) = read_catalog(&path, &dir.0, &Catalog::default()).unwrap();
+        assert_eq!(summary.retired, ["title_b", "ach_rich"]);
+        let title_b = second.titles.iter().find(|t| t.item.id == "title_b");
+        assert!(title_b.is_some_and(|t| t.item.retired && t.item.name == "B"));
+        assert!(second.achievements.iter().all(|a| a.retired));
+
+        // Another restart: still carried, from the archive this time.
+        let (third, summary) = read_catalog(&path, &dir.0, &Catalog::default()).unwrap();
+        assert!(summary.retired.is_empty());
+        let title_b = third.titles.iter().find(|t| t.item.id == "title_b");
+        assert!(title_b.is_some_and(|t| t.item.retired));
+
+        // Back in the file.
+        std::fs::write(&path, SMALL).unwrap();
+        let (fourth, summary) = read_catalog(&path, &dir.0, &Catalog::default()).unwrap();
+        assert_eq!(summary.restored, ["title_b", "ach_rich"]);
+        assert_eq!(fourth.active_titles().count(), 2);
+    }
+}
@@ -9,34 +9,66 @@
 //! methods (`with_*_user_read` / `with_*_user_write`). These methods:
 //!
 //! 1. Acquire the appropriate `RwLock` guard.
-//! 2. Look up (or create) the user's record.
+//! 2. Look up (or create) the user's record — keyed by `UserId` for global
+//!    sub-structs, or by `(GuildId, UserId)` for the per-guild economy.
 //! 3. Call the user-supplied closure with a reference to the sub-struct.
-//! 4. On writes, clone the entire DB and send the snapshot to the persistence
+//! 4. On writes, clone just the changed record and send it to the persistence
 //!    task via [`persistent_data_channel`] — without blocking the caller.
 //!
+//! Writes that must touch several records together (charge a wallet *and*
+//! grant an item, or move something between two users) go through
+//! [`Data::transact`] instead, which applies all of them or none. Wallets have
+//! no `with_wallet_user_write` at all: every tab movement goes through a
+//! transaction so it is recorded in the guild's ledger.
+//!
+//! # Events
+//!
+//! Once a write has committed, the command describes what happened with
+//! [`Data::publish`]. Interaction stats and achievements react to those
+//! events instead of being called from each command (see
+//! [`super::event_bus`]).
+//!
 //! [`persistent_data_channel`]: Data::persistent_data_channel
 
+use crate::dectalk::pool::Pool;
+use crate::pawthos::enums::domain_event::DomainEvent;
 use crate::pawthos::enums::inventory_errors::InventoryError;
+use crate::pawthos::enums::ledger_reason::LedgerReason;
 use crate::pawthos::enums::mimic_errors::MimicError;
-use crate::pawthos::enums::persistent_data::{PersistentData, UserDailyClaimed};
+use crate::pawthos::enums::persistent_data::{DbRow, PersistentData, RowKey, UserDailyClaimed};
 use crate::pawthos::enums::profile_errors::ProfileError;
+use crate::pawthos::enums::reminder_command::ReminderCommand;
 use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::enums::vox_errors::VoxError;
 use crate::pawthos::enums::wallet_errors::WalletError;
+use crate::pawthos::structs::achievement_rule::{RuleSubject, Stat, Touched};
+use crate::pawthos::structs::event_bus::EventBus;
+use crate::pawthos::structs::guild_config::GuildConfig;
+use crate::pawthos::structs::guild_dictionary::GuildDictionary;
+use crate::pawthos::structs::guild_permissions::GuildPermissions;
+use crate::pawthos::structs::guild_user::GuildUser;
 use crate::pawthos::structs::inventory_user::InventoryUser;
+use crate::pawthos::structs::ledger_entry::{LedgerEntry, TabMemo};
 use crate::pawthos::structs::mimic_user::MimicUser;
 use crate::pawthos::structs::profile_user::ProfileUser;
-use crate::pawthos::structs::schedule_event::ScheduleEvent;
+use crate::pawthos::structs::schedule_event::{EventId, ScheduleEvent};
 use crate::pawthos::structs::schedule_user::ScheduleUser;
-use crate::pawthos::structs::shop_catalog::ACHIEVEMENTS;
+use crate::pawthos::structs::transaction::Transaction;
+use crate::pawthos::structs::shop_catalog;
+use crate::pawthos::structs::user::User;
 use crate::pawthos::structs::user_db::UserDB;
+use crate::pawthos::structs::vox_user::VoxUser;
 use crate::pawthos::structs::wallet_user::{DailyClaimResult, WalletUser};
 use crate::pawthos::traits::{
     InventoryDbMarker, MimicDbMarker, ProfileDbMarker, ScheduleDbMarker, UserDbSpec,
-    WalletDbMarker,
+    VoxDbMarker, WalletDbMarker,
 };
+use crate::voice::player::Players;
 use chrono::{DateTime, Duration, Local, NaiveTime, Utc};
-use poise::serenity_prelude::{ChannelId, MessageId, UserId};
+use chrono_tz::Tz;
+use poise::serenity_prelude::{ChannelId, GuildId, Http, MessageId, UserId};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -44,17 +76,19 @@ use tokio::sync::RwLock;
 ///
 /// Stored in [`Data::faucet_bounties`] keyed by the message ID the bot
 /// reacted to. Removed when either (a) a user claims it by clicking the
-/// reaction, or (b) the cleanup task sweeps it after
-/// [`crate::pawthos::consts::FAUCET_EXPIRY_SECS`].
+/// reaction, or (b) the cleanup task sweeps it once the guild's
+/// `faucet_expiry_secs` have passed.
 #[derive(Debug, Clone)]
 pub struct BountyState {
+    /// Guild whose economy pays out the bounty.
+    pub guild_id: GuildId,
+
     /// Channel the bountied message lives in. Needed to remove the reaction
     /// when the bounty is resolved or expires.
     pub channel_id: ChannelId,
 
-    /// Tabs awarded to the claimer. Frozen at spawn time so changes to
-    /// [`crate::pawthos::consts::FAUCET_REWARD`] don't retroactively alter
-    /// existing bounties.
+    /// Tabs awarded to the claimer. Frozen at spawn time so a `/config set
+    /// faucet_reward` doesn't retroactively alter existing bounties.
     pub amount: i64,
 
     /// UTC instant when this bounty expires. Compared against `Utc::now()`
@@ -71,22 +105,25 @@ pub struct BountyState {
 pub struct Data {
     /// The in-memory user database, protected by an async read-write lock.
     ///
-    /// Multiple commands can read concurrently; writes are exclusive.
-    pub user_db: RwLock<UserDB>,
+    /// Multiple commands can read concurrently; writes are exclusive. `Arc`
+    /// so the schedule reminder task can move repeating events on after
+    /// they fire.
+    pub user_db: Arc<RwLock<UserDB>>,
 
     /// Sender half of the persistence channel.
     ///
-    /// Every successful DB write sends a [`PersistentData::UserDB`] snapshot
-    /// here so the background persistence task can flush it to disk
+    /// Every successful DB write sends the changed [`PersistentData::Rows`]
+    /// here so the background persistence task can flush it to the store
     /// asynchronously. Daily-check requests are also routed through this
     /// channel.
     pub persistent_data_channel: tokio::sync::mpsc::Sender<PersistentData>,
 
     /// Sender half of the schedule-reminder channel.
     ///
-    /// Sending `(UserId, ScheduleEvent)` here causes the background scheduler
-    /// to spawn a task that sleeps until the event time and then DMs the user.
-    pub schedule_events_channel: tokio::sync::mpsc::UnboundedSender<(UserId, ScheduleEvent)>,
+    /// The background scheduler keeps one timer per event and fires each
+    /// reminder when it's due. Use [`Data::queue_reminder`] and
+    /// [`Data::cancel_reminder`] rather than sending directly.
+    pub schedule_events_channel: tokio::sync::mpsc::UnboundedSender<ReminderCommand>,
 
     /// Live faucet bounties, keyed by the message ID the bot reacted to.
     ///
@@ -98,6 +135,20 @@ pub struct Data {
     /// Timestamp of the most recent faucet spawn, used to enforce
     /// [`crate::pawthos::consts::FAUCET_GLOBAL_COOLDOWN_SECS`].
     pub faucet_last_spawn: Arc<RwLock<Option<DateTime<Utc>>>>,
+
+    /// Path of the shop catalog file (`--catalog-path`), re-read by
+    /// `!reload_catalog`.
+    pub catalog_path: PathBuf,
+
+    /// The internal event bus. Publish with [`Data::publish`]; listen with
+    /// [`EventBus::subscribe`].
+    pub events: EventBus,
+
+    /// `/vox` playback queues of the guilds the bot is speaking in.
+    pub voice: Players,
+
+    /// The DECtalk worker threads `/vox say` synthesises on.
+    pub tts: Pool,
 }
 
 /// Generates a matching read/write method pair for one feature's user sub-struct.
@@ -110,12 +161,62 @@ pub struct Data {
 /// - `$err`      — the error type returned by the closure (e.g. `MimicError`)
 /// - `$no_user`  — the error variant to return when the user has no DB entry
 ///
+/// Prefix the arguments with `guild:` for guild-scoped markers; the
+/// generated methods then take a `GuildId` ahead of the `UserId`. Use
+/// `guild_read:` (and drop `$write_fn`) for a sub-struct that must only be
+/// written through [`Data::transact`], like the wallet, whose every change
+/// has to land in the ledger.
+///
 /// # Adding a new feature
 /// Add one line inside `impl Data`:
 /// ```ignore
 /// def_db_access!(with_foo_user_read, with_foo_user_write, FooDbMarker, FooUser, FooError, FooError::NoUserFound);
+/// def_db_access!(guild: with_bar_user_read, with_bar_user_write, BarDbMarker, BarUser, BarError, BarError::NoUserFound);
 /// ```
 macro_rules! def_db_access {
+    (guild_read: $read_fn:ident, $marker:ty, $user_type:ty, $err:ty, $no_user:expr) => {
+        /// Read the calling user's sub-struct in `guild_id` without modifying it.
+        ///
+        /// Returns `Err($no_user)` if the user has no entry in that guild.
+        /// The closure receives an immutable reference and must return
+        /// `Result<R, $err>`.
+        pub async fn $read_fn<R, F>(
+            &self,
+            guild_id: GuildId,
+            user_id: UserId,
+            f: F,
+        ) -> Result<R, $err>
+        where
+            F: for<'a> FnOnce(&'a $user_type) -> Result<R, $err>,
+        {
+            self.with_db_user_read::<$marker, _, _>((guild_id, user_id), |maybe_user| {
+                let user = maybe_user.ok_or($no_user)?;
+                f(user)
+            })
+            .await
+        }
+    };
+    (guild: $read_fn:ident, $write_fn:ident, $marker:ty, $user_type:ty, $err:ty, $no_user:expr) => {
+        def_db_access!(guild_read: $read_fn, $marker, $user_type, $err, $no_user);
+
+        /// Mutably access the calling user's sub-struct in `guild_id`.
+        ///
+        /// Creates a default entry if the user is new to that guild. After
+        /// the closure returns, the member's guild record is queued for
+        /// persistence automatically.
+        pub async fn $write_fn<R, F>(
+            &self,
+            guild_id: GuildId,
+            user_id: UserId,
+            f: F,
+        ) -> Result<R, $err>
+        where
+            F: for<'a> FnOnce(&'a mut $user_type) -> Result<R, $err>,
+        {
+            self.with_db_user_write::<$marker, _, _>((guild_id, user_id), |user| f(user))
+                .await
+        }
+    };
     ($read_fn:ident, $write_fn:ident, $marker:ty, $user_type:ty, $err:ty, $no_user:expr) => {
         /// Read the calling user's sub-struct without modifying it.
         ///
@@ -136,9 +237,9 @@ macro_rules! def_db_access {
         /// Mutably access the calling user's sub-struct.
         ///
         /// Creates a default entry if the user is new. After the closure
-        /// returns, the entire database is snapshotted and queued for
-        /// persistence automatically — the caller does not need to do anything
-        /// extra to trigger a save.
+        /// returns, the user's record is queued for persistence
+        /// automatically — the caller does not need to do anything extra to
+        /// trigger a save.
         pub async fn $write_fn<R, F>(&self, user_id: UserId, f: F) -> Result<R, $err>
         where
             F: for<'a> FnOnce(&'a mut $user_type) -> Result<R, $err>,
@@ -154,43 +255,80 @@ impl Data {
     ///
     /// Private — public callers should use the macro-generated `with_*_user_read`
     /// methods which handle the "user not found" case ergonomically.
-    async fn with_db_user_read<DbMarker, R, F>(&self, user_id: UserId, f: F) -> R
+    async fn with_db_user_read<DbMarker, R, F>(&self, key: DbMarker::Key, f: F) -> R
     where
         DbMarker: UserDbSpec,
         F: for<'a> FnOnce(Option<&'a <DbMarker as UserDbSpec>::User>) -> R,
     {
         let lock = DbMarker::db_lock(self);
         let db_guard = lock.read().await;
-        let maybe_user = DbMarker::get_user(&*db_guard, user_id);
+        let maybe_user = DbMarker::get_user(&*db_guard, key);
         f(maybe_user)
     }
 
-    /// Acquire a write lock, call the closure, then snapshot and queue the DB.
+    /// Acquire a write lock, call the closure, then queue the changed row.
     ///
     /// Private — public callers should use the macro-generated `with_*_user_write`
-    /// methods. The snapshot is sent on `persistent_data_channel`; failures are
+    /// methods. The row is sent on `persistent_data_channel`; failures are
     /// logged but not propagated to the caller.
-    async fn with_db_user_write<DbMarker, R, F>(&self, user_id: UserId, f: F) -> R
+    async fn with_db_user_write<DbMarker, R, F>(&self, key: DbMarker::Key, f: F) -> R
     where
-        DbMarker: UserDbSpec,
+        DbMarker: UserDbSpec<Db = UserDB>,
         F: for<'a> FnOnce(&'a mut <DbMarker as UserDbSpec>::User) -> R,
     {
         let lock = DbMarker::db_lock(self);
         let mut db_guard = lock.write().await;
 
-        let user = DbMarker::get_user_mut(&mut *db_guard, user_id);
+        let user = DbMarker::get_user_mut(&mut *db_guard, key);
         let result = f(user);
 
-        let snapshot = db_guard.clone();
+        let rows = db_guard.row(DbMarker::row_key(key)).into_iter().collect();
         drop(db_guard);
+        self.queue_rows(rows).await;
+        result
+    }
+
+    /// Run `f` against any number of records under a single write lock.
+    ///
+    /// The closure gets a [`Transaction`] and can mutate several sub-structs
+    /// of one user, or several users (e.g. a gift's sender and recipient).
+    /// If it returns `Err`, every record it touched is restored and nothing
+    /// is persisted. If it returns `Ok`, all touched records are queued for
+    /// persistence in exactly one [`PersistentData::Rows`] message.
+    ///
+    /// The closure is synchronous: do Discord API calls before or after, never
+    /// while the lock is held.
+    pub async fn transact<R, E, F>(&self, f: F) -> Result<R, E>
+    where
+        F: for<'a> FnOnce(&mut Transaction<'a>) -> Result<R, E>,
+    {
+        let mut db_guard = self.user_db.write().await;
+        let mut tx = Transaction::new(&mut db_guard);
+        let result = f(&mut tx);
+        if result.is_err() {
+            tx.rollback();
+            return result;
+        }
+
+        let rows = tx.commit();
+        drop(db_guard);
+        self.queue_rows(rows).await;
+        result
+    }
+
+    /// Send changed rows to the persistence task. Failures are logged but not
+    /// propagated — the in-memory DB is already updated either way.
+    async fn queue_rows(&self, rows: Vec<DbRow>) {
+        if rows.is_empty() {
+            return;
+        }
         if let Err(e) = self
             .persistent_data_channel
-            .send(DbMarker::to_persistent_data(snapshot))
+            .send(PersistentData::Rows(rows))
             .await
         {
             log::error!("Failed to queue DB save: {:?}", e);
         }
-        result
     }
 
     //
@@ -214,8 +352,7 @@ impl Data {
         ScheduleError::NoUserFound
     );
     def_db_access!(
-        with_wallet_user_read,
-        with_wallet_user_write,
+        guild_read: with_wallet_user_read,
         WalletDbMarker,
         WalletUser,
         WalletError,
@@ -230,31 +367,161 @@ impl Data {
         ProfileError::NoUserFound
     );
     def_db_access!(
-        with_inventory_user_read,
+        guild: with_inventory_user_read,
         with_inventory_user_write,
         InventoryDbMarker,
         InventoryUser,
         InventoryError,
         InventoryError::NoUserFound
     );
+    def_db_access!(
+        with_vox_user_read,
+        with_vox_user_write,
+        VoxDbMarker,
+        VoxUser,
+        VoxError,
+        VoxError::NoUserFound
+    );
 
-    /// Attempt to grant the daily tab reward to a user.
+    /// `guild_id`'s settings — all defaults if nobody has run `/config set`
+    /// there.
     ///
-    /// This method coordinates with the persistence task (via a request/response
-    /// one-shot channel) to atomically check-and-mark the daily claim. The
-    /// wallet list is intentionally serialised through the single-threaded
-    /// persistence loop to avoid race conditions between concurrent `/daily`
-    /// invocations.
+    /// Returns a copy: read it once at the top of a command and pass it
+    /// down, rather than holding the database lock.
+    pub async fn guild_config(&self, guild_id: GuildId) -> GuildConfig {
+        let db = self.user_db.read().await;
+        db.guilds
+            .get(&guild_id)
+            .map(|g| g.config.clone())
+            .unwrap_or_default()
+    }
+
+    /// Change `guild_id`'s settings and queue them for persistence.
+    ///
+    /// If the closure returns `Err` the settings are put back as they were
+    /// and nothing is persisted.
+    pub async fn with_guild_config_write<R, E, F>(&self, guild_id: GuildId, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut GuildConfig) -> Result<R, E>,
+    {
+        let mut db_guard = self.user_db.write().await;
+        let config = &mut db_guard.guilds.entry(guild_id).or_default().config;
+        let original = config.clone();
+        let result = f(config);
+        if result.is_err() {
+            *config = original;
+            return result;
+        }
+
+        let rows = db_guard.row(RowKey::GuildConfig(guild_id)).into_iter().collect();
+        drop(db_guard);
+        self.queue_rows(rows).await;
+        result
+    }
+
+    /// `guild_id`'s staff roles — none if nobody has run
+    /// `/permissions grant` there.
+    pub async fn guild_permissions(&self, guild_id: GuildId) -> GuildPermissions {
+        let db = self.user_db.read().await;
+        db.guilds
+            .get(&guild_id)
+            .map(|g| g.permissions.clone())
+            .unwrap_or_default()
+    }
+
+    /// Change `guild_id`'s staff roles and queue them for persistence.
+    ///
+    /// If the closure returns `Err` the roles are put back as they were and
+    /// nothing is persisted.
+    pub async fn with_guild_permissions_write<R, E, F>(
+        &self,
+        guild_id: GuildId,
+        f: F,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(&mut GuildPermissions) -> Result<R, E>,
+    {
+        let mut db_guard = self.user_db.write().await;
+        let permissions = &mut db_guard.guilds.entry(guild_id).or_default().permissions;
+        let original = permissions.clone();
+        let result = f(permissions);
+        if result.is_err() {
+            *permissions = original;
+            return result;
+        }
+
+        let rows = db_guard
+            .row(RowKey::GuildPermissions(guild_id))
+            .into_iter()
+            .collect();
+        drop(db_guard);
+        self.queue_rows(rows).await;
+        result
+    }
+
+    /// `guild_id`'s `/vox dict` pronunciations — none if nobody has added
+    /// any there.
+    pub async fn guild_dictionary(&self, guild_id: GuildId) -> GuildDictionary {
+        let db = self.user_db.read().await;
+        db.guilds
+            .get(&guild_id)
+            .map(|g| g.dictionary.clone())
+            .unwrap_or_default()
+    }
+
+    /// Change `guild_id`'s pronunciations and queue them for persistence.
+    ///
+    /// If the closure returns `Err` the dictionary is put back as it was and
+    /// nothing is persisted.
+    pub async fn with_guild_dictionary_write<R, E, F>(
+        &self,
+        guild_id: GuildId,
+        f: F,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(&mut GuildDictionary) -> Result<R, E>,
+    {
+        let mut db_guard = self.user_db.write().await;
+        let dictionary = &mut db_guard.guilds.entry(guild_id).or_default().dictionary;
+        let original = dictionary.clone();
+        let result = f(dictionary);
+        if result.is_err() {
+            *dictionary = original;
+            return result;
+        }
+
+        let rows = db_guard
+            .row(RowKey::GuildDictionary(guild_id))
+            .into_iter()
+            .collect();
+        drop(db_guard);
+        self.queue_rows(rows).await;
+        result
+    }
+
+    /// Attempt to grant the daily tab reward to a user in `guild_id`.
+    ///
+    /// Each guild has its own daily window, so claiming in one server does
+    /// not block claiming in another. This method coordinates with the
+    /// persistence task (via a request/response one-shot channel) to
+    /// atomically check-and-mark the daily claim. The wallet list is
+    /// intentionally serialised through the single-threaded persistence loop
+    /// to avoid race conditions between concurrent `/daily` invocations.
     ///
     /// Returns the user's new tab balance on success, or one of:
     /// - [`WalletError::DailyOnCooldown`] — already claimed today, includes
     ///   remaining seconds until midnight.
     /// - [`WalletError::RecvError`] — the persistence channel dropped (fatal).
-    pub async fn wallet_user_daily(&self, user_id: UserId) -> Result<DailyClaimResult, WalletError> {
+    pub async fn wallet_user_daily(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<DailyClaimResult, WalletError> {
         let (tx, rx) = tokio::sync::oneshot::channel();
 
         self.persistent_data_channel
             .send(PersistentData::DailyCheck {
+                guild_id: guild_id.into(),
                 user_id: user_id.into(),
                 sender: tx,
             })
@@ -277,18 +544,70 @@ impl Data {
                 remaining_secs: remaining.num_seconds(),
             })
         } else {
-            self.with_wallet_user_write(user_id, |user| Ok(user.claim_daily()))
-                .await
+            let config = self.guild_config(guild_id).await;
+            self.transact(|tx| {
+                let result = tx
+                    .get_mut::<WalletDbMarker>((guild_id, user_id))
+                    .claim_daily(&config);
+                tx.record(
+                    guild_id,
+                    user_id,
+                    result.reward,
+                    TabMemo::new(LedgerReason::Daily),
+                );
+                Ok(result)
+            })
+            .await
         }
     }
 
-    /// Return the top `limit` users sorted by tab balance (descending).
+    /// One page of `guild_id`'s ledger, newest first, keeping only entries
+    /// that match `filter`.
+    ///
+    /// `page` is zero-based. Returns the entries on that page together with
+    /// the total number of matching entries, so the caller can render
+    /// "page X of Y".
+    pub async fn ledger_page<F>(
+        &self,
+        guild_id: GuildId,
+        filter: F,
+        page: usize,
+        per_page: usize,
+    ) -> (Vec<LedgerEntry>, usize)
+    where
+        F: Fn(&LedgerEntry) -> bool,
+    {
+        let db = self.user_db.read().await;
+        let Some(guild) = db.guilds.get(&guild_id) else {
+            return (Vec::new(), 0);
+        };
+        let matching: Vec<&LedgerEntry> = guild.ledger.iter().rev().filter(|e| filter(e)).collect();
+        let total = matching.len();
+        let entries = matching
+            .into_iter()
+            .skip(page.saturating_mul(per_page))
+            .take(per_page)
+            .cloned()
+            .collect();
+        (entries, total)
+    }
+
+    /// Return the top `limit` members of `guild_id` sorted by tab balance
+    /// (descending).
     ///
     /// Each entry is `(UserId, tabs, current_streak)`. Acquires a read lock
-    /// on the full user database.
-    pub async fn get_tab_leaderboard(&self, limit: usize) -> Vec<(UserId, i64, u32)> {
+    /// on the full user database. Returns an empty list for a guild whose
+    /// economy has never been touched.
+    pub async fn get_tab_leaderboard(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> Vec<(UserId, i64, u32)> {
         let db = self.user_db.read().await;
-        let mut entries: Vec<_> = db
+        let Some(guild) = db.guilds.get(&guild_id) else {
+            return Vec::new();
+        };
+        let mut entries: Vec<_> = guild
             .db
             .iter()
             .map(|(id, user)| (*id, user.wallet.tabs, user.wallet.current_streak))
@@ -298,58 +617,164 @@ impl Data {
         entries
     }
 
-    /// Check every achievement predicate against `user_id`'s current state
-    /// and unlock any that newly qualify.
+    /// Every user who has chosen a timezone (see
+    /// [`ScheduleUser::home_timezone`]), with that timezone.
+    pub async fn home_timezones(&self) -> Vec<(UserId, Tz)> {
+        let db = self.user_db.read().await;
+        db.db
+            .iter()
+            .filter_map(|(id, user)| Some((*id, user.schedule.home_timezone()?)))
+            .collect()
+    }
+
+    /// Snapshot everything an achievement rule can look at for `user_id` in
+    /// `guild_id`: their [`User`] (default if they have none) and their
+    /// [`GuildUser`]. `None` if they have no record in the guild.
+    pub async fn achievement_subject(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Option<(User, GuildUser)> {
+        let db = self.user_db.read().await;
+        let member = db.get_guild_user(guild_id, user_id)?.clone();
+        let user = db.get_user(user_id).cloned().unwrap_or_default();
+        Some((user, member))
+    }
+
+    /// (Re)start the timer for `user_id`'s `event` with its next unsent
+    /// reminder, replacing any timer it already has. Call after every
+    /// change to an event's time, repeat or reminders.
+    pub fn queue_reminder(&self, user_id: UserId, event: ScheduleEvent) {
+        if let Err(e) = self
+            .schedule_events_channel
+            .send(ReminderCommand::Schedule(user_id, Box::new(event)))
+        {
+            log::error!("Failed to queue reminder task! {}", e);
+        }
+    }
+
+    /// Stop the timer of `user_id`'s event `id`, e.g. once it's deleted.
+    pub fn cancel_reminder(&self, user_id: UserId, id: EventId) {
+        if let Err(e) = self
+            .schedule_events_channel
+            .send(ReminderCommand::Cancel(user_id, id))
+        {
+            log::error!("Failed to cancel reminder task! {}", e);
+        }
+    }
+
+    /// Publish a domain event: run the built-in subscribers, then broadcast
+    /// it on [`Self::events`] for everyone else.
+    ///
+    /// Built-in subscribers, in order:
+    /// 1. **Interaction stats** — `MessageSent` bumps
+    ///    [`InventoryUser::messages_sent`].
+    /// 2. **Achievements** — for every member the event touches (see
+    ///    [`DomainEvent::touches`]), re-check the achievements whose rules
+    ///    depend on what changed.
+    ///
+    /// Commands publish after their write has committed, so subscribers see
+    /// the new state.
+    pub async fn publish(&self, http: &Http, event: DomainEvent) {
+        if let DomainEvent::MessageSent {
+            guild_id, user_id, ..
+        } = &event
+        {
+            let _ = self
+                .with_inventory_user_write(*guild_id, *user_id, |inv| {
+                    inv.messages_sent = inv.messages_sent.saturating_add(1);
+                    Ok(())
+                })
+                .await;
+        }
+
+        if let Some(channel_id) = event.channel_id() {
+            for (guild_id, user_id, touched) in event.touches() {
+                self.unlock_achievements(guild_id, user_id, touched, channel_id, http)
+                    .await;
+            }
+        }
+
+        self.events.emit(event);
+    }
+
+    /// Unlock every active achievement that `user_id` now qualifies for in
+    /// `guild_id`, looking only at rules `touched` can have changed.
     ///
     /// On each unlock, appends the achievement ID to both
     /// [`InventoryUser::unlocked_achievements`] and
     /// [`InventoryUser::owned_badges`], then posts a normal (non-ephemeral)
-    /// announcement in `channel_id` so the community sees it.
-    ///
-    /// Safe to call after any stat mutation: the deduplication check against
-    /// `unlocked_achievements` prevents re-announcing.
+    /// announcement in `channel_id` so the community sees it. Unlocking
+    /// changes the member's badge and achievement counts, so rules depending
+    /// on those are checked again until nothing new unlocks.
     ///
     /// Errors from the announcement post are logged but not returned —
     /// achievement progress is data and should never roll back because a
     /// message failed to send.
-    pub async fn check_achievements(
+    async fn unlock_achievements(
         &self,
+        guild_id: GuildId,
         user_id: UserId,
-        channel_id: poise::serenity_prelude::ChannelId,
-        http: &poise::serenity_prelude::Http,
+        mut touched: Touched,
+        channel_id: ChannelId,
+        http: &Http,
     ) {
-        // Snapshot both sub-structs under a single read lock.
-        let snapshot = {
-            let db = self.user_db.read().await;
-            db.get_user(user_id)
-                .map(|u| (u.inventory.clone(), u.wallet.clone()))
-        };
-        let Some((inv, wallet)) = snapshot else {
-            return;
-        };
+        let catalog = shop_catalog::current();
+        let mut announced = Vec::new();
 
-        let newly_unlocked: Vec<&'static _> = ACHIEVEMENTS
-            .iter()
-            .filter(|a| (a.check)(&inv, &wallet))
-            .filter(|a| !inv.unlocked_achievements.iter().any(|x| x == a.id))
-            .collect();
-        if newly_unlocked.is_empty() {
-            return;
-        }
+        loop {
+            // Most events touch no rule at all; skip the snapshot for those.
+            if !catalog
+                .active_achievements()
+                .any(|a| a.rule.is_touched_by(&touched))
+            {
+                break;
+            }
+            let Some((user, member)) = self.achievement_subject(guild_id, user_id).await else {
+                return;
+            };
+            let subject = RuleSubject {
+                user: &user,
+                member: &member,
+            };
+            let newly_unlocked: Vec<_> = catalog
+                .active_achievements()
+                .filter(|a| !member.inventory.unlocked_achievements.contains(&a.id))
+                .filter(|a| a.rule.is_touched_by(&touched) && a.rule.is_met(&subject))
+                .collect();
+            if newly_unlocked.is_empty() {
+                break;
+            }
 
-        // Persist the unlocks.
-        let _ = self
-            .with_inventory_user_write(user_id, |inv| {
-                for a in &newly_unlocked {
-                    inv.unlocked_achievements.push(a.id.to_string());
-                    inv.owned_badges.push(a.id.to_string());
-                }
-                Ok(())
-            })
-            .await;
+            // Persist the unlocks. Re-checked under the write lock so two
+            // events racing for the same member can't unlock twice; only the
+            // call that pushed an achievement announces it.
+            let pushed: Vec<_> = self
+                .with_inventory_user_write(guild_id, user_id, |inv| {
+                    Ok(newly_unlocked
+                        .into_iter()
+                        .filter(|a| {
+                            let new = !inv.unlocked_achievements.contains(&a.id);
+                            if new {
+                                inv.unlocked_achievements.push(a.id.clone());
+                                inv.owned_badges.push(a.id.clone());
+                            }
+                            new
+                        })
+                        .collect())
+                })
+                .await
+                .unwrap_or_default();
+            if pushed.is_empty() {
+                break;
+            }
+
+            announced.extend(pushed);
+            touched = Touched::owning(&[Stat::AchievementsUnlocked, Stat::BadgesOwned]);
+        }
 
         // Announce.
-        for a in newly_unlocked {
+        for a in announced {
             let content = format!(
                 "🎉 <@{user_id}> unlocked an achievement: **{} {}**\n*{}*",
                 a.emoji, a.name, a.description,
@@ -0,0 +1,45 @@
+//! The internal event bus.
+//!
+//! [`EventBus`] is a typed broadcast channel of [`DomainEvent`]s. It lives on
+//! [`super::data::Data`] as `events`; publishing goes through
+//! [`super::data::Data::publish`], which runs the built-in subscribers
+//! (interaction stats, achievements) before broadcasting, so those never
+//! miss an event. Other subscribers call [`EventBus::subscribe`] and read at
+//! their own pace.
+//!
+//! Background tasks that run before `Data` exists (the reminder task) hold a
+//! clone of the bus and [`EventBus::emit`] directly. The activity log in
+//! [`crate::framework`] is the first outside subscriber.
+
+use crate::pawthos::enums::domain_event::DomainEvent;
+use tokio::sync::broadcast;
+
+/// How many events a slow subscriber may fall behind before it starts
+/// missing the oldest ones.
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Cheap to clone; every clone publishes to the same subscribers.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_BUS_CAPACITY).0,
+        }
+    }
+}
+
+impl EventBus {
+    /// Start receiving every event emitted from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Broadcast `event` to the subscribers. Having none is fine.
+    pub fn emit(&self, event: DomainEvent) {
+        let _ = self.sender.send(event);
+    }
+}
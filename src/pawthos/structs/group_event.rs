@@ -0,0 +1,71 @@
+//! The guild side of a group event.
+//!
+//! A group event is an ordinary [`super::schedule_event::ScheduleEvent`] in
+//! its creator's schedule with a [`GroupEvent`] attached. At the reminder
+//! time it's announced in [`GroupEvent::channel_id`], optionally mentioning
+//! a role, and everyone who RSVP'd through the buttons on the announcement
+//! gets a DM.
+
+use poise::serenity_prelude::{ChannelId, GuildId, MessageId, RoleId, UserId};
+use serde::{Deserialize, Serialize};
+
+/// Where a group event is announced and who's going.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupEvent {
+    pub guild_id: GuildId,
+
+    /// Channel the announcement and the reminder are posted in.
+    pub channel_id: ChannelId,
+
+    /// Role mentioned by the reminder, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_id: Option<RoleId>,
+
+    /// The announcement carrying the join/leave buttons. RSVP button
+    /// presses find their event by this.
+    pub announcement: MessageId,
+
+    /// Who's going, in the order they joined. The creator starts out on it.
+    #[serde(default)]
+    pub rsvps: Vec<UserId>,
+}
+
+/// A press of one of the buttons on a group event announcement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rsvp {
+    Join,
+    Leave,
+}
+
+impl Rsvp {
+    /// Prefix shared by every RSVP button's custom ID.
+    const PREFIX: &'static str = "rsvp";
+
+    /// `rsvp:join:<creator>` — the creator's ID tells the handler whose
+    /// schedule to look the event up in.
+    pub fn custom_id(self, creator: UserId) -> String {
+        let action = match self {
+            Rsvp::Join => "join",
+            Rsvp::Leave => "leave",
+        };
+        format!("{}:{action}:{creator}", Self::PREFIX)
+    }
+
+    /// The reverse of [`Self::custom_id`]; `None` for any other button.
+    pub fn parse(custom_id: &str) -> Option<(Rsvp, UserId)> {
+        let mut parts = custom_id.split(':');
+        if parts.next()? != Self::PREFIX {
+            return None;
+        }
+        let action = match parts.next()? {
+            "join" => Rsvp::Join,
+            "leave" => Rsvp::Leave,
+            _ => return None,
+        };
+        let creator = parts.next()?.parse::<u64>().ok().filter(|id| *id != 0)?;
+        parts
+            .next()
+            .is_none()
+            .then_some((action, UserId::new(creator)))
+    }
+}
@@ -0,0 +1,233 @@
+//! Per-guild settings, changed with `/config`.
+//!
+//! Every tunable number the economy uses — rewards, prices, fees, odds — is
+//! a [`ConfigKey`], as are the limits on what `/vox say` speaks. A guild's
+//! [`GuildConfig`] stores only the keys someone has set with `/config set`;
+//! everything else falls back to the compile-time default in
+//! [`crate::pawthos::consts`], so changing a default there still reaches
+//! every guild that never touched that key.
+//!
+//! Call sites read the effective value through the accessor of the same name
+//! (`config.daily_reward()`), with the config fetched once per command via
+//! [`super::data::Data::guild_config`].
+//!
+//! # Adding a key
+//!
+//! Add one entry to the [`guild_config!`] invocation below: doc line, enum
+//! variant, choice name, field, type, default and accepted range. The enum,
+//! the struct field, the accessor and `/config` support are all generated
+//! from it.
+
+use crate::pawthos::consts::*;
+use crate::pawthos::enums::config_errors::ConfigError;
+use crate::pawthos::structs::shop_catalog::Rarity;
+use serde::{Deserialize, Serialize};
+
+/// Generates [`ConfigKey`], [`GuildConfig`] and their per-key plumbing from
+/// one entry per key.
+///
+/// Each entry reads:
+///
+/// ```ignore
+/// /// One-line description, shown by `/config get`.
+/// Variant "choice_name" => field: Type = DEFAULT, min..=max;
+/// ```
+macro_rules! guild_config {
+    ($(
+        $(#[doc = $doc:literal])+
+        $key:ident $name:literal => $field:ident: $ty:ty = $default:expr, $range:expr;
+    )+) => {
+        /// One setting in a [`GuildConfig`]. Doubles as the `/config` key
+        /// choice, named in `snake_case`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+        pub enum ConfigKey {
+            $(
+                $(#[doc = $doc])+
+                #[name = $name]
+                $key,
+            )+
+        }
+
+        impl ConfigKey {
+            /// Every key, in the order `/config get` lists them.
+            pub const ALL: &[ConfigKey] = &[$(ConfigKey::$key),+];
+
+            /// What the key controls.
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(ConfigKey::$key => concat!($($doc),+),)+
+                }
+                .trim()
+            }
+
+            /// The built-in default, formatted for display.
+            pub fn default_value(self) -> String {
+                match self {
+                    $(ConfigKey::$key => {
+                        let default: $ty = $default;
+                        default.to_string()
+                    })+
+                }
+            }
+        }
+
+        /// One guild's overrides. Unset keys use the built-in defaults.
+        ///
+        /// Persisted as part of [`super::guild_db::GuildDB`]; only keys that
+        /// have been set are written out.
+        #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+        pub struct GuildConfig {
+            $(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                $field: Option<$ty>,
+            )+
+        }
+
+        impl GuildConfig {
+            $(
+                $(#[doc = $doc])+
+                pub fn $field(&self) -> $ty {
+                    self.$field.unwrap_or($default)
+                }
+            )+
+
+            /// The effective value of `key`, formatted for display.
+            pub fn get(&self, key: ConfigKey) -> String {
+                match key {
+                    $(ConfigKey::$key => self.$field().to_string(),)+
+                }
+            }
+
+            /// Whether `key` has been set for this guild.
+            pub fn is_set(&self, key: ConfigKey) -> bool {
+                match key {
+                    $(ConfigKey::$key => self.$field.is_some(),)+
+                }
+            }
+
+            /// Go back to the default for `key`.
+            pub fn reset(&mut self, key: ConfigKey) {
+                match key {
+                    $(ConfigKey::$key => self.$field = None,)+
+                }
+            }
+
+            /// Parse `raw` as the type of `key`, check it against the key's
+            /// range and store it.
+            fn parse_and_set(&mut self, key: ConfigKey, raw: &str) -> Result<(), ConfigError> {
+                match key {
+                    $(ConfigKey::$key => {
+                        let value: $ty = raw.trim().parse().map_err(|_| ConfigError::Invalid {
+                            key: $name,
+                            value: raw.to_string(),
+                        })?;
+                        let range = $range;
+                        if !range.contains(&value) {
+                            return Err(ConfigError::OutOfRange {
+                                key: $name,
+                                min: range.start().to_string(),
+                                max: range.end().to_string(),
+                            });
+                        }
+                        self.$field = Some(value);
+                    })+
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+guild_config! {
+    // --- Wallet ---------------------------------------------------------
+    /// Tabs awarded by `/daily`, before the streak bonus.
+    DailyReward "daily_reward" => daily_reward: i64 = DAILY_REWARD, 0..=10_000;
+    /// Most bonus tabs a `/daily` streak can add.
+    MaxStreakBonus "max_streak_bonus" => max_streak_bonus: i64 = MAX_STREAK_BONUS, 0..=10_000;
+    /// Members shown on `/leaderboard`.
+    LeaderboardSize "leaderboard_size" => leaderboard_size: usize = LEADERBOARD_SIZE, 1..=25;
+
+    // --- Shop -----------------------------------------------------------
+    /// Extra tabs the sender pays on top of a gifted item's price.
+    GiftFee "gift_fee" => gift_fee: i64 = GIFT_FEE, 0..=100_000;
+    /// Price of `/shop buy rolecolor`.
+    RoleColorCost "role_color_cost" => role_color_cost: i64 = ROLE_COLOR_COST, 0..=100_000;
+    /// Price of `/shop buy rolename`.
+    RoleNameCost "role_name_cost" => role_name_cost: i64 = ROLE_NAME_COST, 0..=100_000;
+    /// Price of `/profile set colorway`.
+    CustomColorwaySetCost "custom_colorway_set_cost" => custom_colorway_set_cost: i64 = CUSTOM_COLORWAY_SET_COST, 0..=100_000;
+    /// Price of `/profile set banner`.
+    BannerSetCost "banner_set_cost" => banner_set_cost: i64 = BANNER_SET_COST, 0..=100_000;
+
+    // --- Lootbox --------------------------------------------------------
+    /// Price of one lootbox pull.
+    LootboxCost "lootbox_cost" => lootbox_cost: i64 = LOOTBOX_COST, 0..=100_000;
+    /// Tabs refunded when a pull rolls a badge the member already owns.
+    LootboxSalvage "lootbox_salvage" => lootbox_salvage: i64 = LOOTBOX_SALVAGE, 0..=100_000;
+    /// Relative weight of a Common pull (0–1).
+    LootboxChanceCommon "lootbox_chance_common" => lootbox_chance_common: f64 = LOOTBOX_CHANCE_COMMON, 0.0..=1.0;
+    /// Relative weight of an Uncommon pull (0–1).
+    LootboxChanceUncommon "lootbox_chance_uncommon" => lootbox_chance_uncommon: f64 = LOOTBOX_CHANCE_UNCOMMON, 0.0..=1.0;
+    /// Relative weight of a Rare pull (0–1).
+    LootboxChanceRare "lootbox_chance_rare" => lootbox_chance_rare: f64 = LOOTBOX_CHANCE_RARE, 0.0..=1.0;
+    /// Relative weight of a Legendary pull (0–1).
+    LootboxChanceLegendary "lootbox_chance_legendary" => lootbox_chance_legendary: f64 = LOOTBOX_CHANCE_LEGENDARY, 0.0..=1.0;
+
+    // --- /pay -----------------------------------------------------------
+    /// `/pay` fee as a percentage of the amount, rounded up.
+    PayFeePercent "pay_fee_percent" => pay_fee_percent: i64 = PAY_FEE_PERCENT, 0..=100;
+    /// Most tabs a member may send with `/pay` per day, fee excluded.
+    PayDailyCap "pay_daily_cap" => pay_daily_cap: i64 = PAY_DAILY_CAP, 0..=1_000_000;
+    /// Payments of at least this many tabs ask for confirmation first.
+    PayConfirmThreshold "pay_confirm_threshold" => pay_confirm_threshold: i64 = PAY_CONFIRM_THRESHOLD, 1..=1_000_000;
+
+    // --- Faucet ---------------------------------------------------------
+    /// Chance that a message spawns a faucet bounty (0–1).
+    FaucetTriggerChance "faucet_trigger_chance" => faucet_trigger_chance: f64 = FAUCET_TRIGGER_CHANCE, 0.0..=1.0;
+    /// Tabs paid to whoever claims a faucet bounty.
+    FaucetReward "faucet_reward" => faucet_reward: i64 = FAUCET_REWARD, 0..=10_000;
+    /// Seconds a faucet bounty stays claimable.
+    FaucetExpirySecs "faucet_expiry_secs" => faucet_expiry_secs: i64 = FAUCET_EXPIRY_SECS, 60..=86_400;
+
+    // --- Vox ------------------------------------------------------------
+    /// Most characters `/vox say` speaks; longer text is cut short.
+    VoxMaxLength "vox_max_length" => vox_max_length: usize = VOX_MAX_LENGTH, 1..=2_000;
+    /// Whether `/vox say` obeys DECtalk `[:...]` commands (true/false).
+    VoxInlineCommands "vox_inline_commands" => vox_inline_commands: bool = VOX_INLINE_COMMANDS, false..=true;
+}
+
+impl GuildConfig {
+    /// Set `key` from the text a member typed.
+    ///
+    /// Rejects values of the wrong type or outside the key's range, and
+    /// lootbox weights that would leave every rarity at zero. Nothing
+    /// changes on error.
+    pub fn set(&mut self, key: ConfigKey, raw: &str) -> Result<(), ConfigError> {
+        let before = self.clone();
+        self.parse_and_set(key, raw)?;
+        if self.lootbox_weight_total() <= 0.0 {
+            *self = before;
+            return Err(ConfigError::NoLootboxOdds);
+        }
+        Ok(())
+    }
+
+    /// The probability of pulling `rarity`: its weight over the sum of all
+    /// four weights, so the weights need not add up to 1.
+    pub fn lootbox_odds(&self, rarity: Rarity) -> f64 {
+        let weight = match rarity {
+            Rarity::Common => self.lootbox_chance_common(),
+            Rarity::Uncommon => self.lootbox_chance_uncommon(),
+            Rarity::Rare => self.lootbox_chance_rare(),
+            Rarity::Legendary => self.lootbox_chance_legendary(),
+        };
+        weight / self.lootbox_weight_total()
+    }
+
+    fn lootbox_weight_total(&self) -> f64 {
+        self.lootbox_chance_common()
+            + self.lootbox_chance_uncommon()
+            + self.lootbox_chance_rare()
+            + self.lootbox_chance_legendary()
+    }
+}
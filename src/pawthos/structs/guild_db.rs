@@ -0,0 +1,54 @@
+//! One guild's economy, stored inside [`super::user_db::UserDB::guilds`].
+
+use crate::pawthos::structs::{
+    guild_config::GuildConfig, guild_dictionary::GuildDictionary,
+    guild_permissions::GuildPermissions, guild_user::GuildUser, ledger_entry::LedgerEntry,
+};
+use poise::serenity_prelude::UserId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A `HashMap` from Discord user ID to that user's [`GuildUser`] record,
+/// plus the guild's tab ledger, settings, staff roles and pronunciation
+/// dictionary, for a single guild.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GuildDB {
+    /// The underlying map. `#[serde(default)]` means an empty JSON object
+    /// (`{}`) deserialises as an empty map rather than an error.
+    #[serde(default)]
+    pub db: HashMap<UserId, GuildUser>,
+
+    /// Every tab movement in this guild, oldest first. Append-only; see
+    /// [`LedgerEntry`]. Snapshots from before the ledger existed start empty.
+    #[serde(default)]
+    pub ledger: Vec<LedgerEntry>,
+
+    /// Settings changed with `/config`. Snapshots from before per-guild
+    /// settings existed use the defaults throughout.
+    #[serde(default)]
+    pub config: GuildConfig,
+
+    /// Admin and moderator roles set with `/permissions`. Snapshots from
+    /// before staff roles existed start with none.
+    #[serde(default)]
+    pub permissions: GuildPermissions,
+
+    /// Pronunciations added with `/vox dict`. Snapshots from before guild
+    /// dictionaries existed start with none.
+    #[serde(default)]
+    pub dictionary: GuildDictionary,
+}
+
+impl GuildDB {
+    /// Return an immutable reference to a member's record, or `None` if the
+    /// member has never touched this guild's economy.
+    pub fn get_user(&self, user: UserId) -> Option<&GuildUser> {
+        self.db.get(&user)
+    }
+
+    /// Return a mutable reference to a member's record, inserting a
+    /// default-constructed [`GuildUser`] if this is their first interaction.
+    pub fn get_user_mut(&mut self, user: UserId) -> &mut GuildUser {
+        self.db.entry(user).or_default()
+    }
+}
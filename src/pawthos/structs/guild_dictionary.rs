@@ -0,0 +1,45 @@
+//! One guild's `/vox dict` pronunciation overrides.
+
+use crate::dectalk::{DectalkError, dictionary};
+use crate::pawthos::consts::VOX_DICT_MAX_ENTRIES;
+use crate::pawthos::enums::vox_errors::VoxError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Words and how DECtalk should say them in this guild, compiled into a
+/// user dictionary for each `/vox say` (see [`dictionary::compile`]).
+///
+/// Words are kept as [`dictionary::normalise_word`] leaves them and
+/// pronunciations as they were typed, both checked when added.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuildDictionary {
+    #[serde(default)]
+    pub entries: BTreeMap<String, String>,
+}
+
+impl GuildDictionary {
+    /// Say `word` as `pronunciation` from now on. Returns the pronunciation
+    /// it had before, if any.
+    pub fn add(&mut self, word: String, pronunciation: String) -> Result<Option<String>, VoxError> {
+        if !self.entries.contains_key(&word) && self.entries.len() >= VOX_DICT_MAX_ENTRIES {
+            return Err(VoxError::DictionaryFull(VOX_DICT_MAX_ENTRIES));
+        }
+        Ok(self.entries.insert(word, pronunciation))
+    }
+
+    /// Go back to DECtalk's own pronunciation of `word`. Returns the one it
+    /// had.
+    pub fn remove(&mut self, word: &str) -> Result<String, VoxError> {
+        self.entries
+            .remove(word)
+            .ok_or_else(|| VoxError::NotInDictionary(word.to_string()))
+    }
+
+    /// The compiled dictionary, or `None` if there are no entries.
+    pub fn compile(&self) -> Result<Option<Vec<u8>>, DectalkError> {
+        if self.entries.is_empty() {
+            return Ok(None);
+        }
+        dictionary::compile(self.entries.iter().map(|(w, p)| (w.as_str(), p.as_str()))).map(Some)
+    }
+}
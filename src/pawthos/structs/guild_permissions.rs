@@ -0,0 +1,140 @@
+//! One guild's staff roles, managed with `/permissions`.
+
+use crate::pawthos::enums::{
+    permission_errors::PermissionError,
+    permission_level::{PermissionLevel, StaffLevel},
+};
+use poise::serenity_prelude::RoleId;
+use serde::{Deserialize, Serialize};
+
+/// Roles whose holders get [`PermissionLevel::Admin`] or
+/// [`PermissionLevel::Moderator`] in this guild.
+///
+/// A role is in at most one list; granting it the other level moves it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuildPermissions {
+    #[serde(default)]
+    pub admin_roles: Vec<RoleId>,
+    #[serde(default)]
+    pub mod_roles: Vec<RoleId>,
+}
+
+impl GuildPermissions {
+    /// The highest level any of `roles` carries, or
+    /// [`PermissionLevel::Member`] if none of them is a staff role.
+    pub fn level_for(&self, roles: &[RoleId]) -> PermissionLevel {
+        if roles.iter().any(|r| self.admin_roles.contains(r)) {
+            PermissionLevel::Admin
+        } else if roles.iter().any(|r| self.mod_roles.contains(r)) {
+            PermissionLevel::Moderator
+        } else {
+            PermissionLevel::Member
+        }
+    }
+
+    /// Make `role` a staff role at `level`, replacing any level it had.
+    pub fn grant(&mut self, role: RoleId, level: StaffLevel) -> Result<(), PermissionError> {
+        if self.level_of(role) == Some(level) {
+            return Err(PermissionError::AlreadyGranted {
+                role,
+                level: level.into(),
+            });
+        }
+        self.admin_roles.retain(|r| *r != role);
+        self.mod_roles.retain(|r| *r != role);
+        match level {
+            StaffLevel::Admin => self.admin_roles.push(role),
+            StaffLevel::Moderator => self.mod_roles.push(role),
+        }
+        Ok(())
+    }
+
+    /// Stop treating `role` as a staff role. Returns the level it had.
+    pub fn revoke(&mut self, role: RoleId) -> Result<StaffLevel, PermissionError> {
+        let level = self
+            .level_of(role)
+            .ok_or(PermissionError::NotGranted(role))?;
+        self.admin_roles.retain(|r| *r != role);
+        self.mod_roles.retain(|r| *r != role);
+        Ok(level)
+    }
+
+    fn level_of(&self, role: RoleId) -> Option<StaffLevel> {
+        if self.admin_roles.contains(&role) {
+            Some(StaffLevel::Admin)
+        } else if self.mod_roles.contains(&role) {
+            Some(StaffLevel::Moderator)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAFF: RoleId = RoleId::new(1);
+    const OTHER: RoleId = RoleId::new(2);
+    const EVERYONE: RoleId = RoleId::new(3);
+
+    #[test]
+    fn grant_adds_then_moves_a_role() {
+        let mut perms = GuildPermissions::default();
+        perms.grant(STAFF, StaffLevel::Moderator).unwrap();
+        assert_eq!(perms.mod_roles, [STAFF]);
+
+        perms.grant(STAFF, StaffLevel::Admin).unwrap();
+        assert_eq!(perms.admin_roles, [STAFF]);
+        assert!(perms.mod_roles.is_empty());
+    }
+
+    #[test]
+    fn grant_refuses_the_level_a_role_already_has() {
+        let mut perms = GuildPermissions::default();
+        perms.grant(STAFF, StaffLevel::Admin).unwrap();
+        assert!(matches!(
+            perms.grant(STAFF, StaffLevel::Admin),
+            Err(PermissionError::AlreadyGranted {
+                role: STAFF,
+                level: PermissionLevel::Admin
+            })
+        ));
+        assert_eq!(perms.admin_roles, [STAFF]);
+    }
+
+    #[test]
+    fn revoke_returns_the_level_it_took() {
+        let mut perms = GuildPermissions::default();
+        perms.grant(STAFF, StaffLevel::Moderator).unwrap();
+        assert_eq!(perms.revoke(STAFF).unwrap(), StaffLevel::Moderator);
+        assert_eq!(perms, GuildPermissions::default());
+        assert!(matches!(
+            perms.revoke(STAFF),
+            Err(PermissionError::NotGranted(STAFF))
+        ));
+    }
+
+    #[test]
+    fn the_highest_role_wins() {
+        let mut perms = GuildPermissions::default();
+        perms.grant(STAFF, StaffLevel::Admin).unwrap();
+        perms.grant(OTHER, StaffLevel::Moderator).unwrap();
+
+        assert_eq!(perms.level_for(&[]), PermissionLevel::Member);
+        assert_eq!(perms.level_for(&[EVERYONE]), PermissionLevel::Member);
+        assert_eq!(
+            perms.level_for(&[EVERYONE, OTHER]),
+            PermissionLevel::Moderator
+        );
+        assert_eq!(perms.level_for(&[OTHER, STAFF]), PermissionLevel::Admin);
+    }
+
+    #[test]
+    fn levels_compare_lowest_first() {
+        // `checks::require` relies on this to let owners past admin checks.
+        assert!(PermissionLevel::Owner > PermissionLevel::Admin);
+        assert!(PermissionLevel::Admin > PermissionLevel::Moderator);
+        assert!(PermissionLevel::Moderator > PermissionLevel::Member);
+    }
+}
@@ -0,0 +1,24 @@
+//! The per-guild slice of a user's record, stored in [`super::guild_db::GuildDB`].
+
+use crate::pawthos::structs::{inventory_user::InventoryUser, wallet_user::WalletUser};
+use serde::{Deserialize, Serialize};
+
+/// All state a single Discord user has inside one guild's economy.
+///
+/// Tabs, streaks, inventories and achievement progress are scoped per guild
+/// so that communities sharing the bot don't share a currency. Everything
+/// that is about the *person* rather than the *economy* (mimics, schedule,
+/// profile text) stays on [`super::user::User`].
+///
+/// Like [`super::user::User`], every field is `#[serde(default)]` so new
+/// sub-structs can be added without breaking old snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GuildUser {
+    /// State for the wallet (`/daily`, `/balance`, `/leaderboard`).
+    #[serde(default)]
+    pub wallet: WalletUser,
+
+    /// State for the `/shop` suite — owned items, unlock flags, interaction stats.
+    #[serde(default)]
+    pub inventory: InventoryUser,
+}
@@ -0,0 +1,769 @@
+//! iCalendar (`.ics`) files for `/schedule export` and `/schedule import`.
+//!
+//! Export writes one `VEVENT` per event. `DTSTART` is in the event's own
+//! timezone, named by its IANA `TZID` without a `VTIMEZONE` block (the
+//! common calendar apps all resolve IANA names), or in UTC for UTC events.
+//! Repeating events get an `RRULE`.
+//!
+//! Import reads the part of RFC 5545 a schedule can hold: a timed
+//! `DTSTART` and, optionally, an `RRULE` that maps onto a
+//! [`RecurrenceRule`]. Anything else is reported back as a [`Skipped`]
+//! entry rather than imported approximately.
+//!
+//! | `RRULE` | Imported as |
+//! |---|---|
+//! | `FREQ=DAILY;INTERVAL=n` | [`RecurrenceRule::EveryNDays`] |
+//! | `FREQ=WEEKLY;BYDAY=MO,WE` | [`RecurrenceRule::Weekdays`] |
+//! | `FREQ=MONTHLY;BYDAY=2TU` (or `-1TU`) | [`RecurrenceRule::NthWeekday`] |
+//!
+//! `UNTIL` and `COUNT` both become [`Recurrence::until`].
+
+use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::structs::lead_time::LeadTimes;
+use crate::pawthos::structs::recurrence::{self, MonthWeek, Recurrence, RecurrenceRule};
+use crate::pawthos::structs::schedule_event::{EventId, ScheduleEvent};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc, Weekday};
+use chrono_tz::Tz;
+use std::fmt;
+use std::str::FromStr;
+
+/// Identifies the bot as the calendar's producer.
+const PRODID: &str = "-//logosV3//Schedule//EN";
+
+/// Longest line, in bytes, before it's folded onto the next.
+const MAX_LINE_BYTES: usize = 75;
+
+/// Most occurrences a `COUNT` can ask for.
+const MAX_COUNT: u32 = 1000;
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Write `events` as an iCalendar file, stamped with `now`.
+pub fn export(events: &[ScheduleEvent], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{PRODID}"),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@logos", event.id));
+        lines.push(format!("DTSTAMP:{}", utc_stamp(now)));
+        lines.push(dtstart(event));
+        lines.push(format!("SUMMARY:{}", escape(&event.name)));
+        if let Some(rec) = &event.recurrence {
+            lines.push(format!("RRULE:{}", rrule(rec, event.tz)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// `DTSTART;TZID=Europe/London:20260501T140000`, or
+/// `DTSTART:20260501T130000Z` for an event in UTC.
+fn dtstart(event: &ScheduleEvent) -> String {
+    if event.tz == Tz::UTC {
+        return format!("DTSTART:{}", utc_stamp(event.when));
+    }
+    format!(
+        "DTSTART;TZID={}:{}",
+        event.tz.name(),
+        event.when.with_timezone(&event.tz).format("%Y%m%dT%H%M%S")
+    )
+}
+
+/// `FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=…`. `UNTIL` is the last occurrence's
+/// start in UTC, as RFC 5545 requires alongside a `TZID` start.
+fn rrule(rec: &Recurrence, tz: Tz) -> String {
+    let mut rule = match &rec.rule {
+        RecurrenceRule::EveryNDays { interval } => format!("FREQ=DAILY;INTERVAL={interval}"),
+        RecurrenceRule::Weekdays { days } => {
+            let days = days.iter().map(|d| weekday_code(*d)).collect::<Vec<_>>();
+            format!("FREQ=WEEKLY;BYDAY={}", days.join(","))
+        }
+        RecurrenceRule::NthWeekday { week, weekday } => {
+            let n = match week {
+                MonthWeek::First => 1,
+                MonthWeek::Second => 2,
+                MonthWeek::Third => 3,
+                MonthWeek::Fourth => 4,
+                MonthWeek::Last => -1,
+            };
+            format!("FREQ=MONTHLY;BYDAY={n}{}", weekday_code(*weekday))
+        }
+    };
+    if let Some(end) = rec
+        .until
+        .and_then(|until| recurrence::localise(until, rec.time, tz))
+    {
+        rule.push_str(&format!(";UNTIL={}", utc_stamp(end)));
+    }
+    rule
+}
+
+fn utc_stamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a `TEXT` value.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Split `line` into CRLF-terminated lines of at most [`MAX_LINE_BYTES`],
+/// each continuation starting with a space.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_BYTES {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// What [`parse`] made of a calendar.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Events to add, with fresh IDs and the default reminder; see
+    /// [`super::schedule_user::ScheduleUser::import_event`].
+    pub events: Vec<ScheduleEvent>,
+
+    /// Entries that weren't imported, in file order.
+    pub skipped: Vec<Skipped>,
+}
+
+/// A calendar entry that wasn't imported.
+#[derive(Debug)]
+pub struct Skipped {
+    /// Its `SUMMARY`, or `(untitled)`.
+    pub name: String,
+    pub reason: SkipReason,
+}
+
+/// Why a calendar entry wasn't imported.
+#[derive(Debug)]
+pub enum SkipReason {
+    /// A one-off in the past, or a series that has ended.
+    Past,
+    /// Something the schedule can't represent, or a malformed entry.
+    Unreadable(String),
+    /// The schedule already has an event with that name and time.
+    AlreadyScheduled,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Past => f.write_str("already over"),
+            SkipReason::Unreadable(why) => write!(f, "couldn't import it: {why}"),
+            SkipReason::AlreadyScheduled => f.write_str("already in your schedule"),
+        }
+    }
+}
+
+/// Read every `VEVENT` in an iCalendar file.
+///
+/// Floating times (no `TZID` and no `Z`) are read in `default_tz`, which
+/// is also the timezone UTC times are shown in. Repeating events whose
+/// start is past join at their next occurrence after `now`.
+///
+/// Fails with [`ScheduleError::NotACalendar`] only if the file isn't
+/// iCalendar at all; bad entries are skipped individually.
+pub fn parse(
+    text: &str,
+    default_tz: Tz,
+    now: DateTime<Utc>,
+) -> Result<ImportReport, ScheduleError> {
+    let unfolded = text
+        .replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+    let mut lines = unfolded.lines().map(str::trim_end);
+    if !lines.any(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+        return Err(ScheduleError::NotACalendar);
+    }
+
+    let mut report = ImportReport::default();
+    let mut event: Option<Vec<Property>> = None;
+    // Depth of components nested in the current VEVENT, e.g. VALARM.
+    let mut nested = 0;
+    for line in lines {
+        let Some(prop) = Property::parse(line) else {
+            continue;
+        };
+        match (prop.name.as_str(), prop.value.to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") if event.is_none() => event = Some(Vec::new()),
+            ("BEGIN", _) if event.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => {
+                if let Some(props) = event.take() {
+                    match read_event(&props, default_tz, now) {
+                        Ok(imported) => report.events.push(imported),
+                        Err(skipped) => report.skipped.push(skipped),
+                    }
+                }
+            }
+            ("END", _) if nested > 0 => nested -= 1,
+            _ if nested == 0 => {
+                if let Some(props) = &mut event {
+                    props.push(prop);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(report)
+}
+
+/// One `NAME;PARAM=value:VALUE` content line.
+#[derive(Debug)]
+struct Property {
+    /// Upper-cased.
+    name: String,
+    /// Names upper-cased, values unquoted.
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Option<Property> {
+        // The value starts at the first colon outside a quoted parameter.
+        let mut quoted = false;
+        let colon = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                quoted = !quoted;
+                None
+            }
+            ':' if !quoted => Some(i),
+            _ => None,
+        })?;
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next()?.trim().to_ascii_uppercase();
+        let params = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| {
+                (
+                    k.trim().to_ascii_uppercase(),
+                    v.trim_matches('"').to_string(),
+                )
+            })
+            .collect();
+        Some(Property {
+            name,
+            params,
+            value: value.to_string(),
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Turn one `VEVENT`'s properties into an event, or say why not.
+fn read_event(
+    props: &[Property],
+    default_tz: Tz,
+    now: DateTime<Utc>,
+) -> Result<ScheduleEvent, Skipped> {
+    let prop = |name: &str| props.iter().find(|p| p.name == name);
+    let name = prop("SUMMARY")
+        .map(|p| unescape(&p.value))
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "(untitled)".to_string());
+    let skip = |reason| Skipped {
+        name: name.clone(),
+        reason,
+    };
+    let unreadable = |why: String| skip(SkipReason::Unreadable(why));
+
+    if prop("STATUS").is_some_and(|p| p.value.eq_ignore_ascii_case("CANCELLED")) {
+        return Err(unreadable("it's cancelled".to_string()));
+    }
+    let start = prop("DTSTART").ok_or_else(|| unreadable("it has no start time".to_string()))?;
+    let (local, tz, at) = read_start(start, default_tz).map_err(unreadable)?;
+    let recurrence = prop("RRULE")
+        .map(|p| read_rrule(&p.value, local, tz))
+        .transpose()
+        .map_err(unreadable)?;
+
+    let when = match &recurrence {
+        Some(rec) => rec.first_after(local.date(), now, tz),
+        None => Some(at).filter(|at| *at > now),
+    }
+    .ok_or_else(|| skip(SkipReason::Past))?;
+
+    Ok(ScheduleEvent {
+        id: EventId::random(),
+        name,
+        when,
+        tz,
+        recurrence,
+        group: None,
+        lead_times: LeadTimes::default(),
+        reminded: None,
+        snooze: None,
+        done: None,
+    })
+}
+
+/// A `DTSTART`'s local date and time, timezone, and the instant it names.
+fn read_start(
+    start: &Property,
+    default_tz: Tz,
+) -> Result<(NaiveDateTime, Tz, DateTime<Utc>), String> {
+    let value = start.value.trim();
+    if start
+        .param("VALUE")
+        .is_some_and(|v| v.eq_ignore_ascii_case("DATE"))
+        || value.len() == 8
+    {
+        return Err("all-day events aren't supported".to_string());
+    }
+    let tz = match start.param("TZID") {
+        Some(tzid) => resolve_tz(tzid).ok_or_else(|| format!("unknown timezone `{tzid}`"))?,
+        None => default_tz,
+    };
+    let bad = || format!("bad start time `{value}`");
+    match value.strip_suffix(['Z', 'z']) {
+        Some(utc) => {
+            let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+                .map_err(|_| bad())?
+                .and_utc();
+            Ok((at.with_timezone(&tz).naive_local(), tz, at))
+        }
+        None => {
+            let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| bad())?;
+            let at = recurrence::localise(local.date(), local.time(), tz).ok_or_else(bad)?;
+            Ok((local, tz, at))
+        }
+    }
+}
+
+/// An IANA name, possibly behind a vendor prefix such as
+/// `/mozilla.org/20050126_1/Europe/London`.
+fn resolve_tz(tzid: &str) -> Option<Tz> {
+    let mut rest = tzid.trim();
+    loop {
+        if let Ok(tz) = Tz::from_str(rest) {
+            return Some(tz);
+        }
+        rest = rest.split_once('/')?.1;
+    }
+}
+
+/// Map an `RRULE` onto a [`Recurrence`] for a series starting at `start`.
+fn read_rrule(rule: &str, start: NaiveDateTime, tz: Tz) -> Result<Recurrence, String> {
+    let unsupported = || format!("repeat `{rule}` isn't supported");
+    let mut freq = None;
+    let mut interval = 1;
+    let mut by_day = None;
+    let mut until = None;
+    let mut count = None;
+    for part in rule.split(';').filter(|p| !p.is_empty()) {
+        let (key, value) = part.split_once('=').ok_or_else(unsupported)?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => freq = Some(value.to_ascii_uppercase()),
+            "INTERVAL" => interval = value.parse::<u32>().map_err(|_| unsupported())?,
+            "BYDAY" => by_day = Some(value.to_ascii_uppercase()),
+            "UNTIL" => until = Some(read_until(value, tz).ok_or_else(unsupported)?),
+            "COUNT" => count = Some(value.parse::<u32>().map_err(|_| unsupported())?),
+            "WKST" => {}
+            _ => return Err(unsupported()),
+        }
+    }
+
+    let rule = match (freq.as_deref(), by_day.as_deref()) {
+        (Some("DAILY"), None) if (1..=365).contains(&interval) => {
+            RecurrenceRule::EveryNDays { interval }
+        }
+        (Some("WEEKLY"), days) if interval == 1 => {
+            let mut days = match days {
+                Some(days) => days
+                    .split(',')
+                    .map(read_weekday)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(unsupported)?,
+                None => vec![start.weekday()],
+            };
+            days.sort_by_key(|d| d.num_days_from_monday());
+            days.dedup();
+            RecurrenceRule::Weekdays { days }
+        }
+        (Some("MONTHLY"), Some(day)) if interval == 1 => {
+            let split = day
+                .len()
+                .checked_sub(2)
+                .filter(|i| day.is_char_boundary(*i))
+                .ok_or_else(unsupported)?;
+            let weekday = read_weekday(&day[split..]).ok_or_else(unsupported)?;
+            let week = match &day[..split] {
+                "1" | "+1" => MonthWeek::First,
+                "2" | "+2" => MonthWeek::Second,
+                "3" | "+3" => MonthWeek::Third,
+                "4" | "+4" => MonthWeek::Fourth,
+                "-1" => MonthWeek::Last,
+                _ => return Err(unsupported()),
+            };
+            RecurrenceRule::NthWeekday { week, weekday }
+        }
+        _ => return Err(unsupported()),
+    };
+    let mut rec = Recurrence {
+        rule,
+        time: start.time(),
+        until,
+    };
+
+    if let Some(count) = count {
+        if until.is_some() || !(1..=MAX_COUNT).contains(&count) {
+            return Err(unsupported());
+        }
+        // Walk to the last occurrence and end the series on its date.
+        let mut last = rec
+            .first_on_or_after(start.date())
+            .and_then(|d| recurrence::localise(d, rec.time, tz))
+            .ok_or_else(unsupported)?;
+        for _ in 1..count {
+            match rec.next_after(last, last, tz) {
+                Some(next) => last = next,
+                None => break,
+            }
+        }
+        rec.until = Some(last.with_timezone(&tz).date_naive());
+    }
+    Ok(rec)
+}
+
+/// An `UNTIL` as a local date in `tz`: a date, a UTC date-time or a
+/// floating one.
+fn read_until(value: &str, tz: Tz) -> Option<NaiveDate> {
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(at.and_utc().with_timezone(&tz).date_naive());
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d").ok().or_else(|| {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|at| at.date())
+    })
+}
+
+fn read_weekday(code: &str) -> Option<Weekday> {
+    Some(match code.trim() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// Undo [`escape`].
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use chrono_tz::{America, Asia, Australia, Europe};
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// An event at `local` in `tz`.
+    fn event(name: &str, local: &str, tz: Tz, recurrence: Option<Recurrence>) -> ScheduleEvent {
+        let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+        ScheduleEvent {
+            id: EventId::random(),
+            name: name.to_string(),
+            when: recurrence::localise(local.date(), local.time(), tz).unwrap(),
+            tz,
+            recurrence,
+            group: None,
+            lead_times: LeadTimes::default(),
+            reminded: None,
+            snooze: None,
+            done: None,
+        }
+    }
+
+    fn repeat(rule: RecurrenceRule, at: &str, until: Option<&str>) -> Option<Recurrence> {
+        Some(Recurrence {
+            rule,
+            time: NaiveTime::parse_from_str(at, "%H:%M").unwrap(),
+            until: until.map(date),
+        })
+    }
+
+    /// A calendar holding one `VEVENT` per entry of `events`, each given as
+    /// its property lines.
+    fn calendar(events: &[&[&str]]) -> String {
+        let mut lines = vec!["BEGIN:VCALENDAR", "VERSION:2.0"];
+        for props in events {
+            lines.push("BEGIN:VEVENT");
+            lines.extend_from_slice(props);
+            lines.push("END:VEVENT");
+        }
+        lines.push("END:VCALENDAR");
+        lines.join("\r\n")
+    }
+
+    fn import(text: &str) -> ImportReport {
+        parse(text, Tz::UTC, utc("2026-05-01 00:00")).unwrap()
+    }
+
+    #[test]
+    fn export_then_parse_round_trips() {
+        let events = [
+            event("One-off", "2026-06-15 12:00", Tz::UTC, None),
+            event(
+                "Every third day",
+                "2026-06-01 09:30",
+                Europe::London,
+                repeat(RecurrenceRule::EveryNDays { interval: 3 }, "09:30", None),
+            ),
+            event(
+                "Gym",
+                "2026-06-01 18:00",
+                America::New_York,
+                repeat(
+                    RecurrenceRule::Weekdays {
+                        days: vec![Weekday::Mon, Weekday::Wed, Weekday::Fri],
+                    },
+                    "18:00",
+                    Some("2026-07-31"),
+                ),
+            ),
+            event(
+                "Month end",
+                "2026-05-29 20:00",
+                Asia::Tokyo,
+                repeat(
+                    RecurrenceRule::NthWeekday {
+                        week: MonthWeek::Last,
+                        weekday: Weekday::Fri,
+                    },
+                    "20:00",
+                    None,
+                ),
+            ),
+            event(
+                "Planning; budget, \\ notes\nsecond line",
+                "2026-06-09 07:15",
+                Australia::Sydney,
+                repeat(
+                    RecurrenceRule::NthWeekday {
+                        week: MonthWeek::Second,
+                        weekday: Weekday::Tue,
+                    },
+                    "07:15",
+                    Some("2026-12-31"),
+                ),
+            ),
+        ];
+        let now = utc("2026-05-01 00:00");
+        let report = parse(&export(&events, now), Tz::UTC, now).unwrap();
+        assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+        assert_eq!(report.events.len(), events.len());
+        for (read, written) in report.events.iter().zip(&events) {
+            assert_eq!(read.name, written.name);
+            assert_eq!(read.when, written.when, "{}", written.name);
+            assert_eq!(read.tz, written.tz, "{}", written.name);
+            assert_eq!(read.recurrence, written.recurrence, "{}", written.name);
+        }
+    }
+
+    #[test]
+    fn folds_long_lines_on_character_boundaries() {
+        let name = "é".repeat(50) + &"🎉".repeat(20) + "end";
+        let line = format!("SUMMARY:{name}");
+        let folded = fold(&line);
+        assert!(folded.ends_with("\r\n"));
+
+        let lines: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(lines.len() > 1);
+        for (n, part) in lines.iter().enumerate() {
+            assert!(
+                part.len() <= MAX_LINE_BYTES,
+                "line {n} is {} bytes",
+                part.len()
+            );
+            assert_eq!(n > 0, part.starts_with(' '), "line {n}");
+        }
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+
+        let short = fold("SUMMARY:short");
+        assert_eq!(short, "SUMMARY:short\r\n");
+
+        let events = [event(&name, "2026-06-01 09:00", Tz::UTC, None)];
+        let report = import(&export(&events, utc("2026-05-01 00:00")));
+        assert_eq!(report.events[0].name, name);
+    }
+
+    #[test]
+    fn escape_and_unescape() {
+        let text = "a;b,c\\d\ne";
+        assert_eq!(escape(text), r"a\;b\,c\\d\ne");
+        assert_eq!(unescape(&escape(text)), text);
+        assert_eq!(unescape("line\\Nbreak"), "line\nbreak");
+        assert_eq!(unescape("trailing\\"), "trailing\\");
+    }
+
+    #[test]
+    fn vendor_prefixed_tzid() {
+        let report = import(&calendar(&[
+            &[
+                "SUMMARY:Prefixed",
+                "DTSTART;TZID=/mozilla.org/20050126_1/Europe/London:20260601T090000",
+            ],
+            &[
+                "SUMMARY:Quoted",
+                "DTSTART;TZID=\"America/New_York\":20260601T090000",
+            ],
+            &[
+                "SUMMARY:Unknown",
+                "DTSTART;TZID=Nowhere/Special:20260601T090000",
+            ],
+        ]));
+        assert_eq!(report.events.len(), 2);
+        assert_eq!(report.events[0].tz, Europe::London);
+        assert_eq!(report.events[0].when, utc("2026-06-01 08:00"));
+        assert_eq!(report.events[1].tz, America::New_York);
+        assert_eq!(report.events[1].when, utc("2026-06-01 13:00"));
+
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, "Unknown");
+        assert!(matches!(
+            report.skipped[0].reason,
+            SkipReason::Unreadable(_)
+        ));
+    }
+
+    #[test]
+    fn count_becomes_until() {
+        let start = NaiveDateTime::parse_from_str("2026-06-01 09:00", "%Y-%m-%d %H:%M").unwrap();
+        let weekly = read_rrule("FREQ=WEEKLY;BYDAY=MO,TH;COUNT=4", start, Europe::London).unwrap();
+        assert_eq!(weekly.until, Some(date("2026-06-11")));
+
+        let daily = read_rrule("FREQ=DAILY;INTERVAL=2;COUNT=3", start, Europe::London).unwrap();
+        assert_eq!(daily.rule, RecurrenceRule::EveryNDays { interval: 2 });
+        assert_eq!(daily.until, Some(date("2026-06-05")));
+
+        let once = read_rrule("FREQ=DAILY;COUNT=1", start, Europe::London).unwrap();
+        assert_eq!(once.until, Some(date("2026-06-01")));
+
+        for rule in [
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20260610",
+            "FREQ=YEARLY",
+            "FREQ=WEEKLY;INTERVAL=2",
+        ] {
+            assert!(read_rrule(rule, start, Europe::London).is_err(), "{rule}");
+        }
+    }
+
+    #[test]
+    fn skip_reasons() {
+        let report = import(&calendar(&[
+            &["SUMMARY:Holiday", "DTSTART;VALUE=DATE:20260601"],
+            &["SUMMARY:Last year", "DTSTART:20250601T090000Z"],
+            &[
+                "SUMMARY:Called off",
+                "STATUS:CANCELLED",
+                "DTSTART:20260601T090000Z",
+            ],
+            &[
+                "SUMMARY:Ended series",
+                "DTSTART:20250601T090000Z",
+                "RRULE:FREQ=DAILY;UNTIL=20250701T090000Z",
+            ],
+            &["DTSTART:20260601T090000Z"],
+        ]));
+        assert_eq!(report.events.len(), 1);
+        assert_eq!(report.events[0].name, "(untitled)");
+
+        let reasons: Vec<(&str, &SkipReason)> = report
+            .skipped
+            .iter()
+            .map(|s| (s.name.as_str(), &s.reason))
+            .collect();
+        assert!(
+            matches!(
+                reasons[..],
+                [
+                    ("Holiday", SkipReason::Unreadable(_)),
+                    ("Last year", SkipReason::Past),
+                    ("Called off", SkipReason::Unreadable(_)),
+                    ("Ended series", SkipReason::Past),
+                ]
+            ),
+            "{reasons:?}"
+        );
+    }
+
+    #[test]
+    fn not_a_calendar() {
+        assert!(matches!(
+            parse("hello", Tz::UTC, utc("2026-05-01 00:00")),
+            Err(ScheduleError::NotACalendar)
+        ));
+    }
+}
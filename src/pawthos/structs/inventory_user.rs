@@ -67,6 +67,10 @@ pub struct InventoryUser {
     #[serde(default)]
     pub gifts_received: u32,
 
+    /// Incremented each time this user's `/pay` to another member succeeds.
+    #[serde(default)]
+    pub transfers_sent: u32,
+
     /// Lifetime count of `/shop buy lootbox` invocations that actually pulled
     /// a new badge (excludes duplicate-salvage outcomes? — see Phase 8).
     #[serde(default)]
@@ -76,7 +80,8 @@ pub struct InventoryUser {
     #[serde(default)]
     pub faucet_claims: u32,
 
-    /// Running total of tabs spent across all shop purchases and gift fees.
+    /// Running total of tabs spent across all shop purchases, gift fees and
+    /// `/pay` fees.
     #[serde(default)]
     pub tabs_spent_lifetime: i64,
 
@@ -88,3 +93,38 @@ pub struct InventoryUser {
     #[serde(default)]
     pub unlocked_achievements: Vec<String>,
 }
+
+impl InventoryUser {
+    /// Fold a `legacy` global inventory from before economies were split
+    /// per guild into this one: owned items, unlocks and achievements are
+    /// combined, and each statistic keeps the higher count.
+    pub fn merge(&mut self, legacy: InventoryUser) {
+        fn union(ids: &mut Vec<String>, more: Vec<String>) {
+            for id in more {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        union(&mut self.owned_titles, legacy.owned_titles);
+        union(&mut self.owned_colorways, legacy.owned_colorways);
+        union(&mut self.owned_badges, legacy.owned_badges);
+        union(
+            &mut self.unlocked_achievements,
+            legacy.unlocked_achievements,
+        );
+
+        if self.custom_title.is_none() {
+            self.custom_title = legacy.custom_title;
+        }
+        self.unlocked_custom_title |= legacy.unlocked_custom_title;
+
+        self.messages_sent = self.messages_sent.max(legacy.messages_sent);
+        self.gifts_sent = self.gifts_sent.max(legacy.gifts_sent);
+        self.gifts_received = self.gifts_received.max(legacy.gifts_received);
+        self.transfers_sent = self.transfers_sent.max(legacy.transfers_sent);
+        self.lootboxes_opened = self.lootboxes_opened.max(legacy.lootboxes_opened);
+        self.faucet_claims = self.faucet_claims.max(legacy.faucet_claims);
+        self.tabs_spent_lifetime = self.tabs_spent_lifetime.max(legacy.tabs_spent_lifetime);
+    }
+}
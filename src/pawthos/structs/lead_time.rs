@@ -0,0 +1,117 @@
+//! How long before an event its reminders go out.
+//!
+//! Every [`super::schedule_event::ScheduleEvent`] carries a [`LeadTimes`]
+//! list — say 1 day, 1 hour and 10 minutes before, plus one at the start —
+//! and each entry is a separate reminder. New events take the owner's
+//! defaults, set with `/schedule defaults`.
+
+use crate::pawthos::consts::{SCHEDULE_MAX_LEAD_DAYS, SCHEDULE_MAX_LEAD_TIMES};
+use crate::pawthos::enums::schedule_errors::ScheduleError;
+use crate::pawthos::structs::time_input;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Minutes before an event that a reminder goes out; 0 is at the start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LeadTime(u32);
+
+impl LeadTime {
+    /// The "is happening now!" reminder.
+    pub const AT_START: LeadTime = LeadTime(0);
+
+    pub fn duration(self) -> Duration {
+        Duration::minutes(i64::from(self.0))
+    }
+
+    /// `now`, `0`, or a duration such as `1d`, `2 hours` or `1h30m`.
+    fn parse(input: &str) -> Result<LeadTime, ScheduleError> {
+        let input = input.trim();
+        if matches!(input, "0" | "now" | "start" | "at start") {
+            return Ok(LeadTime::AT_START);
+        }
+        let tokens: Vec<&str> = input.split_whitespace().collect();
+        let duration = time_input::parse_duration(&tokens)
+            .map_err(|_| ScheduleError::InvalidLeadTime(input.to_string()))?;
+        if duration > Duration::days(SCHEDULE_MAX_LEAD_DAYS) {
+            return Err(ScheduleError::InvalidLeadTime(input.to_string()));
+        }
+        u32::try_from(duration.num_minutes())
+            .map(LeadTime)
+            .map_err(|_| ScheduleError::InvalidLeadTime(input.to_string()))
+    }
+}
+
+/// `1 day`, `1 hour 30 minutes`, `at the start`.
+impl fmt::Display for LeadTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == LeadTime::AT_START {
+            return f.write_str("at the start");
+        }
+        let parts = [
+            (self.0 / (24 * 60), "day"),
+            (self.0 / 60 % 24, "hour"),
+            (self.0 % 60, "minute"),
+        ];
+        let text = parts
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, unit)| format!("{n} {unit}{}", if *n == 1 { "" } else { "s" }))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{text} before")
+    }
+}
+
+/// An event's reminders, longest lead first, without repeats.
+///
+/// Never empty: the default, also used by events saved before lead times
+/// existed, is a single reminder at the start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LeadTimes(Vec<LeadTime>);
+
+impl Default for LeadTimes {
+    fn default() -> Self {
+        LeadTimes(vec![LeadTime::AT_START])
+    }
+}
+
+impl LeadTimes {
+    /// Parse a comma-separated list such as `1d, 1h, 10m, now`.
+    pub fn parse(input: &str) -> Result<LeadTimes, ScheduleError> {
+        let mut leads = input
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .map(|part| LeadTime::parse(&part.to_lowercase()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if leads.is_empty() {
+            return Err(ScheduleError::InvalidLeadTime(input.to_string()));
+        }
+        leads.sort_by(|a, b| b.cmp(a));
+        leads.dedup();
+        if leads.len() > SCHEDULE_MAX_LEAD_TIMES {
+            return Err(ScheduleError::TooManyLeadTimes);
+        }
+        Ok(LeadTimes(leads))
+    }
+
+    /// Longest lead first, so the reminders come out in time order.
+    pub fn iter(&self) -> impl Iterator<Item = LeadTime> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Whether this is just the one reminder at the start.
+    pub fn is_default(&self) -> bool {
+        *self == LeadTimes::default()
+    }
+}
+
+/// `1 day before, 10 minutes before, at the start`.
+impl fmt::Display for LeadTimes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let leads = self.0.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        f.write_str(&leads.join(", "))
+    }
+}
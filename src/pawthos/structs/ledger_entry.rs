@@ -0,0 +1,86 @@
+//! One line of a guild's append-only tab ledger.
+
+use crate::pawthos::enums::ledger_reason::LedgerReason;
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude::UserId;
+use serde::{Deserialize, Serialize};
+
+/// A single tab movement in one guild's economy.
+///
+/// Entries are only ever appended, by
+/// [`super::transaction::Transaction::credit`] and friends, in the same
+/// commit as the balance change they describe. They are never edited or
+/// removed, so a member's ledger sums to their current balance (for
+/// everything since the ledger was introduced).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// The member whose balance changed.
+    pub user_id: UserId,
+
+    /// Signed change in tabs: positive for income, negative for spending.
+    pub delta: i64,
+
+    /// What caused the movement.
+    pub reason: LedgerReason,
+
+    /// The other party, if any — a gift's recipient, or the owner who ran
+    /// `!give_tabs`.
+    #[serde(default)]
+    pub counterparty: Option<UserId>,
+
+    /// The shop item (or per-use cosmetic) involved, if any.
+    #[serde(default)]
+    pub item_id: Option<String>,
+
+    /// When the movement was committed.
+    pub at: DateTime<Utc>,
+}
+
+/// The descriptive half of a [`LedgerEntry`], supplied by whoever moves the
+/// tabs. The member, amount and timestamp are filled in by the
+/// [`super::transaction::Transaction`].
+///
+/// ```ignore
+/// tx.debit(guild_id, user_id, cost, TabMemo::new(LedgerReason::Gift).counterparty(to).item(id))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TabMemo {
+    reason: LedgerReason,
+    counterparty: Option<UserId>,
+    item_id: Option<String>,
+}
+
+impl TabMemo {
+    /// A memo with only a reason.
+    pub fn new(reason: LedgerReason) -> Self {
+        Self {
+            reason,
+            counterparty: None,
+            item_id: None,
+        }
+    }
+
+    /// Record the other party of the movement.
+    pub fn counterparty(mut self, user_id: UserId) -> Self {
+        self.counterparty = Some(user_id);
+        self
+    }
+
+    /// Record the item the tabs were spent on (or refunded for).
+    pub fn item(mut self, item_id: impl Into<String>) -> Self {
+        self.item_id = Some(item_id.into());
+        self
+    }
+
+    /// Turn the memo into a full entry timestamped now.
+    pub fn into_entry(self, user_id: UserId, delta: i64) -> LedgerEntry {
+        LedgerEntry {
+            user_id,
+            delta,
+            reason: self.reason,
+            counterparty: self.counterparty,
+            item_id: self.item_id,
+            at: Utc::now(),
+        }
+    }
+}
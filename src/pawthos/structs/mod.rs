@@ -6,24 +6,61 @@
 //!
 //! | Module | Contents |
 //! |---|---|
+//! | [`achievement_rule`] | [`achievement_rule::Rule`] — declarative achievement unlock conditions and progress |
+//! | [`catalog_file`] | Loads and validates the shop catalog file; retires removed items |
 //! | [`data`] | [`data::Data`] — the shared state object injected into every command |
+//! | [`event_bus`] | [`event_bus::EventBus`] — typed broadcast channel of domain events |
+//! | [`group_event`] | [`group_event::GroupEvent`] — where a group event is announced and who RSVP'd |
+//! | [`guild_config`] | [`guild_config::GuildConfig`] — one guild's `/config` settings over the built-in defaults |
+//! | [`guild_db`] | [`guild_db::GuildDB`] — one guild's `HashMap<UserId, GuildUser>` |
+//! | [`guild_dictionary`] | [`guild_dictionary::GuildDictionary`] — one guild's `/vox dict` pronunciation overrides |
+//! | [`guild_permissions`] | [`guild_permissions::GuildPermissions`] — one guild's admin and moderator roles |
+//! | [`guild_user`] | Aggregates the per-guild economy sub-structs into one [`guild_user::GuildUser`] |
+//! | [`ical`] | iCalendar (`.ics`) export and import for `/schedule export` and `/schedule import` |
 //! | [`inventory_user`] | Per-user shop inventory, unlock flags, interaction stats |
+//! | [`lead_time`] | [`lead_time::LeadTimes`] — how long before an event its reminders go out |
+//! | [`ledger_entry`] | [`ledger_entry::LedgerEntry`] — one line of a guild's tab ledger |
 //! | [`mimic`] | A single [`mimic::Mimic`] definition (name + optional avatar) |
 //! | [`mimic_user`] | Per-user mimic state: active mimic, list, auto-mode, channel overrides |
+//! | [`recurrence`] | [`recurrence::Recurrence`] — repeat rules for scheduled events, evaluated in the event's timezone |
+//! | [`reminder_queue`] | [`reminder_queue::ReminderQueue`] — the reminder scheduler's time-ordered queue, with cancel and reschedule by event id |
 //! | [`schedule_event`] | A single [`schedule_event::ScheduleEvent`] with time and timezone |
 //! | [`schedule_user`] | Per-user schedule state: timezone and event list |
+//! | [`shop_catalog`] | [`shop_catalog::Catalog`] in use, `lookup_*` helpers, achievements |
+//! | [`snooze`] | [`snooze::Snooze`] and the snooze / done buttons on reminder DMs |
+//! | [`time_input`] | Resolves free-form times (`in 20 minutes`, `tomorrow 9am`) for `/schedule add` |
+//! | [`transaction`] | [`transaction::Transaction`] — atomic multi-record writes for [`data::Data::transact`] |
 //! | [`user`] | Aggregates all per-user sub-structs into one [`user::User`] |
-//! | [`user_db`] | [`user_db::UserDB`] — the top-level `HashMap<UserId, User>` |
+//! | [`vox_user`] | Per-user `/vox` state: saved DECtalk voice |
+//! | [`user_db`] | [`user_db::UserDB`] — the top-level user map plus per-guild economies |
 //! | [`wallet_user`] | Per-user wallet state: tab balance and daily streak |
 
+pub mod achievement_rule;
+pub mod catalog_file;
 pub mod data;
+pub mod event_bus;
+pub mod group_event;
+pub mod guild_config;
+pub mod guild_db;
+pub mod guild_dictionary;
+pub mod guild_permissions;
+pub mod guild_user;
+pub mod ical;
 pub mod inventory_user;
+pub mod lead_time;
+pub mod ledger_entry;
 pub mod mimic;
 pub mod mimic_user;
 pub mod profile_user;
+pub mod recurrence;
+pub mod reminder_queue;
 pub mod schedule_event;
 pub mod schedule_user;
 pub mod shop_catalog;
+pub mod snooze;
+pub mod time_input;
+pub mod transaction;
 pub mod user;
 pub mod user_db;
+pub mod vox_user;
 pub mod wallet_user;
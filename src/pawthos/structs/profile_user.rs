@@ -14,10 +14,14 @@ use serde::{Deserialize, Serialize};
 ///
 /// 1. If `use_custom_title` is true and
 ///    [`crate::pawthos::structs::inventory_user::InventoryUser::custom_title`]
-///    is `Some`, render that.
-/// 2. Else if `active_title_id` points at a catalog entry, render the
-///    catalog title's display name.
+///    is `Some` (and unlocked), render that.
+/// 2. Else if `active_title_id` points at a catalog entry the user owns,
+///    render the catalog title's display name.
 /// 3. Else render no title.
+///
+/// The equipped IDs are global, but items are owned per guild: `/profile
+/// view` only shows an equipped title, custom title or named colorway in a
+/// guild whose inventory has it, and falls back otherwise.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProfileUser {
     /// A short bio or description set by the user.
@@ -61,10 +65,10 @@ pub struct ProfileUser {
     /// Badge IDs pinned to the user's profile card, in display order.
     ///
     /// Capped at [`crate::pawthos::consts::MAX_ACTIVE_BADGES`] by the
-    /// `/profile set badges` command. IDs may reference entries in either
-    /// [`crate::pawthos::structs::shop_catalog::LOOTBOX_POOL`] (`box_*`
-    /// prefix) or [`crate::pawthos::structs::shop_catalog::ACHIEVEMENTS`]
-    /// (`ach_*` prefix). The resolver in `/profile view` checks both and
+    /// `/profile set badges` command. IDs may reference the catalog's
+    /// lootbox pool or its achievements
+    /// ([`crate::pawthos::structs::shop_catalog::Catalog`]; `box_*` and
+    /// `ach_*` prefixes respectively). The resolver in `/profile view` checks both and
     /// skips any that don't match a live catalog entry.
     #[serde(default)]
     pub active_badge_ids: Vec<String>,
@@ -0,0 +1,413 @@
+//! Repeat rules for scheduled events.
+//!
+//! A [`Recurrence`] turns one [`super::schedule_event::ScheduleEvent`] into a
+//! series. Rules work on local dates in the event's timezone and every
+//! occurrence keeps the same wall-clock time, so a 09:30 standup stays at
+//! 09:30 across DST changes.
+//!
+//! | Rule | Example |
+//! |---|---|
+//! | [`RecurrenceRule::EveryNDays`] | every day, every 3 days |
+//! | [`RecurrenceRule::Weekdays`] | every Mon, Wed, Fri |
+//! | [`RecurrenceRule::NthWeekday`] | the second Tuesday of each month |
+//!
+//! Any rule can end on a given date ([`Recurrence::until`]).
+
+use chrono::{
+    DateTime, Datelike, Days, LocalResult, Months, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The kinds of [`RecurrenceRule`], plus "don't repeat", as a slash choice
+/// for `/schedule repeat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum RepeatKind {
+    #[name = "Don't repeat"]
+    Never,
+    #[name = "Every N days"]
+    Days,
+    #[name = "Weekly on chosen days"]
+    Weekly,
+    #[name = "Monthly on the nth weekday"]
+    Monthly,
+}
+
+/// Which week of the month a [`RecurrenceRule::NthWeekday`] falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+pub enum MonthWeek {
+    #[name = "First"]
+    First,
+    #[name = "Second"]
+    Second,
+    #[name = "Third"]
+    Third,
+    #[name = "Fourth"]
+    Fourth,
+    /// The last one in the month, whether that's the fourth or fifth.
+    #[name = "Last"]
+    Last,
+}
+
+impl MonthWeek {
+    /// The week `date` falls in, counted the way [`Self::date_in`] counts.
+    /// A fifth occurrence counts as [`Self::Last`].
+    pub fn of(date: NaiveDate) -> Self {
+        match (date.day() - 1) / 7 {
+            0 => MonthWeek::First,
+            1 => MonthWeek::Second,
+            2 => MonthWeek::Third,
+            3 if date + Days::new(7) <= last_day_of_month(date) => MonthWeek::Fourth,
+            _ => MonthWeek::Last,
+        }
+    }
+
+    /// This week's `weekday` in the month of `year`/`month`.
+    fn date_in(self, year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+        let n = match self {
+            MonthWeek::First => 1,
+            MonthWeek::Second => 2,
+            MonthWeek::Third => 3,
+            MonthWeek::Fourth => 4,
+            MonthWeek::Last => {
+                return NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5)
+                    .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4));
+            }
+        };
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+    }
+}
+
+/// How the dates of a repeating event are picked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceRule {
+    /// Every `interval` days (1 = daily).
+    EveryNDays { interval: u32 },
+    /// Every week on each of `days`.
+    Weekdays { days: Vec<Weekday> },
+    /// Once a month, on the `week`'th `weekday`.
+    NthWeekday { week: MonthWeek, weekday: Weekday },
+}
+
+impl RecurrenceRule {
+    /// Whether the series can land on `date`.
+    fn matches(&self, date: NaiveDate) -> bool {
+        match self {
+            RecurrenceRule::EveryNDays { .. } => true,
+            RecurrenceRule::Weekdays { days } => days.contains(&date.weekday()),
+            RecurrenceRule::NthWeekday { week, weekday } => {
+                week.date_in(date.year(), date.month(), *weekday) == Some(date)
+            }
+        }
+    }
+
+    /// The first date of the series after `date`, assuming `date` was an
+    /// occurrence.
+    fn next_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            RecurrenceRule::EveryNDays { interval } => {
+                date.checked_add_days(Days::new(u64::from((*interval).max(1))))
+            }
+            RecurrenceRule::Weekdays { days } => (1..=7)
+                .filter_map(|n| date.checked_add_days(Days::new(n)))
+                .find(|d| days.contains(&d.weekday())),
+            RecurrenceRule::NthWeekday { week, weekday } => {
+                let month_start = date.with_day(1)?;
+                (0..=12)
+                    .filter_map(|n| month_start.checked_add_months(Months::new(n)))
+                    .filter_map(|m| week.date_in(m.year(), m.month(), *weekday))
+                    .find(|d| *d > date)
+            }
+        }
+    }
+}
+
+/// `every day`, `every Mon, Wed`, `the last Fri of each month`, …
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecurrenceRule::EveryNDays { interval: 1 } => write!(f, "every day"),
+            RecurrenceRule::EveryNDays { interval } => write!(f, "every {interval} days"),
+            RecurrenceRule::Weekdays { days } => {
+                let days = days.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+                write!(f, "every {}", days.join(", "))
+            }
+            RecurrenceRule::NthWeekday { week, weekday } => {
+                write!(
+                    f,
+                    "the {} {weekday} of each month",
+                    week.to_string().to_lowercase()
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for MonthWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(poise::ChoiceParameter::name(self))
+    }
+}
+
+/// A repeat rule plus the wall-clock time and optional last date of the
+/// series.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recurrence {
+    pub rule: RecurrenceRule,
+
+    /// Local time of day of every occurrence. Kept separately from the
+    /// event's `when` so a time pushed forward by a DST gap on one date
+    /// snaps back on the next.
+    pub time: NaiveTime,
+
+    /// Last local date an occurrence may fall on, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+}
+
+impl Recurrence {
+    /// The first date on or after `date` that the rule lands on, or `None`
+    /// if that's past [`Self::until`].
+    pub fn first_on_or_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let first = if self.rule.matches(date) {
+            Some(date)
+        } else {
+            self.rule.next_date(date)
+        };
+        first.filter(|d| self.until.is_none_or(|until| *d <= until))
+    }
+
+    /// The first occurrence after `now` of a series starting on `start`
+    /// (a local date in `tz`). `None` if the series ends first.
+    pub fn first_after(
+        &self,
+        start: NaiveDate,
+        now: DateTime<Utc>,
+        tz: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let first = self
+            .first_on_or_after(start)
+            .and_then(|d| localise(d, self.time, tz))?;
+        if first > now {
+            Some(first)
+        } else {
+            self.next_after(first, now, tz)
+        }
+    }
+
+    /// The first occurrence strictly after `after`, with `current` (an
+    /// occurrence at or before `after`) as the starting point. `None` once
+    /// the series has ended.
+    pub fn next_after(
+        &self,
+        current: DateTime<Utc>,
+        after: DateTime<Utc>,
+        tz: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let mut date = current.with_timezone(&tz).date_naive();
+        loop {
+            date = self.rule.next_date(date)?;
+            if self.until.is_some_and(|until| date > until) {
+                return None;
+            }
+            if let Some(when) = localise(date, self.time, tz)
+                && when > after
+            {
+                return Some(when);
+            }
+        }
+    }
+}
+
+/// `… (every Mon, Wed until 2026-12-01)`-style suffix used in schedule
+/// listings.
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.rule)?;
+        if let Some(until) = self.until {
+            write!(f, " until {until}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `date` at `time` in `tz`, as UTC.
+///
+/// An ambiguous time (the repeated hour when clocks go back) takes the
+/// earlier instant; a time in a DST gap moves forward an hour so the
+/// reminder still fires that day.
+pub fn localise(date: NaiveDate, time: NaiveTime, tz: Tz) -> Option<DateTime<Utc>> {
+    let naive = date.and_time(time);
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => Some(dt.to_utc()),
+        LocalResult::None => tz
+            .from_local_datetime(&(naive + chrono::Duration::hours(1)))
+            .earliest()
+            .map(|dt| dt.to_utc()),
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1)
+        .and_then(|d| d.checked_add_months(Months::new(1)))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use chrono_tz::Europe::London;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+    }
+
+    fn in_london(when: DateTime<Utc>) -> String {
+        when.with_timezone(&London)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    fn recurrence(rule: RecurrenceRule, at: &str) -> Recurrence {
+        Recurrence {
+            rule,
+            time: time(at),
+            until: None,
+        }
+    }
+
+    #[test]
+    fn weekly_keeps_local_time_across_dst() {
+        let weekly = recurrence(
+            RecurrenceRule::Weekdays {
+                days: vec![Weekday::Mon],
+            },
+            "09:30",
+        );
+        // Clocks go forward on Sunday 2026-03-29 and back on 2026-10-25.
+        let before = localise(date("2026-03-23"), weekly.time, London).unwrap();
+        assert_eq!(before, utc("2026-03-23 09:30"));
+        let after = weekly.next_after(before, before, London).unwrap();
+        assert_eq!(after, utc("2026-03-30 08:30"));
+        assert_eq!(in_london(after), "2026-03-30 09:30");
+
+        let autumn = localise(date("2026-10-19"), weekly.time, London).unwrap();
+        let next = weekly.next_after(autumn, autumn, London).unwrap();
+        assert_eq!(in_london(next), "2026-10-26 09:30");
+        assert_eq!(next, utc("2026-10-26 09:30"));
+    }
+
+    #[test]
+    fn last_weekday_in_four_and_five_week_months() {
+        let last_friday = recurrence(
+            RecurrenceRule::NthWeekday {
+                week: MonthWeek::Last,
+                weekday: Weekday::Fri,
+            },
+            "18:00",
+        );
+        // May 2026 has five Fridays, June four.
+        assert_eq!(
+            last_friday.first_on_or_after(date("2026-05-01")),
+            Some(date("2026-05-29"))
+        );
+        let may = localise(date("2026-05-29"), last_friday.time, London).unwrap();
+        let june = last_friday.next_after(may, may, London).unwrap();
+        assert_eq!(in_london(june), "2026-06-26 18:00");
+        let july = last_friday.next_after(june, june, London).unwrap();
+        assert_eq!(in_london(july), "2026-07-31 18:00");
+    }
+
+    #[test]
+    fn month_week_of_the_fourth_week() {
+        // June 2026 has 30 days: a 22nd-23rd weekday recurs on the 29th-30th.
+        for (day, week) in [
+            ("2026-06-21", MonthWeek::Third),
+            ("2026-06-22", MonthWeek::Fourth),
+            ("2026-06-23", MonthWeek::Fourth),
+            ("2026-06-24", MonthWeek::Last),
+            ("2026-06-28", MonthWeek::Last),
+            ("2026-06-29", MonthWeek::Last),
+            ("2026-02-22", MonthWeek::Last),
+            ("2024-02-22", MonthWeek::Fourth),
+        ] {
+            assert_eq!(MonthWeek::of(date(day)), week, "{day}");
+        }
+
+        // Whatever week a date is called, that week's weekday is the date.
+        for month in ["2026-02-01", "2024-02-01", "2026-06-01", "2026-05-01"] {
+            let first = date(month);
+            for d in first.iter_days().take_while(|d| d.month() == first.month()) {
+                let week = MonthWeek::of(d);
+                assert_eq!(week.date_in(d.year(), d.month(), d.weekday()), Some(d));
+            }
+        }
+    }
+
+    #[test]
+    fn until_is_inclusive() {
+        let daily = Recurrence {
+            until: Some(date("2026-05-03")),
+            ..recurrence(RecurrenceRule::EveryNDays { interval: 1 }, "09:00")
+        };
+        assert_eq!(
+            daily.first_on_or_after(date("2026-05-03")),
+            Some(date("2026-05-03"))
+        );
+        assert_eq!(daily.first_on_or_after(date("2026-05-04")), None);
+
+        let second = localise(date("2026-05-02"), daily.time, London).unwrap();
+        let last = daily.next_after(second, second, London).unwrap();
+        assert_eq!(in_london(last), "2026-05-03 09:00");
+        assert_eq!(daily.next_after(last, last, London), None);
+    }
+
+    #[test]
+    fn first_after_skips_past_occurrences() {
+        let every_three_days = recurrence(RecurrenceRule::EveryNDays { interval: 3 }, "12:00");
+        let first = every_three_days
+            .first_after(date("2026-05-01"), utc("2026-05-05 12:00"), London)
+            .unwrap();
+        assert_eq!(in_london(first), "2026-05-07 12:00");
+
+        let future = every_three_days
+            .first_after(date("2026-06-01"), utc("2026-05-05 12:00"), London)
+            .unwrap();
+        assert_eq!(in_london(future), "2026-06-01 12:00");
+    }
+
+    #[test]
+    fn localise_across_dst() {
+        // The gap: 01:30 doesn't exist on 2026-03-29, so it runs at 02:30.
+        assert_eq!(
+            localise(date("2026-03-29"), time("01:30"), London),
+            Some(utc("2026-03-29 01:30"))
+        );
+        // The overlap: 01:30 happens twice on 2026-10-25; take the first.
+        assert_eq!(
+            localise(date("2026-10-25"), time("01:30"), London),
+            Some(utc("2026-10-25 00:30"))
+        );
+
+        // A daily 01:30 snaps back after the gap.
+        let daily = recurrence(RecurrenceRule::EveryNDays { interval: 1 }, "01:30");
+        let gap = localise(date("2026-03-29"), daily.time, London).unwrap();
+        assert_eq!(in_london(gap), "2026-03-29 02:30");
+        let next = daily.next_after(gap, gap, London).unwrap();
+        assert_eq!(in_london(next), "2026-03-30 01:30");
+    }
+}
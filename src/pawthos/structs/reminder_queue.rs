@@ -0,0 +1,185 @@
+//! The reminder scheduler's time-ordered queue.
+//!
+//! One queue, drained by a single task in [`crate::framework`], holds the
+//! next unsent reminder of every event. Timers are keyed by owner and
+//! [`EventId`], so an event has at most one: scheduling it again moves the
+//! timer, and deleting it cancels the timer outright.
+
+use crate::pawthos::structs::schedule_event::{EventId, ScheduleEvent};
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude::UserId;
+use std::collections::{BTreeMap, HashMap};
+
+/// Identifies one event's timer.
+type TimerKey = (UserId, EventId);
+
+/// Queued reminders, earliest first.
+#[derive(Debug, Default)]
+pub struct ReminderQueue {
+    /// Events by the time their next reminder goes out. The key breaks ties
+    /// between events due at the same moment.
+    by_time: BTreeMap<(DateTime<Utc>, TimerKey), ScheduleEvent>,
+
+    /// When each queued event is due, to find its entry in `by_time`.
+    due_at: HashMap<TimerKey, DateTime<Utc>>,
+}
+
+impl ReminderQueue {
+    /// Queue the next unsent reminder of `event`, replacing its current
+    /// timer. An event with nothing left to send is only removed.
+    pub fn schedule(&mut self, user_id: UserId, event: ScheduleEvent) {
+        let key = (user_id, event.id);
+        self.cancel(key.0, key.1);
+        if let Some((_, at)) = event.next_reminder() {
+            self.due_at.insert(key, at);
+            self.by_time.insert((at, key), event);
+        }
+    }
+
+    /// Drop the timer of `user_id`'s event `id`. Returns whether it had one.
+    pub fn cancel(&mut self, user_id: UserId, id: EventId) -> bool {
+        let key = (user_id, id);
+        match self.due_at.remove(&key) {
+            Some(at) => self.by_time.remove(&(at, key)).is_some(),
+            None => false,
+        }
+    }
+
+    /// When the earliest queued reminder goes out.
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        self.by_time.keys().next().map(|(at, _)| *at)
+    }
+
+    /// Take every event whose reminder is due at or before `now`, earliest
+    /// first.
+    pub fn pop_due(&mut self, now: DateTime<Utc>) -> Vec<(UserId, ScheduleEvent)> {
+        let mut due = Vec::new();
+        while let Some(entry) = self.by_time.first_entry() {
+            let (at, key) = *entry.key();
+            if at > now {
+                break;
+            }
+            let event = entry.remove();
+            self.due_at.remove(&key);
+            due.push((key.0, event));
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pawthos::structs::lead_time::LeadTimes;
+    use chrono::NaiveDateTime;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+    }
+
+    /// A one-off event `id`, reminded only at its start.
+    fn event(id: u64, when: &str) -> ScheduleEvent {
+        ScheduleEvent {
+            id: id.to_string().parse().unwrap(),
+            name: format!("event {id}"),
+            when: utc(when),
+            tz: chrono_tz::UTC,
+            recurrence: None,
+            group: None,
+            lead_times: LeadTimes::default(),
+            reminded: None,
+            snooze: None,
+            done: None,
+        }
+    }
+
+    /// The owners and names of `due`, in order.
+    fn names(due: &[(UserId, ScheduleEvent)]) -> Vec<(u64, &str)> {
+        due.iter()
+            .map(|(user_id, event)| (user_id.get(), event.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn scheduling_again_moves_the_timer() {
+        let mut queue = ReminderQueue::default();
+        let alice = UserId::new(1);
+        queue.schedule(alice, event(7, "2026-01-01 09:00"));
+        queue.schedule(alice, event(7, "2026-01-01 12:00"));
+
+        assert_eq!(queue.next_due(), Some(utc("2026-01-01 12:00")));
+        assert!(queue.pop_due(utc("2026-01-01 11:00")).is_empty());
+        assert_eq!(queue.pop_due(utc("2026-01-01 12:00")).len(), 1);
+        assert_eq!(queue.next_due(), None);
+    }
+
+    #[test]
+    fn the_same_id_for_another_user_is_another_timer() {
+        let mut queue = ReminderQueue::default();
+        queue.schedule(UserId::new(1), event(7, "2026-01-01 09:00"));
+        queue.schedule(UserId::new(2), event(7, "2026-01-01 12:00"));
+
+        assert_eq!(queue.pop_due(utc("2026-01-01 12:00")).len(), 2);
+    }
+
+    #[test]
+    fn nothing_left_to_send_only_removes() {
+        let mut queue = ReminderQueue::default();
+        let alice = UserId::new(1);
+        queue.schedule(alice, event(7, "2026-01-01 09:00"));
+        let mut sent = event(7, "2026-01-01 09:00");
+        sent.reminded = Some(sent.when);
+        queue.schedule(alice, sent);
+
+        assert_eq!(queue.next_due(), None);
+    }
+
+    #[test]
+    fn cancel_removes_the_timer() {
+        let mut queue = ReminderQueue::default();
+        let alice = UserId::new(1);
+        let id = "7".parse().unwrap();
+        queue.schedule(alice, event(7, "2026-01-01 09:00"));
+
+        assert!(!queue.cancel(UserId::new(2), id));
+        assert!(queue.cancel(alice, id));
+        assert!(!queue.cancel(alice, id));
+        assert_eq!(queue.next_due(), None);
+        assert!(queue.pop_due(utc("2027-01-01 00:00")).is_empty());
+    }
+
+    #[test]
+    fn pop_due_takes_due_entries_in_time_order() {
+        let mut queue = ReminderQueue::default();
+        let (alice, bob) = (UserId::new(1), UserId::new(2));
+        queue.schedule(bob, event(1, "2026-01-01 10:00"));
+        queue.schedule(alice, event(2, "2026-01-01 11:00"));
+        queue.schedule(alice, event(3, "2026-01-01 09:00"));
+        queue.schedule(bob, event(4, "2026-01-01 12:00"));
+
+        let due = queue.pop_due(utc("2026-01-01 11:00"));
+        assert_eq!(
+            names(&due),
+            [(1, "event 3"), (2, "event 1"), (1, "event 2")]
+        );
+        assert_eq!(queue.next_due(), Some(utc("2026-01-01 12:00")));
+        assert!(queue.pop_due(utc("2026-01-01 11:59")).is_empty());
+    }
+
+    #[test]
+    fn ties_go_by_user_then_event() {
+        let mut queue = ReminderQueue::default();
+        let (alice, bob) = (UserId::new(1), UserId::new(2));
+        queue.schedule(bob, event(1, "2026-01-01 09:00"));
+        queue.schedule(alice, event(9, "2026-01-01 09:00"));
+        queue.schedule(alice, event(5, "2026-01-01 09:00"));
+
+        let due = queue.pop_due(utc("2026-01-01 09:00"));
+        assert_eq!(
+            names(&due),
+            [(1, "event 5"), (1, "event 9"), (2, "event 1")]
+        );
+    }
+}
@@ -1,20 +1,71 @@
 //! A single scheduled reminder event.
 
 use std::fmt;
+use std::str::FromStr;
 
+use crate::pawthos::structs::{
+    group_event::GroupEvent,
+    lead_time::{LeadTime, LeadTimes},
+    recurrence::Recurrence,
+    snooze::Snooze,
+};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Identifies one of a user's events, whatever its name.
+///
+/// Events saved before IDs existed get a fresh one each time they're
+/// loaded, until they're next saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EventId(u64);
+
+impl EventId {
+    pub fn random() -> Self {
+        EventId(rand::random())
+    }
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for EventId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(EventId)
+    }
+}
+
+/// Which of an event's reminders is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reminder {
+    /// One of its lead times, for the current occurrence.
+    Lead(LeadTime),
+    /// A snooze asked for from an earlier reminder.
+    Snooze(Snooze),
+}
+
 /// A named event with an absolute UTC timestamp and the user's timezone.
 ///
 /// Events are stored in UTC so they survive the user changing their timezone,
 /// but the timezone is kept alongside so reminders and the list display can
 /// convert back to local time.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScheduleEvent {
+    /// Stable ID used to cancel and reschedule the event's reminders, and
+    /// by the autocompletes that pick it.
+    #[serde(default = "EventId::random")]
+    pub id: EventId,
+
     /// Human-readable name of the event (chosen by the user).
     pub name: String,
 
-    /// Absolute time of the event in UTC.
+    /// Absolute time of the event in UTC. For a repeating event, the next
+    /// occurrence.
     ///
     /// The schedule reminder task sleeps until `when` and then sends the user
     /// a DM.
@@ -23,16 +74,103 @@ pub struct ScheduleEvent {
     /// The user's timezone at the time the event was created.
     ///
     /// Used to render the event time in the user's local time zone when
-    /// listing events or sending reminders.
+    /// listing events or sending reminders, and to work out the dates of a
+    /// repeating event.
     pub tz: chrono_tz::Tz,
+
+    /// How the event repeats, or `None` for a one-off. Events saved before
+    /// repeats existed are one-offs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+
+    /// The channel, role and RSVPs of a group event, or `None` for a
+    /// private reminder that only DMs its owner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<GroupEvent>,
+
+    /// When reminders go out relative to `when`, each one separately.
+    #[serde(default)]
+    pub lead_times: LeadTimes,
+
+    /// Send time of the latest reminder already sent for the current
+    /// occurrence. Persisted so a restart doesn't send it again; cleared
+    /// whenever the occurrence moves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminded: Option<DateTime<Utc>>,
+
+    /// A snoozed reminder still to come, set from a reminder DM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snooze: Option<Snooze>,
+
+    /// The latest occurrence marked done from a reminder DM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub done: Option<DateTime<Utc>>,
+}
+
+impl ScheduleEvent {
+    /// The first occurrence after `now`, or `None` for a one-off or a series
+    /// that has ended.
+    pub fn next_occurrence(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.recurrence
+            .as_ref()?
+            .next_after(self.when, now.max(self.when), self.tz)
+    }
+
+    /// The first reminder of the current occurrence not yet sent: its lead
+    /// time and when it goes out.
+    pub fn pending_reminder(&self) -> Option<(LeadTime, DateTime<Utc>)> {
+        self.lead_times
+            .iter()
+            .map(|lead| (lead, self.when - lead.duration()))
+            .find(|(_, at)| self.reminded.is_none_or(|sent| *at > sent))
+    }
+
+    /// The next reminder to send, lead time or snooze, and when it goes
+    /// out. This is what the reminder scheduler waits for.
+    pub fn next_reminder(&self) -> Option<(Reminder, DateTime<Utc>)> {
+        let lead = self
+            .pending_reminder()
+            .map(|(lead, at)| (Reminder::Lead(lead), at));
+        let snooze = self.snooze.map(|s| (Reminder::Snooze(s), s.at));
+        match (lead, snooze) {
+            (Some(lead), Some(snooze)) if snooze.1 < lead.1 => Some(snooze),
+            (lead, snooze) => lead.or(snooze),
+        }
+    }
+
+    /// Whether `other` is this event at the same occurrence, with the same
+    /// reminders already sent and snoozed. Anything else — RSVPs, the name, a group
+    /// event's channel — can change without making a queued copy stale.
+    pub fn same_reminder(&self, other: &ScheduleEvent) -> bool {
+        self.id == other.id
+            && self.when == other.when
+            && self.reminded == other.reminded
+            && self.snooze == other.snooze
+    }
 }
 
-/// Formats the event as `"<name> : <local datetime>"`.
+/// Formats the event as `"<name> : <local datetime>"`, followed by
+/// `"(repeats <rule>)"` for a repeating event, `"in <#channel>"` for a
+/// group event, the reminders unless it's just the one at the start, and
+/// any snooze.
 ///
 /// Used by [`super::schedule_user::ScheduleUser::list_events`] to build the
 /// schedule list embed description.
 impl fmt::Display for ScheduleEvent {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} : {}", self.name, self.when.with_timezone(&self.tz))
+        write!(f, "{} : {}", self.name, self.when.with_timezone(&self.tz))?;
+        if let Some(recurrence) = &self.recurrence {
+            write!(f, " (repeats {recurrence})")?;
+        }
+        if let Some(group) = &self.group {
+            write!(f, " in <#{}>", group.channel_id)?;
+        }
+        if !self.lead_times.is_default() {
+            write!(f, " [reminders {}]", self.lead_times)?;
+        }
+        if let Some(snooze) = &self.snooze {
+            write!(f, " (snoozed until {})", snooze.at.with_timezone(&self.tz))?;
+        }
+        Ok(())
     }
 }
@@ -1,10 +1,18 @@
 //! Per-user state for the schedule feature.
 
 use crate::pawthos::{
-    enums::schedule_errors::ScheduleError, structs::schedule_event::ScheduleEvent,
+    enums::schedule_errors::ScheduleError,
+    structs::{
+        group_event::{GroupEvent, Rsvp},
+        lead_time::LeadTimes,
+        recurrence::Recurrence,
+        schedule_event::{EventId, Reminder, ScheduleEvent},
+        snooze::Snooze,
+    },
 };
 use chrono::Utc;
 use chrono_tz::Tz;
+use poise::serenity_prelude::{MessageId, UserId};
 use serde::{Deserialize, Serialize};
 
 /// All schedule-related state for a single user.
@@ -20,23 +28,69 @@ pub struct ScheduleUser {
     #[serde(default)]
     pub timezone: Tz,
 
+    /// Whether the user chose [`Self::timezone`] rather than getting the
+    /// default. See [`Self::home_timezone`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub timezone_set: bool,
+
     /// All upcoming events for this user, sorted ascending by [`ScheduleEvent::when`].
     pub events: Vec<ScheduleEvent>,
+
+    /// Reminders new events get unless they're given their own. Set via
+    /// `/schedule defaults`.
+    #[serde(default)]
+    pub default_lead_times: LeadTimes,
 }
 
 impl ScheduleUser {
     /// Add a new event and keep the list sorted by time.
     ///
     /// `when` must be in UTC. The user's current `timezone` is stored on the
-    /// event so it can be displayed in local time later.
+    /// event so it can be displayed in local time later. The event starts
+    /// out as a one-off; see [`Self::set_recurrence`]. Its reminders are
+    /// `lead_times`, or the user's [`Self::default_lead_times`].
     ///
     /// Returns a clone of the newly created event (needed to enqueue the
     /// reminder task).
-    pub fn add_event(&mut self, name: String, when: chrono::DateTime<Utc>) -> ScheduleEvent {
+    pub fn add_event(
+        &mut self,
+        name: String,
+        when: chrono::DateTime<Utc>,
+        lead_times: Option<LeadTimes>,
+    ) -> ScheduleEvent {
+        self.insert_event(name, when, lead_times, None)
+    }
+
+    /// Like [`Self::add_event`], for a group event announced in a guild
+    /// channel. The caller has already posted the announcement.
+    pub fn add_group_event(
+        &mut self,
+        name: String,
+        when: chrono::DateTime<Utc>,
+        lead_times: Option<LeadTimes>,
+        group: GroupEvent,
+    ) -> ScheduleEvent {
+        self.insert_event(name, when, lead_times, Some(group))
+    }
+
+    fn insert_event(
+        &mut self,
+        name: String,
+        when: chrono::DateTime<Utc>,
+        lead_times: Option<LeadTimes>,
+        group: Option<GroupEvent>,
+    ) -> ScheduleEvent {
         let event = ScheduleEvent {
+            id: EventId::random(),
             name,
             when,
             tz: self.timezone,
+            recurrence: None,
+            group,
+            lead_times: lead_times.unwrap_or_else(|| self.default_lead_times.clone()),
+            reminded: None,
+            snooze: None,
+            done: None,
         };
 
         self.events.push(event.clone());
@@ -44,25 +98,220 @@ impl ScheduleUser {
         event
     }
 
-    /// Remove an event by name and return its name on success.
-    ///
-    /// Returns [`ScheduleError::EventNotFound`] if no event with that name
-    /// exists.
-    pub fn delete_event(&mut self, target: String) -> Result<String, ScheduleError> {
-        let idx = self
+    /// Add an event read from a calendar file, with the user's default
+    /// reminders, unless the schedule already has one with the same name
+    /// and time. Returns the added event.
+    pub fn import_event(&mut self, mut event: ScheduleEvent) -> Option<ScheduleEvent> {
+        if self
             .events
             .iter()
-            .position(|m| m.name == target)
+            .any(|e| e.name == event.name && e.when == event.when)
+        {
+            return None;
+        }
+        event.lead_times = self.default_lead_times.clone();
+        self.events.push(event.clone());
+        self.events.sort_by_key(|e| e.when);
+        Some(event)
+    }
+
+    /// Index of the event `target` picks: its ID (what the autocompletes
+    /// fill in) or, failing that, its name.
+    ///
+    /// Returns [`ScheduleError::EventNotFound`] if neither matches.
+    fn position(&self, target: &str) -> Result<usize, ScheduleError> {
+        self.events
+            .iter()
+            .position(|e| e.id.to_string() == target)
+            .or_else(|| self.events.iter().position(|e| e.name == target))
+            .ok_or(ScheduleError::EventNotFound)
+    }
+
+    /// Remove the event `target` picks (by ID or name) and return it.
+    pub fn delete_event(&mut self, target: &str) -> Result<ScheduleEvent, ScheduleError> {
+        let idx = self.position(target)?;
+        Ok(self.events.remove(idx))
+    }
+
+    /// A clone of the event `target` picks (by ID or name).
+    pub fn event(&self, target: &str) -> Result<ScheduleEvent, ScheduleError> {
+        Ok(self.events[self.position(target)?].clone())
+    }
+
+    /// Like [`Self::event`], failing with [`ScheduleError::NotAGroupEvent`]
+    /// for a private reminder.
+    pub fn group_event(&self, target: &str) -> Result<ScheduleEvent, ScheduleError> {
+        let event = self.event(target)?;
+        match event.group {
+            Some(_) => Ok(event),
+            None => Err(ScheduleError::NotAGroupEvent),
+        }
+    }
+
+    /// Store an edited copy of an event (as returned by [`Self::event`]) in
+    /// place of the event with the same ID, and return what was stored.
+    ///
+    /// A group event's RSVPs are taken from the stored event rather than
+    /// `edited`, so anyone who joined or left while the edit was being made
+    /// isn't lost. Moving the event makes all its reminders due again.
+    pub fn replace_event(
+        &mut self,
+        mut edited: ScheduleEvent,
+    ) -> Result<ScheduleEvent, ScheduleError> {
+        let stored = self
+            .events
+            .iter_mut()
+            .find(|e| e.id == edited.id)
             .ok_or(ScheduleError::EventNotFound)?;
+        if let (Some(stored), Some(edited)) = (&stored.group, &mut edited.group) {
+            edited.rsvps = stored.rsvps.clone();
+        }
+        edited.reminded = if edited.when == stored.when {
+            stored.reminded
+        } else {
+            None
+        };
+        *stored = edited.clone();
+        self.events.sort_by_key(|e| e.when);
+        Ok(edited)
+    }
 
-        let removed = self.events.remove(idx);
-        Ok(removed.name)
+    /// Add `user` to, or take them off, the RSVP list of the group event
+    /// announced in `announcement`. Returns the updated event.
+    pub fn rsvp(
+        &mut self,
+        announcement: MessageId,
+        user: UserId,
+        rsvp: Rsvp,
+    ) -> Result<ScheduleEvent, ScheduleError> {
+        let event = self
+            .events
+            .iter_mut()
+            .find(|e| {
+                e.group
+                    .as_ref()
+                    .is_some_and(|g| g.announcement == announcement)
+            })
+            .ok_or(ScheduleError::EventNotFound)?;
+        let rsvps = &mut event.group.as_mut().unwrap().rsvps;
+        match (rsvp, rsvps.iter().position(|id| *id == user)) {
+            (Rsvp::Join, None) => rsvps.push(user),
+            (Rsvp::Join, Some(_)) => return Err(ScheduleError::AlreadyGoing),
+            (Rsvp::Leave, Some(idx)) => {
+                rsvps.remove(idx);
+            }
+            (Rsvp::Leave, None) => return Err(ScheduleError::NotGoing),
+        }
+        Ok(event.clone())
+    }
+
+    /// Snooze the reminder for `occurrence` of event `id` until `at`,
+    /// replacing any earlier snooze. Returns the updated event.
+    pub fn snooze(
+        &mut self,
+        id: EventId,
+        occurrence: chrono::DateTime<Utc>,
+        at: chrono::DateTime<Utc>,
+    ) -> Result<ScheduleEvent, ScheduleError> {
+        let event = self.event_by_id(id)?;
+        if event.done == Some(occurrence) {
+            return Err(ScheduleError::AlreadyDone);
+        }
+        event.snooze = Some(Snooze { at, occurrence });
+        Ok(event.clone())
+    }
+
+    /// Mark `occurrence` of event `id` done: drop its snooze and, if it's
+    /// the current occurrence, its remaining reminders. A repeating event
+    /// moves on to its next occurrence. Returns the updated event.
+    pub fn complete(
+        &mut self,
+        id: EventId,
+        occurrence: chrono::DateTime<Utc>,
+        now: chrono::DateTime<Utc>,
+    ) -> Result<ScheduleEvent, ScheduleError> {
+        let event = self.event_by_id(id)?;
+        if event.done == Some(occurrence) {
+            return Err(ScheduleError::AlreadyDone);
+        }
+        event.done = Some(occurrence);
+        if event.snooze.is_some_and(|s| s.occurrence == occurrence) {
+            event.snooze = None;
+        }
+        if event.when == occurrence {
+            event.reminded = event
+                .lead_times
+                .iter()
+                .last()
+                .map(|l| event.when - l.duration());
+            if let Some(next) = event.next_occurrence(now) {
+                event.when = next;
+                event.reminded = None;
+            }
+        }
+        let event = event.clone();
+        self.events.sort_by_key(|e| e.when);
+        Ok(event)
+    }
+
+    fn event_by_id(&mut self, id: EventId) -> Result<&mut ScheduleEvent, ScheduleError> {
+        self.events
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or(ScheduleError::EventNotFound)
+    }
+
+    /// Set or clear the repeat of the event `target` picks (by ID or name),
+    /// and return the updated event.
+    ///
+    /// `rule` receives the event's current local date and time (the start of
+    /// the series) and builds the [`Recurrence`], or `None` to make it a
+    /// one-off again. A series whose start is already past joins at its
+    /// next occurrence after `now`.
+    pub fn set_recurrence<F>(
+        &mut self,
+        target: &str,
+        now: chrono::DateTime<Utc>,
+        rule: F,
+    ) -> Result<ScheduleEvent, ScheduleError>
+    where
+        F: FnOnce(chrono::NaiveDateTime) -> Result<Option<Recurrence>, ScheduleError>,
+    {
+        let idx = self.position(target)?;
+        let event = &mut self.events[idx];
+        let start = event.when.with_timezone(&event.tz).naive_local();
+        let recurrence = rule(start)?;
+
+        if let Some(rec) = &recurrence {
+            let when = rec
+                .first_after(start.date(), now, event.tz)
+                .ok_or(ScheduleError::EndsBeforeStart)?;
+            if when != event.when {
+                event.when = when;
+                event.reminded = None;
+            }
+        }
+        event.recurrence = recurrence;
+
+        let event = event.clone();
+        self.events.sort_by_key(|e| e.when);
+        Ok(event)
     }
 
     /// Update the user's timezone. Does not retroactively adjust stored event
     /// times (they remain in UTC and are re-displayed in the new timezone).
     pub fn set_timezone(&mut self, tz: Tz) {
         self.timezone = tz;
+        self.timezone_set = true;
+    }
+
+    /// The timezone the user chose with `/schedule set_tz`, if any, for
+    /// showing times to other people.
+    ///
+    /// Records saved before [`Self::timezone_set`] existed count as set
+    /// unless they're still on the UTC default.
+    pub fn home_timezone(&self) -> Option<Tz> {
+        (self.timezone_set || self.timezone != Tz::UTC).then_some(self.timezone)
     }
 
     /// Build a newline-separated string of all events for use in an embed
@@ -76,9 +325,83 @@ impl ScheduleUser {
     }
 
     /// Remove all events whose `when` timestamp is in the past (before `now`).
+    /// Repeating events move on to their next occurrence instead, and are
+    /// only removed once the series has ended. Events with a snooze still to
+    /// come are kept.
     ///
     /// Called by `/schedule list` so stale events don't clutter the display.
-    pub fn prune_past_events(&mut self, now: chrono::DateTime<Utc>) {
-        self.events.retain(|e| e.when > now);
+    /// Returns the repeating events that moved on, whose reminders need
+    /// queueing again.
+    pub fn prune_past_events(&mut self, now: chrono::DateTime<Utc>) -> Vec<ScheduleEvent> {
+        let mut advanced = Vec::new();
+        self.events.retain_mut(|e| {
+            if e.when > now || e.snooze.is_some() {
+                return true;
+            }
+            match e.next_occurrence(now) {
+                Some(next) => {
+                    e.when = next;
+                    e.reminded = None;
+                    advanced.push(e.clone());
+                    true
+                }
+                None => false,
+            }
+        });
+        self.events.sort_by_key(|e| e.when);
+        advanced
+    }
+
+    /// Set the reminders new events get by default.
+    pub fn set_default_lead_times(&mut self, lead_times: LeadTimes) {
+        self.default_lead_times = lead_times;
     }
+
+    /// Record that the next reminder of `due` has come round, and work out
+    /// what to queue after it: the occurrence's next reminder, or after its
+    /// last one, the next occurrence of a repeating event. A snooze is used
+    /// up once it fires.
+    ///
+    /// `due` is the copy the scheduler has been holding; if the stored event
+    /// is no longer that occurrence (deleted, moved, or already moved on) or
+    /// that reminder was already sent, the reminder is stale and
+    /// `None` is returned. Otherwise the stored copy is returned, so a group
+    /// event is announced with its current channel and RSVPs.
+    pub fn fire(&mut self, due: &ScheduleEvent, now: chrono::DateTime<Utc>) -> Option<Fired> {
+        let event = self.events.iter_mut().find(|e| e.same_reminder(due))?;
+        let (reminder, at) = event.next_reminder()?;
+        match reminder {
+            Reminder::Lead(_) => event.reminded = Some(at),
+            Reminder::Snooze(_) => event.snooze = None,
+        }
+        let sent = event.clone();
+
+        if event.pending_reminder().is_none()
+            && let Some(when) = event.next_occurrence(now)
+        {
+            event.when = when;
+            event.reminded = None;
+        }
+        // A one-off, or the end of a series, stays in the list until
+        // `/schedule list` prunes it.
+        let next = event.next_reminder().is_some().then(|| event.clone());
+        self.events.sort_by_key(|e| e.when);
+        Some(Fired {
+            event: sent,
+            reminder,
+            next,
+        })
+    }
+}
+
+/// A reminder [`ScheduleUser::fire`] found due.
+#[derive(Debug)]
+pub struct Fired {
+    /// The stored event, as of this reminder.
+    pub event: ScheduleEvent,
+    /// Which of its reminders this is.
+    pub reminder: Reminder,
+    /// What to queue next: the same occurrence's next reminder, the next
+    /// occurrence, or nothing once the event is over.
+    pub next: Option<ScheduleEvent>,
 }
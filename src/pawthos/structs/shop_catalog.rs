@@ -1,19 +1,34 @@
-//! Static shop catalog.
+//! The shop catalog.
 //!
-//! All purchasable items are defined as compile-time constants here. Adding or
-//! removing an item is a code change — the on-disk user data only stores item
-//! IDs, so catalog reshuffles cannot corrupt existing inventories.
+//! Titles, named colorways, lootbox badges, unlocks and achievements are
+//! data, not code: they are read from the catalog file (`catalog.toml` by
+//! default, see [`super::catalog_file`]) at startup and can be swapped out at
+//! runtime with `!reload_catalog`. The loaded [`Catalog`] is held behind a process-wide
+//! lock; [`current`] hands out a cheap snapshot, and the `lookup_*` helpers
+//! search the snapshot by ID. The on-disk user data only stores item IDs, so
+//! catalog reshuffles cannot corrupt existing inventories.
+//!
+//! Achievement unlock conditions are small declarative rules (see
+//! [`super::achievement_rule`]), so they live in the same file.
 //!
 //! # Layout
 //!
-//! Items are split across typed tables so each category can carry the extra
+//! Items are split across typed lists so each category can carry the extra
 //! data it needs (colorways carry hex values, lootbox badges carry emoji
-//! strings). All tables share the common [`ShopItem`] header, so [`lookup`]
-//! can find any item by ID regardless of category.
+//! strings). All lists share the common [`ShopItem`] header.
 //!
 //! Banners are user-supplied (URL or attachment) only — there is no curated
 //! banner catalog. See `/profile set banner` for the per-set charge model.
 //!
+//! # Retired items
+//!
+//! An item deleted from the catalog file is not forgotten: it stays in the
+//! [`Catalog`] with [`ShopItem::retired`] set, so members who own it still
+//! see its name and can keep it equipped. Retired items are left out of
+//! `/shop browse`, autocomplete, purchases, gifts and lootbox pulls.
+//! Achievements retire the same way: no longer awarded, but earned badges
+//! keep rendering.
+//!
 //! # ID conventions
 //!
 //! - `title_*`         — titles
@@ -24,15 +39,18 @@
 //!
 //! Namespacing by prefix lets `/shop inventory` partition a user's
 //! `owned_badges` vec into lootbox vs. achievement sections without storing
-//! a separate tag.
+//! a separate tag. The loader enforces the prefixes.
 
-use crate::pawthos::consts::LOOTBOX_COST;
-use crate::pawthos::structs::inventory_user::InventoryUser;
-use crate::pawthos::structs::wallet_user::WalletUser;
+use crate::pawthos::structs::achievement_rule::Rule;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock, RwLock};
 
 /// Rarity tier of a shop item. Drives lootbox roll probabilities and
 /// optional UI affordances (colour tags, sort order).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Written in lowercase in the catalog file (`rarity = "rare"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Rarity {
     Common,
     Uncommon,
@@ -40,274 +58,194 @@ pub enum Rarity {
     Legendary,
 }
 
+impl Rarity {
+    /// Parse the lowercase name used in the catalog file.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "common" => Some(Rarity::Common),
+            "uncommon" => Some(Rarity::Uncommon),
+            "rare" => Some(Rarity::Rare),
+            "legendary" => Some(Rarity::Legendary),
+            _ => None,
+        }
+    }
+
+    /// The lowercase name used in the catalog file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::Legendary => "legendary",
+        }
+    }
+}
+
 /// The common header every catalog entry carries.
 ///
 /// Typed definition structs ([`TitleDef`], [`ColorwayDef`], etc.) embed this
 /// plus their category-specific payload. The category itself is implicit in
-/// which static array the entry lives in; the lookup helpers
-/// ([`lookup_title`], [`lookup_colorway`], [`lookup_badge`]) preserve that
-/// distinction.
-#[derive(Debug, Clone, Copy)]
+/// which list the entry lives in; the lookup helpers ([`lookup_title`],
+/// [`lookup_colorway`], [`lookup_badge`]) preserve that distinction.
+#[derive(Debug, Clone)]
 pub struct ShopItem {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
+    pub id: String,
+    pub name: String,
+    pub description: String,
     pub cost: i64,
     pub rarity: Rarity,
+    /// `true` once the item has been removed from the catalog file. See the
+    /// module docs.
+    pub retired: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct TitleDef {
     pub item: ShopItem,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ColorwayDef {
     pub item: ShopItem,
     /// 24-bit RGB colour value (`0xRRGGBB`).
     pub hex: u32,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct BadgeDef {
     pub item: ShopItem,
     /// Discord emoji string — custom (`<:name:id>`) or unicode (`🔥`).
-    pub emoji: &'static str,
+    pub emoji: String,
 }
 
 // ---------------------------------------------------------------------------
-// Tables
+// Catalog
 // ---------------------------------------------------------------------------
 
-/// Purchasable titles — 10 tabs each.
-pub const TITLES: &[TitleDef] = &[
-    TitleDef { item: ShopItem {
-        id: "title_tab_hoarder", name: "Tab Hoarder",
-        description: "For those who save every last tab.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_early_adopter", name: "Early Adopter",
-        description: "Here before the merch drops.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_certified_gremlin", name: "Certified Gremlin",
-        description: "Your behaviour is noted.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_caffeine_dependent", name: "Caffeine Dependent",
-        description: "Powered by legal stimulants.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_professional_lurker", name: "Professional Lurker",
-        description: "Reads everything. Says nothing.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_night_owl", name: "Night Owl",
-        description: "Active when the sun isn't.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_early_bird", name: "Early Bird",
-        description: "Up before the standups.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-    TitleDef { item: ShopItem {
-        id: "title_keyboard_warrior", name: "Keyboard Warrior",
-        description: "Typing for the cause.",
-        cost: 10, rarity: Rarity::Common,
-    }},
-];
+/// Every catalog item, retired ones included.
+///
+/// Built by [`super::catalog_file::load`]. The `active_*` iterators skip
+/// retired items and are what anything offering items for sale should use.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    /// Purchasable titles.
+    pub titles: Vec<TitleDef>,
+    /// Named colorways, each paired with an RGB hex value.
+    pub colorways: Vec<ColorwayDef>,
+    /// Lootbox pull pool. The lootbox flow falls back to Common when a rolled
+    /// rarity has no active candidates.
+    pub lootbox_pool: Vec<BadgeDef>,
+    /// One-time paywall unlocks. Only IDs the code knows how to apply are
+    /// accepted (see [`super::catalog_file::KNOWN_UNLOCKS`]).
+    pub unlocks: Vec<ShopItem>,
+    /// Achievements and their unlock rules.
+    pub achievements: Vec<Achievement>,
+}
 
-/// Named colorways — 20 tabs each. Paired with an RGB hex value.
-pub const COLORWAYS: &[ColorwayDef] = &[
-    ColorwayDef { item: ShopItem {
-        id: "colorway_sunset", name: "Sunset",
-        description: "Warm coral red.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0xFF6B6B },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_ocean", name: "Ocean",
-        description: "Deep blue.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0x4A90E2 },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_neon_pink", name: "Neon Pink",
-        description: "Loud and proud.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0xFF1493 },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_midnight", name: "Midnight",
-        description: "Almost black.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0x1A1A3E },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_gold", name: "Gold",
-        description: "Premium yellow.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0xFFD700 },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_lavender", name: "Lavender",
-        description: "Soft purple.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0xB57EDC },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_crimson", name: "Crimson",
-        description: "Classic red.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0xDC143C },
-    ColorwayDef { item: ShopItem {
-        id: "colorway_mint", name: "Mint",
-        description: "Cool green.",
-        cost: 20, rarity: Rarity::Common,
-    }, hex: 0x98D8A1 },
-];
+impl Catalog {
+    /// Titles that can still be bought or gifted.
+    pub fn active_titles(&self) -> impl Iterator<Item = &TitleDef> {
+        self.titles.iter().filter(|t| !t.item.retired)
+    }
 
-/// Lootbox pull pool.
-///
-/// 10 badges total, partitioned by rarity so the distribution from
-/// [`crate::pawthos::consts`]'s `LOOTBOX_CHANCE_*` probabilities has at least
-/// one candidate per tier:
-///
-/// | Rarity | Count | Odds |
-/// |---|---|---|
-/// | Common    | 4 | 60% |
-/// | Uncommon  | 3 | 25% |
-/// | Rare      | 2 | 10% |
-/// | Legendary | 1 | 5%  |
-///
-/// Uses unicode emojis so new servers don't need custom Discord emoji setup.
-/// Grow the pool by appending new entries — the lootbox flow handles any size.
-pub const LOOTBOX_POOL: &[BadgeDef] = &[
-    // --- Common (60%) ----------------------------------------------------
-    BadgeDef {
-        item: ShopItem {
-            id: "box_coffee", name: "Coffee Addict",
-            description: "Fueled by caffeine.",
-            cost: 0, rarity: Rarity::Common,
-        },
-        emoji: "☕",
-    },
-    BadgeDef {
-        item: ShopItem {
-            id: "box_bookworm", name: "Bookworm",
-            description: "Reads the docs.",
-            cost: 0, rarity: Rarity::Common,
-        },
-        emoji: "📖",
-    },
-    BadgeDef {
-        item: ShopItem {
-            id: "box_pixel_pusher", name: "Pixel Pusher",
-            description: "Shipper of CSS.",
-            cost: 0, rarity: Rarity::Common,
-        },
-        emoji: "🖼️",
-    },
-    BadgeDef {
-        item: ShopItem {
-            id: "box_moonlit", name: "Moonlit",
-            description: "Working past bedtime.",
-            cost: 0, rarity: Rarity::Common,
-        },
-        emoji: "🌙",
-    },
-    // --- Uncommon (25%) --------------------------------------------------
-    BadgeDef {
-        item: ShopItem {
-            id: "box_speedrunner", name: "Speedrunner",
-            description: "Beat the standup.",
-            cost: 0, rarity: Rarity::Uncommon,
-        },
-        emoji: "🚀",
-    },
-    BadgeDef {
-        item: ShopItem {
-            id: "box_trailblazer", name: "Trailblazer",
-            description: "Commits before coffee.",
-            cost: 0, rarity: Rarity::Uncommon,
-        },
-        emoji: "🧭",
-    },
-    BadgeDef {
-        item: ShopItem {
-            id: "box_stargazer", name: "Stargazer",
-            description: "Collects repo stars.",
-            cost: 0, rarity: Rarity::Uncommon,
-        },
-        emoji: "⭐",
-    },
-    // --- Rare (10%) ------------------------------------------------------
-    BadgeDef {
-        item: ShopItem {
-            id: "box_alchemist", name: "Alchemist",
-            description: "Turns bugs into features.",
-            cost: 0, rarity: Rarity::Rare,
-        },
-        emoji: "🧪",
-    },
-    BadgeDef {
-        item: ShopItem {
-            id: "box_code_wizard", name: "Code Wizard",
-            description: "It's not magic, it's hashmaps.",
-            cost: 0, rarity: Rarity::Rare,
-        },
-        emoji: "🪄",
-    },
-    // --- Legendary (5%) --------------------------------------------------
-    BadgeDef {
-        item: ShopItem {
-            id: "box_void_walker", name: "Void Walker",
-            description: "Stared into the debugger and smiled.",
-            cost: 0, rarity: Rarity::Legendary,
-        },
-        emoji: "🌌",
-    },
-];
+    /// Colorways that can still be bought or gifted.
+    pub fn active_colorways(&self) -> impl Iterator<Item = &ColorwayDef> {
+        self.colorways.iter().filter(|c| !c.item.retired)
+    }
+
+    /// Badges that can still be pulled from a lootbox.
+    pub fn active_badges(&self) -> impl Iterator<Item = &BadgeDef> {
+        self.lootbox_pool.iter().filter(|b| !b.item.retired)
+    }
+
+    /// Unlocks that can still be bought.
+    pub fn active_unlocks(&self) -> impl Iterator<Item = &ShopItem> {
+        self.unlocks.iter().filter(|u| !u.retired)
+    }
+
+    /// Achievements that can still be earned.
+    pub fn active_achievements(&self) -> impl Iterator<Item = &Achievement> {
+        self.achievements.iter().filter(|a| !a.retired)
+    }
+
+    /// Every item header in the catalog, retired ones included. Achievements
+    /// are not items and are not included.
+    pub fn items(&self) -> impl Iterator<Item = &ShopItem> {
+        self.titles
+            .iter()
+            .map(|t| &t.item)
+            .chain(self.colorways.iter().map(|c| &c.item))
+            .chain(self.lootbox_pool.iter().map(|b| &b.item))
+            .chain(self.unlocks.iter())
+    }
 
-/// One-time paywall unlocks — 30 tabs each.
+    /// The ID of every item and achievement, retired ones included.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.items()
+            .map(|i| i.id.as_str())
+            .chain(self.achievements.iter().map(|a| a.id.as_str()))
+    }
+}
+
+/// The catalog in use. Starts empty until [`super::catalog_file::load`] runs
+/// at startup.
+static CATALOG: LazyLock<RwLock<Arc<Catalog>>> = LazyLock::new(Default::default);
+
+/// A snapshot of the catalog in use.
 ///
-/// Custom colorway and custom banner are no longer one-time unlocks; they
-/// charge per-set in `/profile set colorway|banner`. Only the custom title
-/// remains an unlock because the title text is stored once and reused.
-pub const UNLOCKS: &[ShopItem] = &[
-    ShopItem {
-        id: "unlock_custom_title", name: "Custom Title Unlock",
-        description: "Enables `/profile set customtitle <text>` (up to 32 chars).",
-        cost: 30, rarity: Rarity::Uncommon,
-    },
-];
+/// Cheap (one `Arc` clone). Hold on to it for the length of one command so
+/// every lookup in that command sees the same catalog, even if a reload lands
+/// midway.
+pub fn current() -> Arc<Catalog> {
+    CATALOG.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Replace the catalog in use. Commands already holding a snapshot from
+/// [`current`] keep theirs.
+pub fn install(catalog: Catalog) {
+    *CATALOG.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(catalog);
+}
 
-/// Virtual lootbox purchase — not backed by an entry in a typed table. The
+/// Virtual lootbox purchase — not backed by an entry in the catalog. The
 /// lootbox command references this directly so `/shop browse` can list it
-/// alongside real items.
-pub const LOOTBOX_ITEM: ShopItem = ShopItem {
-    id: "lootbox", name: "Badge Lootbox",
-    description: "Rolls a random badge by rarity. Duplicates salvage for tabs.",
-    cost: LOOTBOX_COST, rarity: Rarity::Common,
-};
+/// alongside real items. The price is per guild (`lootbox_cost` in
+/// [`super::guild_config::GuildConfig`]), so the caller passes it in.
+pub fn lootbox_item(cost: i64) -> ShopItem {
+    ShopItem {
+        id: "lootbox".into(),
+        name: "Badge Lootbox".into(),
+        description: "Rolls a random badge by rarity. Duplicates salvage for tabs.".into(),
+        cost,
+        rarity: Rarity::Common,
+        retired: false,
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Lookup
 // ---------------------------------------------------------------------------
+//
+// Lookups include retired items so existing inventories keep rendering.
+// Purchase and gift flows check `item.retired` themselves.
 
 /// Find a title definition by ID (includes rarity / cost / description).
-pub fn lookup_title(id: &str) -> Option<&'static TitleDef> {
-    TITLES.iter().find(|t| t.item.id == id)
+pub fn lookup_title(id: &str) -> Option<TitleDef> {
+    current().titles.iter().find(|t| t.item.id == id).cloned()
 }
 
 /// Find a colorway by ID.
-pub fn lookup_colorway(id: &str) -> Option<&'static ColorwayDef> {
-    COLORWAYS.iter().find(|c| c.item.id == id)
+pub fn lookup_colorway(id: &str) -> Option<ColorwayDef> {
+    current().colorways.iter().find(|c| c.item.id == id).cloned()
 }
 
 /// Find a badge (from the lootbox pool) by ID.
-pub fn lookup_badge(id: &str) -> Option<&'static BadgeDef> {
-    LOOTBOX_POOL.iter().find(|b| b.item.id == id)
+pub fn lookup_badge(id: &str) -> Option<BadgeDef> {
+    current().lootbox_pool.iter().find(|b| b.item.id == id).cloned()
+}
+
+/// Find an unlock by ID.
+pub fn lookup_unlock(id: &str) -> Option<ShopItem> {
+    current().unlocks.iter().find(|u| u.id == id).cloned()
 }
 
 // ---------------------------------------------------------------------------
@@ -316,122 +254,29 @@ pub fn lookup_badge(id: &str) -> Option<&'static BadgeDef> {
 
 /// A server-interaction achievement.
 ///
-/// Unlocks are gated by a predicate that inspects the user's current
-/// [`InventoryUser`] and [`WalletUser`]. On unlock the ID is pushed onto
+/// Unlocks when [`Self::rule`] holds for a member (see
+/// [`super::achievement_rule`]). On unlock the ID is pushed onto
 /// `inventory.unlocked_achievements` and also into `inventory.owned_badges`
 /// so it surfaces in `/shop inventory`'s Badges section (under
-/// "Achievements" via the `ach_` prefix convention).
-#[derive(Clone, Copy)]
+/// "Achievements" via the `ach_` prefix convention). ID prefix is `ach_` to
+/// distinguish from lootbox badges (`box_`).
+#[derive(Debug, Clone)]
 pub struct Achievement {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub emoji: &'static str,
-    pub description: &'static str,
-    /// Predicate — `true` iff the user has earned this achievement.
-    ///
-    /// Pure; no I/O. Checked every time a stat mutates.
-    pub check: fn(&InventoryUser, &WalletUser) -> bool,
+    pub id: String,
+    pub name: String,
+    pub emoji: String,
+    pub description: String,
+    /// Condition for unlocking. Checked every time a stat mutates.
+    pub rule: Rule,
+    /// `true` once the achievement has been removed from the catalog file.
+    /// Retired achievements are no longer awarded, but members who earned
+    /// one keep its badge.
+    pub retired: bool,
 }
 
-/// Static achievement registry. ID prefix is `ach_` to distinguish from
-/// lootbox badges (`box_`).
-pub const ACHIEVEMENTS: &[Achievement] = &[
-    Achievement {
-        id: "ach_chatterbox",
-        name: "Chatterbox",
-        emoji: "💬",
-        description: "Send 100 messages.",
-        check: |i, _| i.messages_sent >= 100,
-    },
-    Achievement {
-        id: "ach_lorekeeper",
-        name: "Lorekeeper",
-        emoji: "📚",
-        description: "Send 1,000 messages.",
-        check: |i, _| i.messages_sent >= 1_000,
-    },
-    Achievement {
-        id: "ach_oracle",
-        name: "Oracle",
-        emoji: "🔮",
-        description: "Send 10,000 messages.",
-        check: |i, _| i.messages_sent >= 10_000,
-    },
-    Achievement {
-        id: "ach_first_spend",
-        name: "First Spend",
-        emoji: "🎉",
-        description: "Make your first shop purchase.",
-        check: |i, _| i.tabs_spent_lifetime >= 1,
-    },
-    Achievement {
-        id: "ach_spender",
-        name: "Spender",
-        emoji: "💸",
-        description: "Spend 50 tabs across the shop.",
-        check: |i, _| i.tabs_spent_lifetime >= 50,
-    },
-    Achievement {
-        id: "ach_whale",
-        name: "Whale",
-        emoji: "🐋",
-        description: "Spend 500 tabs across the shop.",
-        check: |i, _| i.tabs_spent_lifetime >= 500,
-    },
-    Achievement {
-        id: "ach_generous",
-        name: "Generous",
-        emoji: "🎁",
-        description: "Send 1 gift.",
-        check: |i, _| i.gifts_sent >= 1,
-    },
-    Achievement {
-        id: "ach_philanthropist",
-        name: "Philanthropist",
-        emoji: "💝",
-        description: "Send 10 gifts.",
-        check: |i, _| i.gifts_sent >= 10,
-    },
-    Achievement {
-        id: "ach_beloved",
-        name: "Beloved",
-        emoji: "🫂",
-        description: "Receive 5 gifts.",
-        check: |i, _| i.gifts_received >= 5,
-    },
-    Achievement {
-        id: "ach_committed",
-        name: "Committed",
-        emoji: "🔥",
-        description: "Hit a 7-day daily streak.",
-        check: |_, w| w.current_streak >= 7,
-    },
-    Achievement {
-        id: "ach_devoted",
-        name: "Devoted",
-        emoji: "🌟",
-        description: "Hit a 30-day daily streak.",
-        check: |_, w| w.current_streak >= 30,
-    },
-    Achievement {
-        id: "ach_quick_fingers",
-        name: "Quick Fingers",
-        emoji: "⚡",
-        description: "Claim 5 tab-reaction faucet drops.",
-        check: |i, _| i.faucet_claims >= 5,
-    },
-    Achievement {
-        id: "ach_treasure_hunter",
-        name: "Treasure Hunter",
-        emoji: "💎",
-        description: "Open 10 lootboxes.",
-        check: |i, _| i.lootboxes_opened >= 10,
-    },
-];
-
-/// Find an achievement by ID.
-pub fn lookup_achievement(id: &str) -> Option<&'static Achievement> {
-    ACHIEVEMENTS.iter().find(|a| a.id == id)
+/// Find an achievement by ID (includes retired ones).
+pub fn lookup_achievement(id: &str) -> Option<Achievement> {
+    current().achievements.iter().find(|a| a.id == id).cloned()
 }
 
 /// Resolve a badge ID to its `(emoji, name)` for display.
@@ -439,7 +284,7 @@ pub fn lookup_achievement(id: &str) -> Option<&'static Achievement> {
 /// Checks the lootbox pool first, then the achievement registry. Returns
 /// `None` if the ID doesn't match either — callers should filter those out
 /// when rendering so stale data in `owned_badges` doesn't break the view.
-pub fn resolve_badge_display(id: &str) -> Option<(&'static str, &'static str)> {
+pub fn resolve_badge_display(id: &str) -> Option<(String, String)> {
     if let Some(b) = lookup_badge(id) {
         return Some((b.emoji, b.item.name));
     }
@@ -0,0 +1,114 @@
+//! Snoozing and completing a reminder from its DM.
+//!
+//! A private reminder DM carries a row of [`ReminderButton`]s. Snoozing
+//! stores a [`Snooze`] on the event, which the reminder scheduler fires like
+//! any other reminder; marking it done records the occurrence as completed
+//! and stops its remaining reminders.
+
+use crate::pawthos::consts::SNOOZE_TOMORROW_HOUR;
+use crate::pawthos::structs::{recurrence, schedule_event::EventId};
+use chrono::{DateTime, Days, Duration, NaiveTime, Utc};
+use chrono_tz::Tz;
+use poise::serenity_prelude::ButtonStyle;
+use serde::{Deserialize, Serialize};
+
+/// An extra reminder asked for from a reminder DM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snooze {
+    /// When to remind again.
+    pub at: DateTime<Utc>,
+
+    /// The occurrence the snoozed reminder was for. A repeating event may
+    /// have moved on to its next occurrence by the time the snooze is up.
+    pub occurrence: DateTime<Utc>,
+}
+
+/// A press of one of the buttons on a reminder DM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderButton {
+    Snooze5m,
+    Snooze1h,
+    SnoozeTomorrow,
+    Done,
+}
+
+impl ReminderButton {
+    /// Prefix shared by every reminder button's custom ID.
+    const PREFIX: &'static str = "remind";
+
+    /// In the order they're shown.
+    pub const ALL: [ReminderButton; 4] = [
+        ReminderButton::Snooze5m,
+        ReminderButton::Snooze1h,
+        ReminderButton::SnoozeTomorrow,
+        ReminderButton::Done,
+    ];
+
+    fn action(self) -> &'static str {
+        match self {
+            ReminderButton::Snooze5m => "snooze-5m",
+            ReminderButton::Snooze1h => "snooze-1h",
+            ReminderButton::SnoozeTomorrow => "snooze-tomorrow",
+            ReminderButton::Done => "done",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReminderButton::Snooze5m => "Snooze 5m",
+            ReminderButton::Snooze1h => "Snooze 1h",
+            ReminderButton::SnoozeTomorrow => "Tomorrow",
+            ReminderButton::Done => "Done",
+        }
+    }
+
+    pub fn style(self) -> ButtonStyle {
+        match self {
+            ReminderButton::Done => ButtonStyle::Success,
+            _ => ButtonStyle::Secondary,
+        }
+    }
+
+    /// `remind:snooze-1h:<event>:<occurrence>` — the occurrence, as a Unix
+    /// timestamp, tells a snooze of last week's reminder from this week's.
+    pub fn custom_id(self, event: EventId, occurrence: DateTime<Utc>) -> String {
+        format!(
+            "{}:{}:{event}:{}",
+            Self::PREFIX,
+            self.action(),
+            occurrence.timestamp()
+        )
+    }
+
+    /// The reverse of [`Self::custom_id`]; `None` for any other button.
+    pub fn parse(custom_id: &str) -> Option<(ReminderButton, EventId, DateTime<Utc>)> {
+        let mut parts = custom_id.split(':');
+        if parts.next()? != Self::PREFIX {
+            return None;
+        }
+        let action = parts.next()?;
+        let button = Self::ALL.into_iter().find(|b| b.action() == action)?;
+        let event = parts.next()?.parse().ok()?;
+        let occurrence = DateTime::from_timestamp(parts.next()?.parse().ok()?, 0)?;
+        parts
+            .next()
+            .is_none()
+            .then_some((button, event, occurrence))
+    }
+
+    /// When a snooze button reminds again, pressed at `now` by someone in
+    /// `tz`: in 5 minutes, in an hour, or tomorrow morning. `None` for
+    /// [`ReminderButton::Done`].
+    pub fn snooze_until(self, now: DateTime<Utc>, tz: Tz) -> Option<DateTime<Utc>> {
+        match self {
+            ReminderButton::Snooze5m => Some(now + Duration::minutes(5)),
+            ReminderButton::Snooze1h => Some(now + Duration::hours(1)),
+            ReminderButton::SnoozeTomorrow => {
+                let tomorrow = now.with_timezone(&tz).date_naive() + Days::new(1);
+                let morning = NaiveTime::from_hms_opt(SNOOZE_TOMORROW_HOUR, 0, 0)?;
+                recurrence::localise(tomorrow, morning, tz)
+            }
+            ReminderButton::Done => None,
+        }
+    }
+}
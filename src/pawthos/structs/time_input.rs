@@ -0,0 +1,389 @@
+//! Free-form times for `/schedule add` and `/time convert`.
+//!
+//! [`resolve`] turns what a user types into an absolute time in their
+//! timezone; [`interpret`] does the same without insisting on the future.
+//! Accepted forms:
+//!
+//! | Form | Examples |
+//! |---|---|
+//! | Relative | `in 20 minutes`, `2h30m`, `in 1 hour 15 min`, `in 3 days` |
+//! | Day + time | `tomorrow 9am`, `today at 17:30`, `next friday 5:30pm`, `friday noon` |
+//! | Date + time | `2026-05-01 14:00`, `9am 2026-05-01` |
+//! | Time only | `17:30`, `9pm` — today, or tomorrow if that's already passed |
+//!
+//! Words are case-insensitive, and `at` / `on` are ignored. A plain weekday
+//! means the next one, today included if the time hasn't passed yet; `next`
+//! always skips today.
+
+use crate::pawthos::enums::schedule_errors::ScheduleError;
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// Resolve `input` against `now` in `tz`.
+///
+/// Fails with a [`ScheduleError`] naming the part that didn't parse, or if
+/// the result isn't in the future.
+pub fn resolve(input: &str, now: DateTime<Utc>, tz: Tz) -> Result<DateTime<Tz>, ScheduleError> {
+    let when = interpret(input, now, tz)?;
+    if when <= now {
+        return Err(ScheduleError::TimeInPast);
+    }
+    Ok(when)
+}
+
+/// Like [`resolve`], but a time that has already passed (`2026-01-01
+/// 09:00`) is returned as is.
+pub fn interpret(input: &str, now: DateTime<Utc>, tz: Tz) -> Result<DateTime<Tz>, ScheduleError> {
+    let input = input.trim().to_lowercase();
+    let tokens: Vec<&str> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty() && !matches!(*t, "at" | "on"))
+        .collect();
+
+    let offset = match tokens.split_first() {
+        None => return Err(ScheduleError::UnrecognisedTime(input)),
+        Some((&"in", rest)) => Some(parse_duration(rest)?),
+        // "2h30m" without the "in".
+        Some(_) => parse_duration(&tokens).ok(),
+    };
+    let when = match offset {
+        Some(offset) => now
+            .checked_add_signed(offset)
+            .ok_or(ScheduleError::TimeOutOfRange)?,
+        None => resolve_calendar(&tokens, now.with_timezone(&tz))?.to_utc(),
+    };
+    Ok(when.with_timezone(&tz))
+}
+
+// ---------------------------------------------------------------------------
+// Relative times
+// ---------------------------------------------------------------------------
+
+/// `20 minutes`, `2h30m`, `1 hour 15 min`, `a day`.
+///
+/// Also reads the lead times of `/schedule defaults`.
+pub fn parse_duration(tokens: &[&str]) -> Result<Duration, ScheduleError> {
+    let joined = tokens.join(" ");
+    let unrecognised = || ScheduleError::UnrecognisedTime(joined.clone());
+
+    // Split into alternating number and unit runs, whether or not they're
+    // separated by spaces: "2h30m" and "2 h 30 m" read the same.
+    let mut total = Duration::zero();
+    let mut amount: Option<i64> = None;
+    let mut chars = joined.chars().filter(|c| !c.is_whitespace()).peekable();
+    while chars.peek().is_some() {
+        if chars.peek().is_some_and(char::is_ascii_digit) {
+            let mut digits = String::new();
+            while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                digits.push(c);
+            }
+            amount = Some(digits.parse().map_err(|_| ScheduleError::TimeOutOfRange)?);
+            continue;
+        }
+        let mut word = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_ascii_digit()) {
+            word.push(c);
+        }
+        // "a day" / "an hour" — the article glued onto the unit by the
+        // whitespace filter above.
+        let (count, unit) = match (amount.take(), word.as_str()) {
+            (Some(n), unit) => (n, unit),
+            (None, w) if w.starts_with("an") => (1, &w[2..]),
+            (None, w) if w.starts_with('a') => (1, &w[1..]),
+            (None, _) => return Err(unrecognised()),
+        };
+        let step = match unit {
+            "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(count),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(count),
+            "d" | "day" | "days" => Duration::try_days(count),
+            "w" | "wk" | "wks" | "week" | "weeks" => Duration::try_weeks(count),
+            _ => return Err(unrecognised()),
+        };
+        total = step
+            .and_then(|step| total.checked_add(&step))
+            .ok_or(ScheduleError::TimeOutOfRange)?;
+    }
+
+    if amount.is_some() || total.is_zero() {
+        // A trailing number with no unit ("in 20"), or nothing at all.
+        return Err(unrecognised());
+    }
+    Ok(total)
+}
+
+// ---------------------------------------------------------------------------
+// Calendar times
+// ---------------------------------------------------------------------------
+
+/// Which day a calendar time falls on, before the time of day is known.
+enum Day {
+    Date(NaiveDate),
+    /// A plain weekday — the next one, today included.
+    Weekday(Weekday),
+    /// `next <weekday>` — the next one after today.
+    NextWeekday(Weekday),
+}
+
+/// `tomorrow 9am`, `next friday 17:30`, `2026-05-01 14:00`, `9pm`.
+fn resolve_calendar(tokens: &[&str], now: DateTime<Tz>) -> Result<DateTime<Tz>, ScheduleError> {
+    let today = now.date_naive();
+    let mut day: Option<Day> = None;
+    let mut time: Option<NaiveTime> = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let next = tokens.get(i + 1).copied();
+        i += 1;
+
+        let parsed_day = match token {
+            "today" | "tonight" => Some(Day::Date(today)),
+            "tomorrow" => Some(Day::Date(today + Days::new(1))),
+            "next" => {
+                let weekday = next
+                    .and_then(parse_weekday)
+                    .ok_or_else(|| ScheduleError::UnrecognisedTime(tokens.join(" ")))?;
+                i += 1;
+                Some(Day::NextWeekday(weekday))
+            }
+            _ => parse_weekday(token).map(Day::Weekday).or_else(|| {
+                NaiveDate::parse_from_str(token, "%Y-%m-%d")
+                    .ok()
+                    .map(Day::Date)
+            }),
+        };
+        if let Some(parsed) = parsed_day {
+            if day.replace(parsed).is_some() {
+                return Err(ScheduleError::UnrecognisedTime(tokens.join(" ")));
+            }
+            continue;
+        }
+
+        // "9 am" arrives as two tokens; glue the suffix back on.
+        let (clock, used_next) = match next {
+            Some(suffix @ ("am" | "pm"))
+                if token.chars().all(|c| c.is_ascii_digit() || c == ':') =>
+            {
+                (format!("{token}{suffix}"), true)
+            }
+            _ => (token.to_string(), false),
+        };
+        if used_next {
+            i += 1;
+        }
+        let parsed = parse_clock(&clock)?;
+        if time.replace(parsed).is_some() {
+            return Err(ScheduleError::UnrecognisedTime(tokens.join(" ")));
+        }
+    }
+
+    let time = time.ok_or(ScheduleError::MissingTime)?;
+    let date = match day {
+        Some(Day::Date(date)) => date,
+        Some(Day::NextWeekday(weekday)) => next_weekday(today + Days::new(1), weekday),
+        Some(Day::Weekday(weekday)) => {
+            let date = next_weekday(today, weekday);
+            if date == today && time <= now.time() {
+                date + Days::new(7)
+            } else {
+                date
+            }
+        }
+        None if time > now.time() => today,
+        None => today + Days::new(1),
+    };
+
+    date.and_time(time)
+        .and_local_timezone(now.timezone())
+        .single()
+        .ok_or(ScheduleError::AmbiguousOrInvalidTime)
+}
+
+/// `9am`, `9:30pm`, `17:30`, `noon`, `midnight`.
+///
+/// Anything else is reported as unrecognised; something that looks like a
+/// clock time but isn't one (`25:00`, `13pm`) as an invalid time.
+fn parse_clock(token: &str) -> Result<NaiveTime, ScheduleError> {
+    match token {
+        "noon" | "midday" => return Ok(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+        "midnight" => return Ok(NaiveTime::MIN),
+        _ => {}
+    }
+
+    let (digits, meridiem) = if let Some(d) = token.strip_suffix("am") {
+        (d, Some(false))
+    } else if let Some(d) = token.strip_suffix("pm") {
+        (d, Some(true))
+    } else {
+        (token, None)
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return Err(ScheduleError::UnrecognisedTime(token.to_string()));
+    }
+    // A bare number is a duration or a date fragment, not a time of day.
+    if meridiem.is_none() && !digits.contains(':') {
+        return Err(ScheduleError::UnrecognisedTime(token.to_string()));
+    }
+
+    let invalid = || ScheduleError::InvalidClockTime(token.to_string());
+    let (hour, minute) = match digits.split_once(':') {
+        Some((h, m)) if m.len() == 2 => (
+            h.parse::<u32>().map_err(|_| invalid())?,
+            m.parse::<u32>().map_err(|_| invalid())?,
+        ),
+        Some(_) => return Err(invalid()),
+        None => (digits.parse::<u32>().map_err(|_| invalid())?, 0),
+    };
+    let hour = match meridiem {
+        None => hour,
+        Some(_) if !(1..=12).contains(&hour) => return Err(invalid()),
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(invalid)
+}
+
+/// `fri`, `friday`, …
+fn parse_weekday(token: &str) -> Option<Weekday> {
+    token.parse().ok()
+}
+
+/// The first `weekday` on or after `from`.
+fn next_weekday(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Days::new(u64::from(ahead))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDateTime, TimeZone};
+    use chrono_tz::Europe::London;
+
+    /// 2026-05-01 is a Friday; London is on BST (UTC+1).
+    fn at(local: &str) -> DateTime<Utc> {
+        London
+            .from_local_datetime(&naive(local))
+            .single()
+            .unwrap()
+            .to_utc()
+    }
+
+    fn naive(local: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    /// Where `input` lands in London local time, typed at `now`.
+    fn local(input: &str, now: &str) -> NaiveDateTime {
+        interpret(input, at(now), London).unwrap().naive_local()
+    }
+
+    fn error(input: &str) -> ScheduleError {
+        interpret(input, at("2026-05-01 12:00"), London).unwrap_err()
+    }
+
+    #[test]
+    fn relative_times() {
+        let now = at("2026-05-01 12:00");
+        for (input, minutes) in [
+            ("in 20 minutes", 20),
+            ("2h30m", 150),
+            ("in 2 h 30 m", 150),
+            ("an hour", 60),
+            ("in a day", 24 * 60),
+            ("in 1 hour 15 min", 75),
+        ] {
+            let when = interpret(input, now, London).unwrap();
+            assert_eq!(when.to_utc() - now, Duration::minutes(minutes), "{input}");
+        }
+    }
+
+    #[test]
+    fn day_and_time() {
+        let now = "2026-05-01 12:00";
+        assert_eq!(local("tomorrow 9am", now), naive("2026-05-02 09:00"));
+        assert_eq!(local("Today at 17:30", now), naive("2026-05-01 17:30"));
+        assert_eq!(local("2026-06-01 14:00", now), naive("2026-06-01 14:00"));
+        assert_eq!(local("9am 2026-06-01", now), naive("2026-06-01 09:00"));
+    }
+
+    #[test]
+    fn time_only_is_today_or_tomorrow() {
+        let now = "2026-05-01 12:00";
+        assert_eq!(local("9 pm", now), naive("2026-05-01 21:00"));
+        assert_eq!(local("9am", now), naive("2026-05-02 09:00"));
+        assert_eq!(local("12am", now), naive("2026-05-02 00:00"));
+        assert_eq!(local("12pm", "2026-05-01 11:00"), naive("2026-05-01 12:00"));
+        assert_eq!(local("noon", now), naive("2026-05-02 12:00"));
+    }
+
+    #[test]
+    fn weekday_includes_today_until_the_time_passes() {
+        let before = "2026-05-01 12:00";
+        let after = "2026-05-01 18:00";
+        assert_eq!(local("friday 17:30", before), naive("2026-05-01 17:30"));
+        assert_eq!(local("friday 17:30", after), naive("2026-05-08 17:30"));
+        assert_eq!(
+            local("next friday 17:30", before),
+            naive("2026-05-08 17:30")
+        );
+        assert_eq!(local("next friday 17:30", after), naive("2026-05-08 17:30"));
+        assert_eq!(local("mon 9am", before), naive("2026-05-04 09:00"));
+    }
+
+    #[test]
+    fn impossible_clock_times() {
+        for input in ["25:00", "13pm", "0am", "9:5", "12:60"] {
+            assert!(
+                matches!(error(input), ScheduleError::InvalidClockTime(_)),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn unrecognised_input() {
+        for input in ["in 20", "in 2h30", "tomorrow 9", "soon", "next 9am", ""] {
+            assert!(
+                matches!(error(input), ScheduleError::UnrecognisedTime(_)),
+                "{input}"
+            );
+        }
+        assert!(matches!(error("tomorrow"), ScheduleError::MissingTime));
+    }
+
+    #[test]
+    fn dst_gap_and_overlap() {
+        // Clocks go forward at 01:00 on 2026-03-29 and back at 02:00 on
+        // 2026-10-25.
+        let now = at("2026-03-01 12:00");
+        for input in ["2026-03-29 01:30", "2026-10-25 01:30"] {
+            assert!(
+                matches!(
+                    interpret(input, now, London),
+                    Err(ScheduleError::AmbiguousOrInvalidTime)
+                ),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            local("2026-03-29 02:30", "2026-03-01 12:00"),
+            naive("2026-03-29 02:30")
+        );
+    }
+
+    #[test]
+    fn resolve_wants_the_future() {
+        let now = at("2026-05-01 12:00");
+        assert!(matches!(
+            resolve("2026-05-01 11:00", now, London),
+            Err(ScheduleError::TimeInPast)
+        ));
+        assert_eq!(
+            interpret("2026-05-01 11:00", now, London)
+                .unwrap()
+                .naive_local(),
+            naive("2026-05-01 11:00")
+        );
+        assert!(resolve("in 1 minute", now, London).is_ok());
+    }
+}
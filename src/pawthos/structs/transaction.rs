@@ -0,0 +1,291 @@
+//! Multi-record, all-or-nothing writes against the [`UserDB`].
+//!
+//! A [`Transaction`] is handed to the closure passed to
+//! [`super::data::Data::transact`]. It gives mutable access to any number of
+//! sub-structs — several sub-systems of one user, or several users — while
+//! the single write lock on the database is held.
+//!
+//! The first time a record is touched, a copy of it is kept. If the closure
+//! returns `Err`, every touched record is put back (records that didn't exist
+//! before are removed again), so a half-finished purchase never leaks. If it
+//! returns `Ok`, the touched records are collected into one batch for the
+//! persistence task.
+//!
+//! Tabs only move through [`Transaction::credit`] and [`Transaction::debit`],
+//! which queue a [`LedgerEntry`] alongside the balance change. The entries are
+//! appended to the guild's ledger on commit and dropped on rollback, so the
+//! ledger never records a movement that didn't happen.
+
+use crate::pawthos::enums::persistent_data::{DbRow, RowKey};
+use crate::pawthos::enums::wallet_errors::WalletError;
+use crate::pawthos::structs::inventory_user::InventoryUser;
+use crate::pawthos::structs::ledger_entry::{LedgerEntry, TabMemo};
+use crate::pawthos::structs::profile_user::ProfileUser;
+use crate::pawthos::structs::user_db::UserDB;
+use crate::pawthos::traits::{InventoryDbMarker, ProfileDbMarker, UserDbSpec, WalletDbMarker};
+use poise::serenity_prelude::{GuildId, UserId};
+
+/// Mutable view of the [`UserDB`] for the duration of one
+/// [`super::data::Data::transact`] call.
+pub struct Transaction<'a> {
+    db: &'a mut UserDB,
+
+    /// Every record touched so far, with its value from before the
+    /// transaction (`None` if it didn't exist yet). In first-touch order.
+    originals: Vec<(RowKey, Option<DbRow>)>,
+
+    /// Ledger lines for the tab movements made so far, appended on commit.
+    ledger: Vec<(GuildId, LedgerEntry)>,
+}
+
+impl<'a> Transaction<'a> {
+    /// Start a transaction over `db`. The caller must hold the write lock for
+    /// as long as the transaction lives.
+    pub fn new(db: &'a mut UserDB) -> Self {
+        Self {
+            db,
+            originals: Vec::new(),
+            ledger: Vec::new(),
+        }
+    }
+
+    /// Read a sub-struct without marking its record as touched.
+    ///
+    /// Returns `None` if the record doesn't exist (yet).
+    pub fn get<M>(&self, key: M::Key) -> Option<&M::User>
+    where
+        M: UserDbSpec<Db = UserDB>,
+    {
+        M::get_user(self.db, key)
+    }
+
+    /// Mutably access a sub-struct, creating a default record if needed.
+    ///
+    /// The record is saved when the transaction commits and restored if it
+    /// rolls back.
+    pub fn get_mut<M>(&mut self, key: M::Key) -> &mut M::User
+    where
+        M: UserDbSpec<Db = UserDB>,
+    {
+        let row_key = M::row_key(key);
+        if !self.originals.iter().any(|(k, _)| *k == row_key) {
+            self.originals.push((row_key, self.db.row(row_key)));
+        }
+        M::get_user_mut(self.db, key)
+    }
+
+    /// Add `amount` tabs to a member's wallet and log it. Returns the new
+    /// balance.
+    ///
+    /// `amount` may be negative for owner adjustments, which are allowed to
+    /// take a balance below zero.
+    pub fn credit(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        amount: i64,
+        memo: TabMemo,
+    ) -> i64 {
+        let balance = self
+            .get_mut::<WalletDbMarker>((guild_id, user_id))
+            .add_tabs(amount);
+        self.record(guild_id, user_id, amount, memo);
+        balance
+    }
+
+    /// Take `amount` tabs from a member's wallet and log it. Returns the new
+    /// balance.
+    ///
+    /// Fails with [`WalletError::NotEnoughTabs`] — logging nothing — if the
+    /// balance is too low.
+    pub fn debit(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        amount: i64,
+        memo: TabMemo,
+    ) -> Result<i64, WalletError> {
+        let balance = self
+            .get_mut::<WalletDbMarker>((guild_id, user_id))
+            .remove_tabs(amount)?;
+        self.record(guild_id, user_id, -amount, memo);
+        Ok(balance)
+    }
+
+    /// Log a movement of `delta` tabs that was applied to the wallet some
+    /// other way, e.g. by [`crate::pawthos::structs::wallet_user::WalletUser::claim_daily`].
+    pub fn record(&mut self, guild_id: GuildId, user_id: UserId, delta: i64, memo: TabMemo) {
+        self.ledger.push((guild_id, memo.into_entry(user_id, delta)));
+    }
+
+    /// Shorthand for `get_mut::<InventoryDbMarker>((guild_id, user_id))`.
+    pub fn inventory(&mut self, guild_id: GuildId, user_id: UserId) -> &mut InventoryUser {
+        self.get_mut::<InventoryDbMarker>((guild_id, user_id))
+    }
+
+    /// Shorthand for `get_mut::<ProfileDbMarker>(user_id)`.
+    pub fn profile(&mut self, user_id: UserId) -> &mut ProfileUser {
+        self.get_mut::<ProfileDbMarker>(user_id)
+    }
+
+    /// Finish successfully: append the queued ledger lines, then return the
+    /// current value of every touched record, followed by the new ledger
+    /// lines, for persistence.
+    pub fn commit(self) -> Vec<DbRow> {
+        let mut rows: Vec<DbRow> = self
+            .originals
+            .into_iter()
+            .filter_map(|(key, _)| self.db.row(key))
+            .collect();
+        for (guild_id, entry) in self.ledger {
+            let ledger = &mut self.db.guilds.entry(guild_id).or_default().ledger;
+            ledger.push(entry.clone());
+            rows.push(DbRow::Ledger {
+                guild_id,
+                entry: Box::new(entry),
+            });
+        }
+        rows
+    }
+
+    /// Abandon the transaction, restoring every touched record to its
+    /// original value. Restores in reverse order of first touch; queued ledger
+    /// lines are discarded.
+    pub fn rollback(self) {
+        for (key, original) in self.originals.into_iter().rev() {
+            self.db.restore(key, original);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dectalk::pool::Pool;
+    use crate::pawthos::enums::ledger_reason::LedgerReason;
+    use crate::pawthos::enums::persistent_data::PersistentData;
+    use crate::pawthos::enums::wallet_errors::WalletError;
+    use crate::pawthos::structs::data::Data;
+    use crate::pawthos::structs::ledger_entry::TabMemo;
+    use crate::pawthos::structs::user_db::UserDB;
+    use crate::store::UserStore;
+    use crate::store::sqlite::SqliteStore;
+    use crate::voice::player::Players;
+    use poise::serenity_prelude::{GuildId, UserId};
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::{RwLock, mpsc};
+
+    const GUILD: GuildId = GuildId::new(1);
+    const ALICE: UserId = UserId::new(10);
+    const BOB: UserId = UserId::new(20);
+    const CAROL: UserId = UserId::new(30);
+
+    /// A [`Data`] with no DECtalk workers, and the receiving end of its
+    /// persistence channel.
+    fn data() -> (Data, mpsc::Receiver<PersistentData>) {
+        let (persist, persisted) = mpsc::channel(16);
+        let data = Data {
+            user_db: Arc::new(RwLock::new(UserDB::default())),
+            persistent_data_channel: persist,
+            schedule_events_channel: mpsc::unbounded_channel().0,
+            faucet_bounties: Default::default(),
+            faucet_last_spawn: Default::default(),
+            catalog_path: Default::default(),
+            events: Default::default(),
+            voice: Players::default(),
+            tts: Pool::new(0, 1, Duration::from_secs(1)),
+        };
+        (data, persisted)
+    }
+
+    /// Hand every batch sent so far to `store`, as the persistence task
+    /// does. Returns how many there were.
+    fn persist(persisted: &mut mpsc::Receiver<PersistentData>, store: &mut SqliteStore) -> usize {
+        let mut batches = 0;
+        while let Ok(message) = persisted.try_recv() {
+            let PersistentData::Rows(rows) = message else {
+                panic!("unexpected {message:?}");
+            };
+            store.save_rows(rows).unwrap();
+            batches += 1;
+        }
+        batches
+    }
+
+    /// `db` as JSON, to compare whole databases.
+    fn json(db: &UserDB) -> serde_json::Value {
+        serde_json::to_value(db).unwrap()
+    }
+
+    fn memo() -> TabMemo {
+        TabMemo::new(LedgerReason::Admin)
+    }
+
+    #[tokio::test]
+    async fn commit_persists_every_touched_record_in_one_batch() {
+        let (data, mut persisted) = data();
+        let mut store = SqliteStore::open(Path::new(":memory:")).unwrap();
+
+        data.transact(|tx| {
+            tx.credit(GUILD, ALICE, 100, memo());
+            tx.debit(GUILD, ALICE, 30, memo().counterparty(BOB))?;
+            tx.credit(GUILD, BOB, 30, memo().counterparty(ALICE));
+            tx.inventory(GUILD, BOB).gifts_received += 1;
+            tx.profile(BOB).bio = Some("hi".into());
+            Ok::<_, WalletError>(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(persist(&mut persisted, &mut store), 1);
+
+        let db = data.user_db.read().await;
+        assert_eq!(db.get_guild_user(GUILD, ALICE).unwrap().wallet.tabs, 70);
+        let bob = db.get_guild_user(GUILD, BOB).unwrap();
+        assert_eq!(bob.wallet.tabs, 30);
+        assert_eq!(bob.inventory.gifts_received, 1);
+        let deltas: Vec<_> = db.guilds[&GUILD].ledger.iter().map(|e| e.delta).collect();
+        assert_eq!(deltas, [100, -30, 30]);
+
+        assert_eq!(json(&store.load().unwrap()), json(&db));
+    }
+
+    #[tokio::test]
+    async fn err_rolls_everything_back_and_persists_nothing() {
+        let (data, mut persisted) = data();
+        let mut store = SqliteStore::open(Path::new(":memory:")).unwrap();
+        data.transact(|tx| {
+            tx.credit(GUILD, ALICE, 50, memo());
+            Ok::<_, WalletError>(())
+        })
+        .await
+        .unwrap();
+        persist(&mut persisted, &mut store);
+        let before = json(&*data.user_db.read().await);
+
+        let result = data
+            .transact(|tx| {
+                tx.credit(GUILD, ALICE, 10, memo());
+                tx.inventory(GUILD, ALICE).gifts_sent = 5;
+                tx.credit(GUILD, CAROL, 5, memo());
+                tx.profile(CAROL).bio = Some("new".into());
+                tx.debit(GUILD, ALICE, 1_000, memo())?;
+                Ok(())
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(WalletError::NotEnoughTabs {
+                cost: 1_000,
+                balance: 60
+            })
+        ));
+
+        assert!(persisted.try_recv().is_err(), "nothing may be persisted");
+        let db = data.user_db.read().await;
+        assert!(db.get_guild_user(GUILD, CAROL).is_none());
+        assert!(db.get_user(CAROL).is_none());
+        assert_eq!(json(&db), before);
+        assert_eq!(json(&store.load().unwrap()), before);
+    }
+}
@@ -2,16 +2,17 @@
 
 use crate::pawthos::structs::{
     inventory_user::InventoryUser, mimic_user::MimicUser, profile_user::ProfileUser,
-    schedule_user::ScheduleUser, wallet_user::WalletUser,
+    schedule_user::ScheduleUser, vox_user::VoxUser, wallet_user::WalletUser,
 };
 use serde::{Deserialize, Serialize};
 
-/// All state associated with a single Discord user.
+/// All guild-independent state associated with a single Discord user.
 ///
 /// Each field is its own sub-struct owned by a different feature area. When a
 /// new feature is added, a new field is added here and a corresponding
 /// [`super::super::traits::UserDbSpec`] marker implementation routes the
-/// generic DB helpers to the right field.
+/// generic DB helpers to the right field. Economy state (wallet, inventory)
+/// lives per guild on [`super::guild_user::GuildUser`] instead.
 ///
 /// The `#[serde(default)]` attributes on each field ensure that old JSON
 /// snapshots (which may not have all fields) deserialise cleanly into the
@@ -26,15 +27,24 @@ pub struct User {
     #[serde(default)]
     pub schedule: ScheduleUser,
 
-    /// State for the wallet (`/daily`, `/balance`, `/color set`).
-    #[serde(default)]
-    pub wallet: WalletUser,
-
     /// State for the `/profile` command suite.
     #[serde(default)]
     pub profile: ProfileUser,
 
-    /// State for the `/shop` suite — owned items, unlock flags, interaction stats.
+    /// State for the `/vox` command suite.
     #[serde(default)]
-    pub inventory: InventoryUser,
+    pub vox: VoxUser,
+
+    /// Global wallet from before economies were scoped per guild.
+    ///
+    /// Only ever `Some` right after loading an old `user.json`; the startup
+    /// migration moves it into the home guild and clears it. Never written
+    /// back once empty.
+    #[serde(default, rename = "wallet", skip_serializing_if = "Option::is_none")]
+    pub legacy_wallet: Option<WalletUser>,
+
+    /// Global inventory from before economies were scoped per guild. See
+    /// [`Self::legacy_wallet`].
+    #[serde(default, rename = "inventory", skip_serializing_if = "Option::is_none")]
+    pub legacy_inventory: Option<InventoryUser>,
 }
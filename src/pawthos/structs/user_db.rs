@@ -1,11 +1,21 @@
 //! The top-level user database, persisted to `user.json`.
 
-use crate::pawthos::structs::{schedule_event::ScheduleEvent, user::User};
-use poise::serenity_prelude::UserId;
+use crate::pawthos::enums::persistent_data::{DbRow, RowKey};
+use crate::pawthos::structs::{
+    guild_db::GuildDB, guild_user::GuildUser, schedule_event::ScheduleEvent, user::User,
+};
+use poise::serenity_prelude::{GuildId, UserId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// In-memory user database — a `HashMap` from Discord user ID to [`User`].
+/// In-memory user database.
+///
+/// Holds two layers:
+///
+/// - `db` — a `HashMap` from Discord user ID to [`User`], for state that
+///   follows the user everywhere (mimics, schedule, profile).
+/// - `guilds` — a `HashMap` from guild ID to [`GuildDB`], for the economy
+///   (tabs, inventory, achievements), which is scoped per guild.
 ///
 /// This is the single source of truth for all per-user state. It is held
 /// behind a [`tokio::sync::RwLock`] inside [`super::data::Data`] and written
@@ -16,6 +26,12 @@ pub struct UserDB {
     /// (`{}`) deserialises as an empty map rather than an error.
     #[serde(default)]
     pub db: HashMap<UserId, User>,
+
+    /// Per-guild economies. Missing in snapshots written before guild
+    /// scoping existed; `#[serde(default)]` starts those with no guilds and
+    /// [`crate::framework`]'s migration folds the legacy global records in.
+    #[serde(default)]
+    pub guilds: HashMap<GuildId, GuildDB>,
 }
 
 impl UserDB {
@@ -31,14 +47,137 @@ impl UserDB {
         self.db.entry(user).or_default()
     }
 
-    /// Collect all scheduled events across every user.
+    /// Return an immutable reference to a user's record in `guild`, or
+    /// `None` if they have never touched that guild's economy.
+    pub fn get_guild_user(&self, guild: GuildId, user: UserId) -> Option<&GuildUser> {
+        self.guilds.get(&guild).and_then(|g| g.get_user(user))
+    }
+
+    /// Return a mutable reference to a user's record in `guild`, creating
+    /// both the guild and the member entry on first use.
+    pub fn get_guild_user_mut(&mut self, guild: GuildId, user: UserId) -> &mut GuildUser {
+        self.guilds.entry(guild).or_default().get_user_mut(user)
+    }
+
+    /// Clone the record at `key` out as a [`DbRow`], or `None` if it doesn't
+    /// exist.
+    pub fn row(&self, key: RowKey) -> Option<DbRow> {
+        match key {
+            RowKey::User(user_id) => self.get_user(user_id).map(|user| DbRow::User {
+                user_id,
+                user: Box::new(user.clone()),
+            }),
+            RowKey::GuildUser(guild_id, user_id) => {
+                self.get_guild_user(guild_id, user_id)
+                    .map(|user| DbRow::GuildUser {
+                        guild_id,
+                        user_id,
+                        user: Box::new(user.clone()),
+                    })
+            }
+            RowKey::GuildConfig(guild_id) => {
+                self.guilds.get(&guild_id).map(|g| DbRow::GuildConfig {
+                    guild_id,
+                    config: Box::new(g.config.clone()),
+                })
+            }
+            RowKey::GuildPermissions(guild_id) => {
+                self.guilds.get(&guild_id).map(|g| DbRow::GuildPermissions {
+                    guild_id,
+                    permissions: Box::new(g.permissions.clone()),
+                })
+            }
+            RowKey::GuildDictionary(guild_id) => {
+                self.guilds.get(&guild_id).map(|g| DbRow::GuildDictionary {
+                    guild_id,
+                    dictionary: Box::new(g.dictionary.clone()),
+                })
+            }
+        }
+    }
+
+    /// Put the record at `key` back to `original` — a value previously taken
+    /// with [`Self::row`]. `None` removes the record, undoing an insert.
+    pub fn restore(&mut self, key: RowKey, original: Option<DbRow>) {
+        match (key, original) {
+            (_, Some(DbRow::User { user_id, user })) => {
+                self.db.insert(user_id, *user);
+            }
+            (
+                _,
+                Some(DbRow::GuildUser {
+                    guild_id,
+                    user_id,
+                    user,
+                }),
+            ) => {
+                self.guilds.entry(guild_id).or_default().db.insert(user_id, *user);
+            }
+            (_, Some(DbRow::GuildConfig { guild_id, config })) => {
+                self.guilds.entry(guild_id).or_default().config = *config;
+            }
+            (
+                _,
+                Some(DbRow::GuildPermissions {
+                    guild_id,
+                    permissions,
+                }),
+            ) => {
+                self.guilds.entry(guild_id).or_default().permissions = *permissions;
+            }
+            (
+                _,
+                Some(DbRow::GuildDictionary {
+                    guild_id,
+                    dictionary,
+                }),
+            ) => {
+                self.guilds.entry(guild_id).or_default().dictionary = *dictionary;
+            }
+            // Ledger lines are only produced on commit, so there is never
+            // one to restore.
+            (_, Some(DbRow::Ledger { .. })) => {}
+            (RowKey::User(user_id), None) => {
+                self.db.remove(&user_id);
+            }
+            (RowKey::GuildUser(guild_id, user_id), None) => {
+                if let Some(guild) = self.guilds.get_mut(&guild_id) {
+                    guild.db.remove(&user_id);
+                }
+            }
+            (RowKey::GuildConfig(guild_id), None) => {
+                if let Some(guild) = self.guilds.get_mut(&guild_id) {
+                    guild.config = Default::default();
+                }
+            }
+            (RowKey::GuildPermissions(guild_id), None) => {
+                if let Some(guild) = self.guilds.get_mut(&guild_id) {
+                    guild.permissions = Default::default();
+                }
+            }
+            (RowKey::GuildDictionary(guild_id), None) => {
+                if let Some(guild) = self.guilds.get_mut(&guild_id) {
+                    guild.dictionary = Default::default();
+                }
+            }
+        }
+    }
+
+    /// Collect every scheduled event that still has a reminder to send.
     ///
     /// Called once at bot startup so the schedule reminder task can re-queue
-    /// reminders for events that survived a restart.
+    /// reminders for events that survived a restart. Each event picks up
+    /// after its last sent reminder, so nothing is sent twice.
     pub fn get_events(&self) -> Vec<(UserId, ScheduleEvent)> {
         self.db
             .iter()
-            .flat_map(|(id, user)| user.schedule.events.iter().map(|ev| (*id, ev.clone())))
+            .flat_map(|(id, user)| {
+                user.schedule
+                    .events
+                    .iter()
+                    .filter(|ev| ev.next_reminder().is_some())
+                    .map(|ev| (*id, ev.clone()))
+            })
             .collect()
     }
 }
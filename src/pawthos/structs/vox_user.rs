@@ -0,0 +1,13 @@
+//! Per-user state for the `/vox` command suite.
+
+use crate::dectalk::Voice;
+use serde::{Deserialize, Serialize};
+
+/// All vox-related state for a single user.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct VoxUser {
+    /// Voice saved with `/vox voice`, used by `/vox say` for any setting not
+    /// given as an option.
+    #[serde(default)]
+    pub voice: Voice,
+}
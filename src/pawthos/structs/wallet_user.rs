@@ -3,8 +3,8 @@
 use chrono::{Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 
-use crate::pawthos::consts::{DAILY_REWARD, MAX_STREAK_BONUS};
 use crate::pawthos::enums::wallet_errors::WalletError;
+use crate::pawthos::structs::guild_config::GuildConfig;
 
 /// The result of a successful `/daily` claim.
 ///
@@ -15,6 +15,8 @@ pub struct DailyClaimResult {
     pub balance: i64,
     /// How many tabs were awarded this time (base + streak bonus).
     pub reward: i64,
+    /// The streak-bonus part of `reward`.
+    pub bonus: i64,
     /// The user's current consecutive-day streak after this claim.
     pub current_streak: u32,
 }
@@ -22,7 +24,8 @@ pub struct DailyClaimResult {
 /// All wallet-related state for a single user.
 ///
 /// "Tabs" are the in-server currency. Users earn them via `/daily` (with a
-/// streak bonus up to [`MAX_STREAK_BONUS`]) and the tab-reaction faucet, and
+/// streak bonus up to the guild's `max_streak_bonus`) and the tab-reaction
+/// faucet, and
 /// spend them in the `/shop` and on per-set cosmetics like `/profile set
 /// banner` or `/shop buy rolecolor`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -44,6 +47,16 @@ pub struct WalletUser {
     /// `None` if they have never claimed.
     #[serde(default)]
     pub last_claim_date: Option<NaiveDate>,
+
+    /// Tabs sent with `/pay` on [`Self::pay_date`], fee excluded. Counts
+    /// towards the guild's `pay_daily_cap`.
+    #[serde(default)]
+    pub paid_today: i64,
+
+    /// The local date `paid_today` refers to. `None` if the user has never
+    /// paid anyone.
+    #[serde(default)]
+    pub pay_date: Option<NaiveDate>,
 }
 
 impl WalletUser {
@@ -69,15 +82,55 @@ impl WalletUser {
         }
     }
 
+    /// How many more tabs the user may send with `/pay` today, out of a
+    /// daily `cap`.
+    pub fn pay_allowance(&self, cap: i64) -> i64 {
+        if self.pay_date == Some(Local::now().date_naive()) {
+            (cap - self.paid_today).max(0)
+        } else {
+            cap
+        }
+    }
+
+    /// Count `amount` against today's outbound `/pay` cap.
+    ///
+    /// Returns [`WalletError::PayCapExceeded`] — leaving the counter unchanged
+    /// — if it would take the user past `cap`. Does not touch the balance;
+    /// the caller debits separately.
+    pub fn use_pay_allowance(&mut self, amount: i64, cap: i64) -> Result<(), WalletError> {
+        let remaining = self.pay_allowance(cap);
+        if amount > remaining {
+            return Err(WalletError::PayCapExceeded { cap, remaining });
+        }
+
+        let today = Local::now().date_naive();
+        if self.pay_date != Some(today) {
+            self.pay_date = Some(today);
+            self.paid_today = 0;
+        }
+        self.paid_today += amount;
+        Ok(())
+    }
+
+    /// Fold a `legacy` global wallet from before economies were split per
+    /// guild into this one: balances add up, and the longer streak and the
+    /// later claim are kept. Today's `/pay` allowance stays this wallet's.
+    pub fn merge(&mut self, legacy: WalletUser) {
+        self.tabs += legacy.tabs;
+        self.current_streak = self.current_streak.max(legacy.current_streak);
+        self.last_claim_date = self.last_claim_date.max(legacy.last_claim_date);
+    }
+
     /// Grant the daily reward with streak tracking.
     ///
     /// - If the user claimed yesterday, the streak increments.
     /// - Otherwise the streak resets to 1.
-    /// - Bonus tabs scale with the streak up to [`MAX_STREAK_BONUS`].
+    /// - The guild's `daily_reward` is paid, plus one bonus tab per streak
+    ///   day after the first, up to its `max_streak_bonus`.
     ///
     /// Should only be called after confirming the user hasn't already claimed
     /// today (see [`crate::pawthos::structs::data::Data::wallet_user_daily`]).
-    pub fn claim_daily(&mut self) -> DailyClaimResult {
+    pub fn claim_daily(&mut self, config: &GuildConfig) -> DailyClaimResult {
         let today = Local::now().date_naive();
 
         self.current_streak = match self.last_claim_date {
@@ -89,13 +142,14 @@ impl WalletUser {
 
         self.last_claim_date = Some(today);
 
-        let bonus = (self.current_streak as i64 - 1).min(MAX_STREAK_BONUS);
-        let reward = DAILY_REWARD + bonus;
+        let bonus = (self.current_streak as i64 - 1).min(config.max_streak_bonus());
+        let reward = config.daily_reward() + bonus;
         self.tabs += reward;
 
         DailyClaimResult {
             balance: self.tabs,
             reward,
+            bonus,
             current_streak: self.current_streak,
         }
     }
@@ -8,23 +8,28 @@
 //! sub-struct does this marker type refer to?".
 //!
 //! The [`impl_user_db_spec!`] macro then generates the boilerplate `impl`
-//! blocks from a single line each.
+//! blocks from a single line each. Economy sub-structs (wallet, inventory)
+//! are scoped per guild and live on [`super::structs::guild_user::GuildUser`];
+//! their markers use [`impl_guild_db_spec!`] instead, which keys lookups by
+//! `(GuildId, UserId)` rather than `UserId` alone.
 //!
 //! # Adding a new feature
 //!
 //! 1. Add `pub struct NewFeatureDbMarker;` below the existing markers.
-//! 2. Add one `impl_user_db_spec!(NewFeatureDbMarker, NewFeatureUser, new_field);` call.
+//! 2. Add one `impl_user_db_spec!(NewFeatureDbMarker, NewFeatureUser, new_field);` call
+//!    (or `impl_guild_db_spec!` if the state belongs to a guild's economy).
 //! 3. Add one `def_db_access!(...)` call in [`super::structs::data`].
 
-use crate::pawthos::enums::persistent_data::PersistentData;
+use crate::pawthos::enums::persistent_data::RowKey;
 use crate::pawthos::structs::data::Data;
 use crate::pawthos::structs::inventory_user::InventoryUser;
 use crate::pawthos::structs::mimic_user::MimicUser;
 use crate::pawthos::structs::profile_user::ProfileUser;
 use crate::pawthos::structs::schedule_user::ScheduleUser;
 use crate::pawthos::structs::user_db::UserDB;
+use crate::pawthos::structs::vox_user::VoxUser;
 use crate::pawthos::structs::wallet_user::WalletUser;
-use poise::serenity_prelude::UserId;
+use poise::serenity_prelude::{GuildId, UserId};
 use tokio::sync::RwLock;
 
 // ---------------------------------------------------------------------------
@@ -37,15 +42,20 @@ pub struct MimicDbMarker;
 /// Marker type that routes generic DB operations to the schedule sub-struct.
 pub struct ScheduleDbMarker;
 
-/// Marker type that routes generic DB operations to the wallet sub-struct.
+/// Marker type that routes generic DB operations to the (guild-scoped)
+/// wallet sub-struct.
 pub struct WalletDbMarker;
 
 /// Marker type that routes generic DB operations to the profile sub-struct.
 pub struct ProfileDbMarker;
 
-/// Marker type that routes generic DB operations to the inventory sub-struct.
+/// Marker type that routes generic DB operations to the (guild-scoped)
+/// inventory sub-struct.
 pub struct InventoryDbMarker;
 
+/// Marker type that routes generic DB operations to the vox sub-struct.
+pub struct VoxDbMarker;
+
 // ---------------------------------------------------------------------------
 // Trait definition
 // ---------------------------------------------------------------------------
@@ -60,6 +70,10 @@ pub trait UserDbSpec {
     /// The top-level database type (always [`UserDB`] for now).
     type Db: Clone;
 
+    /// What identifies one record: `UserId` for global sub-structs,
+    /// `(GuildId, UserId)` for guild-scoped ones.
+    type Key: Copy;
+
     /// The per-user sub-struct this marker routes to (e.g. `MimicUser`).
     type User;
 
@@ -68,16 +82,17 @@ pub trait UserDbSpec {
 
     /// Look up an immutable reference to the user's sub-struct.
     ///
-    /// Returns `None` if the user has never interacted with the bot.
-    fn get_user(db: &Self::Db, user_id: UserId) -> Option<&Self::User>;
+    /// Returns `None` if the user has never interacted with the bot (or,
+    /// for guild-scoped markers, with that guild's economy).
+    fn get_user(db: &Self::Db, key: Self::Key) -> Option<&Self::User>;
 
     /// Look up a mutable reference to the user's sub-struct, inserting a
     /// default entry if the user is new.
-    fn get_user_mut(db: &mut Self::Db, user_id: UserId) -> &mut Self::User;
+    fn get_user_mut(db: &mut Self::Db, key: Self::Key) -> &mut Self::User;
 
-    /// Wrap a database snapshot into the correct [`PersistentData`] variant
-    /// so the persistence task knows which file to write.
-    fn to_persistent_data(db: Self::Db) -> PersistentData;
+    /// Which record of the database `key` lives in, so writes can persist
+    /// (or roll back) just that row.
+    fn row_key(key: Self::Key) -> RowKey;
 }
 
 // ---------------------------------------------------------------------------
@@ -100,6 +115,7 @@ macro_rules! impl_user_db_spec {
     ($marker:ident, $user_type:ty, $field:ident) => {
         impl UserDbSpec for $marker {
             type Db = UserDB;
+            type Key = UserId;
             type User = $user_type;
 
             fn db_lock(data: &Data) -> &RwLock<Self::Db> {
@@ -114,8 +130,44 @@ macro_rules! impl_user_db_spec {
                 &mut db.get_user_mut(user_id).$field
             }
 
-            fn to_persistent_data(db: Self::Db) -> PersistentData {
-                PersistentData::UserDB(db)
+            fn row_key(user_id: UserId) -> RowKey {
+                RowKey::User(user_id)
+            }
+        }
+    };
+}
+
+/// Generate a guild-scoped [`UserDbSpec`] implementation for a marker type.
+///
+/// Identical to [`impl_user_db_spec!`] except that `$field` names a field on
+/// [`super::structs::guild_user::GuildUser`] and records are keyed by
+/// `(GuildId, UserId)`.
+///
+/// # Usage
+/// ```ignore
+/// impl_guild_db_spec!(WalletDbMarker, WalletUser, wallet);
+/// ```
+macro_rules! impl_guild_db_spec {
+    ($marker:ident, $user_type:ty, $field:ident) => {
+        impl UserDbSpec for $marker {
+            type Db = UserDB;
+            type Key = (GuildId, UserId);
+            type User = $user_type;
+
+            fn db_lock(data: &Data) -> &RwLock<Self::Db> {
+                &data.user_db
+            }
+
+            fn get_user(db: &Self::Db, (guild_id, user_id): Self::Key) -> Option<&Self::User> {
+                db.get_guild_user(guild_id, user_id).map(|u| &u.$field)
+            }
+
+            fn get_user_mut(db: &mut Self::Db, (guild_id, user_id): Self::Key) -> &mut Self::User {
+                &mut db.get_guild_user_mut(guild_id, user_id).$field
+            }
+
+            fn row_key((guild_id, user_id): Self::Key) -> RowKey {
+                RowKey::GuildUser(guild_id, user_id)
             }
         }
     };
@@ -127,6 +179,7 @@ macro_rules! impl_user_db_spec {
 
 impl_user_db_spec!(MimicDbMarker, MimicUser, mimic);
 impl_user_db_spec!(ScheduleDbMarker, ScheduleUser, schedule);
-impl_user_db_spec!(WalletDbMarker, WalletUser, wallet);
 impl_user_db_spec!(ProfileDbMarker, ProfileUser, profile);
-impl_user_db_spec!(InventoryDbMarker, InventoryUser, inventory);
+impl_user_db_spec!(VoxDbMarker, VoxUser, vox);
+impl_guild_db_spec!(WalletDbMarker, WalletUser, wallet);
+impl_guild_db_spec!(InventoryDbMarker, InventoryUser, inventory);
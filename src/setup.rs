@@ -2,8 +2,8 @@
 //!
 //! This module re-exports the two primary setup functions so that `main.rs`
 //! only needs a single `use crate::setup::*`-style import. It also owns the
-//! [`APIKey`] deserialisation and the [`get_api_token`] helper that reads
-//! the bot token from `s.json`.
+//! [`APIKey`] deserialisation and the [`load_api_key`] helper that reads
+//! the bot token and owners from `s.json`.
 
 pub use crate::framework::setup_framework;
 pub use crate::logging::setup_logging;
@@ -14,7 +14,7 @@ use poise::serenity_prelude as serenity;
 /// Shape of the `s.json` secrets file.
 ///
 /// ```json
-/// { "token": "your-discord-bot-token-here" }
+/// { "token": "your-discord-bot-token-here", "owners": [326389428593623040] }
 /// ```
 ///
 /// Keep this file out of version control — it contains the bot's Discord token.
@@ -22,18 +22,22 @@ use poise::serenity_prelude as serenity;
 pub struct APIKey {
     /// The Discord bot token used to authenticate with the Gateway.
     pub token: String,
+
+    /// User IDs with owner permission (see [`crate::checks`]), on top of
+    /// whoever owns the bot's Discord application. Optional.
+    #[serde(default)]
+    pub owners: Vec<serenity::UserId>,
 }
 
-/// Read the Discord bot token from `s.json` in the working directory.
+/// Read the Discord bot token and owner list from `s.json` in the working
+/// directory.
 ///
 /// # Panics
 /// Panics (via [`ResultExt::unwrap_or_log`]) if the file is missing or
 /// contains invalid JSON. Both conditions are unrecoverable at startup.
-pub fn get_api_token() -> String {
+pub fn load_api_key() -> APIKey {
     let file_contents =
-        std::fs::read_to_string("s.json").unwrap_or_log("setup::get_api_token::read_to_string");
+        std::fs::read_to_string("s.json").unwrap_or_log("setup::load_api_key::read_to_string");
 
-    let api_key: APIKey =
-        serenity::json::from_str(file_contents).unwrap_or_log("setup::json::from_str");
-    api_key.token
+    serenity::json::from_str(file_contents).unwrap_or_log("setup::json::from_str")
 }
@@ -0,0 +1,225 @@
+//! The original JSON snapshot backend.
+//!
+//! Keeps a mirror of the whole [`UserDB`] and rewrites `user.json` in full on
+//! every change. Daily claims live in `wallet_list.json`. Simple and easy to
+//! inspect by hand, but each write costs time proportional to the size of the
+//! whole database — see [`super::sqlite`] for the alternative.
+
+use super::UserStore;
+use crate::pawthos::enums::persistent_data::{DbRow, UserDailyClaimed};
+use crate::pawthos::enums::store_errors::StoreError;
+use crate::pawthos::structs::user_db::UserDB;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const USER_DB_PATH: &str = "user.json";
+const DAILY_CLAIM_LOG_PATH: &str = "wallet_list.json";
+
+/// [`UserStore`] backed by `user.json` and `wallet_list.json` in the working
+/// directory.
+#[derive(Debug, Default)]
+pub struct JsonStore {
+    /// Directory holding both files. Empty, the default, for the working
+    /// directory.
+    dir: PathBuf,
+
+    /// Copy of the database as last written, so changed rows can be folded
+    /// in before the full rewrite.
+    db: UserDB,
+}
+
+impl UserStore for JsonStore {
+    fn load(&mut self) -> Result<UserDB, StoreError> {
+        self.db = load_user_db(&self.dir);
+        Ok(self.db.clone())
+    }
+
+    fn save_all(&mut self, db: &UserDB) -> Result<(), StoreError> {
+        self.db = db.clone();
+        save_user_db(&self.dir, &self.db)
+    }
+
+    fn save_rows(&mut self, rows: Vec<DbRow>) -> Result<(), StoreError> {
+        for row in rows {
+            match row {
+                DbRow::User { user_id, user } => {
+                    self.db.db.insert(user_id, *user);
+                }
+                DbRow::GuildUser {
+                    guild_id,
+                    user_id,
+                    user,
+                } => {
+                    self.db
+                        .guilds
+                        .entry(guild_id)
+                        .or_default()
+                        .db
+                        .insert(user_id, *user);
+                }
+                DbRow::Ledger { guild_id, entry } => {
+                    self.db.guilds.entry(guild_id).or_default().ledger.push(*entry);
+                }
+                DbRow::GuildConfig { guild_id, config } => {
+                    self.db.guilds.entry(guild_id).or_default().config = *config;
+                }
+                DbRow::GuildPermissions {
+                    guild_id,
+                    permissions,
+                } => {
+                    self.db.guilds.entry(guild_id).or_default().permissions = *permissions;
+                }
+                DbRow::GuildDictionary {
+                    guild_id,
+                    dictionary,
+                } => {
+                    self.db.guilds.entry(guild_id).or_default().dictionary = *dictionary;
+                }
+            }
+        }
+        save_user_db(&self.dir, &self.db)
+    }
+
+    fn daily_check(
+        &mut self,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<UserDailyClaimed, StoreError> {
+        let mut claim_log = load_daily_claim_log(&self.dir)?;
+
+        let today = chrono::Local::now().date_naive();
+        if claim_log.date < today {
+            claim_log.guilds.clear();
+            claim_log.date = today;
+        }
+
+        let list = claim_log.guilds.entry(guild_id).or_default();
+        let result = if list.contains(&user_id) {
+            UserDailyClaimed::Claimed
+        } else {
+            list.push(user_id);
+            UserDailyClaimed::Unclaimed
+        };
+
+        save_daily_claim_log(&self.dir, &claim_log)?;
+        Ok(result)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// User DB
+// ---------------------------------------------------------------------------
+
+/// Write `db` to `user.json` in `dir` atomically (write to `.tmp`, then
+/// rename).
+///
+/// The atomic rename prevents a partially-written file from corrupting the
+/// database if the process is killed mid-write.
+fn save_user_db(dir: &Path, db: &UserDB) -> Result<(), StoreError> {
+    let db_json = serde_json::to_string(db)?;
+    let path = dir.join(USER_DB_PATH);
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, &db_json)?;
+    std::fs::rename(&tmp_path, &path)?;
+    log::debug!("{USER_DB_PATH} saved :3c");
+    Ok(())
+}
+
+/// Load the user database from `user.json` in `dir`.
+///
+/// Falls back to an empty [`UserDB`] if the file is absent or malformed,
+/// logging a warning/error accordingly so the operator knows what happened.
+fn load_user_db(dir: &Path) -> UserDB {
+    let user_db =
+        std::fs::read_to_string(dir.join(USER_DB_PATH)).map(|s| serde_json::from_str::<UserDB>(&s));
+
+    match user_db {
+        Ok(Ok(db)) => {
+            log::info!("{USER_DB_PATH} found, importing db..");
+            db
+        }
+        Ok(Err(e)) => {
+            log::error!(
+                "{USER_DB_PATH} exists but deserialization failed: {e}. Starting with empty DB."
+            );
+            Default::default()
+        }
+        Err(_) => {
+            log::warn!("{USER_DB_PATH} NOT found, making new db..");
+            Default::default()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Daily claim log
+// ---------------------------------------------------------------------------
+
+/// The daily-claim tracking record.
+///
+/// Persisted as `wallet_list.json` (filename retained for compatibility with
+/// existing deployments). Resets automatically when [`DailyClaimLog::date`]
+/// falls behind the current local date — no cron job or scheduled reset is
+/// needed.
+///
+/// Logs written before guild scoping only carry the old flat `list`; serde
+/// drops it, which at worst lets those users claim once more that day.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DailyClaimLog {
+    /// The date for which `guilds` was last updated.
+    pub date: chrono::NaiveDate,
+
+    /// Raw guild ID → raw user IDs of users who have already claimed today
+    /// in that guild.
+    #[serde(default)]
+    pub guilds: HashMap<u64, Vec<u64>>,
+}
+
+/// Write the daily-claim log to `dir` atomically.
+fn save_daily_claim_log(dir: &Path, claim_log: &DailyClaimLog) -> Result<(), StoreError> {
+    let claim_log_json = serde_json::to_string(claim_log)?;
+    let path = dir.join(DAILY_CLAIM_LOG_PATH);
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, &claim_log_json)?;
+    std::fs::rename(&tmp_path, &path)?;
+    log::debug!("{DAILY_CLAIM_LOG_PATH} saved :3c");
+    Ok(())
+}
+
+/// Load the daily-claim log from `dir`, returning an empty log on
+/// missing/corrupt file.
+pub fn load_daily_claim_log(dir: &Path) -> Result<DailyClaimLog, StoreError> {
+    let claim_log = std::fs::read_to_string(dir.join(DAILY_CLAIM_LOG_PATH))
+        .map(|s| serde_json::from_str::<DailyClaimLog>(&s));
+
+    match claim_log {
+        Ok(Ok(db)) => {
+            log::info!("{DAILY_CLAIM_LOG_PATH} found, importing..");
+            Ok(db)
+        }
+        Ok(Err(e)) => {
+            log::error!(
+                "{DAILY_CLAIM_LOG_PATH} exists but deserialization failed: {e}. Starting fresh."
+            );
+            Ok(Default::default())
+        }
+        Err(_) => {
+            log::warn!("{DAILY_CLAIM_LOG_PATH} NOT found, making new..");
+            Ok(Default::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        crate::store::tests::assert_round_trips("json-store", |dir| JsonStore {
+            dir: dir.to_path_buf(),
+            ..Default::default()
+        });
+    }
+}
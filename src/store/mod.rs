@@ -0,0 +1,199 @@
+//! Pluggable storage backends for the user database.
+//!
+//! The in-memory [`UserDB`] inside [`crate::pawthos::structs::data::Data`] is
+//! always the source of truth while the bot runs. A [`UserStore`] is what the
+//! persistence task in [`crate::framework`] hands each changed row to, and
+//! what the database is loaded from at startup.
+//!
+//! | Backend | Module | Files | Cost per write |
+//! |---|---|---|---|
+//! | [`StoreKind::Json`] | [`json`] | `user.json`, `wallet_list.json` | Rewrites the whole file |
+//! | [`StoreKind::Sqlite`] | [`sqlite`] | `--sqlite-path` (default `logos.db`) | Upserts the changed rows |
+//!
+//! Pick one with `--store json|sqlite`. Existing JSON data can be copied into
+//! a SQLite database once with `--import-json` (see [`import_json`]).
+
+use crate::pawthos::enums::persistent_data::{DbRow, UserDailyClaimed};
+use crate::pawthos::enums::store_errors::StoreError;
+use crate::pawthos::structs::user_db::UserDB;
+use std::path::Path;
+
+pub mod json;
+pub mod sqlite;
+
+/// Which [`UserStore`] implementation to run with. Selected by `--store`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StoreKind {
+    /// `user.json` + `wallet_list.json` in the working directory.
+    Json,
+    /// An embedded SQLite database.
+    Sqlite,
+}
+
+/// A place the user database can be loaded from and persisted to.
+///
+/// Every method is synchronous: stores are only ever driven from the single
+/// persistence task, one message at a time, so no two calls overlap.
+pub trait UserStore: Send {
+    /// Load the full database. Called once at startup.
+    ///
+    /// A store with nothing in it yet returns an empty [`UserDB`] rather than
+    /// an error.
+    fn load(&mut self) -> Result<UserDB, StoreError>;
+
+    /// Replace everything in the store with `db`.
+    ///
+    /// Used after startup migrations have rewritten records, and by
+    /// [`import_json`].
+    fn save_all(&mut self, db: &UserDB) -> Result<(), StoreError>;
+
+    /// Persist a batch of changed records.
+    ///
+    /// The batch comes from a single write (see
+    /// [`crate::pawthos::structs::data::Data::transact`]) and must be saved
+    /// all-or-nothing where the backend allows it.
+    fn save_rows(&mut self, rows: Vec<DbRow>) -> Result<(), StoreError>;
+
+    /// Check whether `user_id` has already claimed their daily reward today
+    /// in `guild_id`, and if not, mark them as having claimed it.
+    ///
+    /// The daily window resets at midnight local time.
+    fn daily_check(&mut self, guild_id: u64, user_id: u64)
+    -> Result<UserDailyClaimed, StoreError>;
+}
+
+/// Open the backend selected by `kind`.
+///
+/// `sqlite_path` is ignored for [`StoreKind::Json`].
+pub fn open(kind: StoreKind, sqlite_path: &Path) -> Result<Box<dyn UserStore>, StoreError> {
+    Ok(match kind {
+        StoreKind::Json => Box::new(json::JsonStore::default()),
+        StoreKind::Sqlite => Box::new(sqlite::SqliteStore::open(sqlite_path)?),
+    })
+}
+
+/// Copy `user.json` and `wallet_list.json` into the SQLite database at
+/// `sqlite_path`.
+///
+/// Any rows already in the SQLite database are replaced. Today's daily
+/// claims carry over, so nobody can claim twice on the day of the switch.
+/// Records are copied verbatim; startup migrations run on the next normal
+/// launch as usual.
+pub fn import_json(sqlite_path: &Path) -> Result<(), StoreError> {
+    let mut json = json::JsonStore::default();
+    let db = json.load()?;
+    let claims = json::load_daily_claim_log(Path::new(""))?;
+
+    let mut sqlite = sqlite::SqliteStore::open(sqlite_path)?;
+    sqlite.save_all(&db)?;
+    sqlite.import_daily_claims(claims.date, &claims.guilds)?;
+
+    log::info!(
+        "Imported {} user(s) and {} guild(s) into {}.",
+        db.db.len(),
+        db.guilds.len(),
+        sqlite_path.display(),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pawthos::enums::ledger_reason::LedgerReason;
+    use crate::pawthos::enums::permission_level::StaffLevel;
+    use crate::pawthos::enums::persistent_data::RowKey;
+    use crate::pawthos::structs::guild_config::ConfigKey;
+    use crate::pawthos::structs::ledger_entry::TabMemo;
+    use crate::pawthos::structs::transaction::Transaction;
+    use poise::serenity_prelude::{GuildId, RoleId, UserId};
+    use std::path::PathBuf;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const ALICE: UserId = UserId::new(10);
+    const BOB: UserId = UserId::new(20);
+
+    /// A fresh directory under the system temp dir, removed on drop.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("logos-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        /// A new empty directory inside this one.
+        fn dir(&self, name: &str) -> PathBuf {
+            let dir = self.0.join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            dir
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// A database with every kind of row in it, and the rows that built it.
+    fn sample() -> (UserDB, Vec<DbRow>) {
+        let mut db = UserDB::default();
+        let mut tx = Transaction::new(&mut db);
+        tx.credit(GUILD, ALICE, 120, TabMemo::new(LedgerReason::Daily));
+        let transfer = TabMemo::new(LedgerReason::Transfer);
+        tx.debit(GUILD, ALICE, 20, transfer.clone().counterparty(BOB))
+            .unwrap();
+        tx.credit(GUILD, BOB, 20, transfer.counterparty(ALICE));
+        tx.profile(ALICE).bio = Some("hello".into());
+        let mut rows = tx.commit();
+
+        let guild = db.guilds.get_mut(&GUILD).unwrap();
+        guild.config.set(ConfigKey::PayFeePercent, "7").unwrap();
+        guild
+            .permissions
+            .grant(RoleId::new(5), StaffLevel::Admin)
+            .unwrap();
+        guild
+            .dictionary
+            .add("logos".into(), "l 'ow g ow s".into())
+            .unwrap();
+        for key in [
+            RowKey::GuildConfig(GUILD),
+            RowKey::GuildPermissions(GUILD),
+            RowKey::GuildDictionary(GUILD),
+        ] {
+            rows.extend(db.row(key));
+        }
+        (db, rows)
+    }
+
+    fn json(db: &UserDB) -> serde_json::Value {
+        serde_json::to_value(db).unwrap()
+    }
+
+    /// Save [`sample`] with `save_all`, and separately with `save_rows`,
+    /// through stores made by `open`, and check a fresh store loads it back
+    /// unchanged each time.
+    pub(super) fn assert_round_trips<S: UserStore>(name: &str, open: impl Fn(&Path) -> S) {
+        let scratch = Scratch::new(name);
+        let (db, rows) = sample();
+
+        let all = scratch.dir("all");
+        open(&all).save_all(&db).unwrap();
+        assert_eq!(json(&open(&all).load().unwrap()), json(&db), "save_all");
+
+        let batched = scratch.dir("rows");
+        let mut store = open(&batched);
+        store.load().unwrap();
+        store.save_rows(rows).unwrap();
+        drop(store);
+        assert_eq!(
+            json(&open(&batched).load().unwrap()),
+            json(&db),
+            "save_rows"
+        );
+    }
+}
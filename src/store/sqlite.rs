@@ -0,0 +1,363 @@
+//! Embedded SQLite backend.
+//!
+//! Each [`DbRow`] maps to one row of a table, so a write only touches the
+//! records that changed, and a batch from one transaction commits together.
+//! Records are stored as JSON text in a `data` column, which keeps schema
+//! evolution identical to the JSON backend: new fields
+//! are picked up through the same `#[serde(default)]` attributes, and no SQL
+//! migration is needed when a sub-struct grows.
+//!
+//! # Tables
+//!
+//! | Table | Key | Contents |
+//! |---|---|---|
+//! | `users` | `user_id` | [`crate::pawthos::structs::user::User`] as JSON |
+//! | `guild_users` | `(guild_id, user_id)` | [`crate::pawthos::structs::guild_user::GuildUser`] as JSON |
+//! | `daily_claims` | `(guild_id, user_id)` | Local date of the member's last `/daily` claim |
+//! | `ledger` | `id` (insertion order) | [`crate::pawthos::structs::ledger_entry::LedgerEntry`] as JSON, with its `guild_id` |
+//! | `guild_configs` | `guild_id` | [`crate::pawthos::structs::guild_config::GuildConfig`] as JSON |
+//! | `guild_permissions` | `guild_id` | [`crate::pawthos::structs::guild_permissions::GuildPermissions`] as JSON |
+//! | `guild_dictionaries` | `guild_id` | [`crate::pawthos::structs::guild_dictionary::GuildDictionary`] as JSON |
+//!
+//! Discord IDs are snowflakes below 2⁶³, so they round-trip through SQLite's
+//! signed `INTEGER` unchanged.
+
+use super::UserStore;
+use crate::pawthos::enums::persistent_data::{DbRow, UserDailyClaimed};
+use crate::pawthos::enums::store_errors::StoreError;
+use crate::pawthos::structs::user_db::UserDB;
+use chrono::NaiveDate;
+use poise::serenity_prelude::{GuildId, UserId};
+use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS users (
+    user_id INTEGER PRIMARY KEY,
+    data    TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS guild_users (
+    guild_id INTEGER NOT NULL,
+    user_id  INTEGER NOT NULL,
+    data     TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+CREATE TABLE IF NOT EXISTS daily_claims (
+    guild_id INTEGER NOT NULL,
+    user_id  INTEGER NOT NULL,
+    date     TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+CREATE TABLE IF NOT EXISTS ledger (
+    id       INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id INTEGER NOT NULL,
+    data     TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS guild_configs (
+    guild_id INTEGER PRIMARY KEY,
+    data     TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS guild_permissions (
+    guild_id INTEGER PRIMARY KEY,
+    data     TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS guild_dictionaries (
+    guild_id INTEGER PRIMARY KEY,
+    data     TEXT NOT NULL
+);
+";
+
+const UPSERT_USER: &str = "
+INSERT INTO users (user_id, data) VALUES (?1, ?2)
+ON CONFLICT (user_id) DO UPDATE SET data = excluded.data";
+
+const UPSERT_GUILD_USER: &str = "
+INSERT INTO guild_users (guild_id, user_id, data) VALUES (?1, ?2, ?3)
+ON CONFLICT (guild_id, user_id) DO UPDATE SET data = excluded.data";
+
+const INSERT_LEDGER: &str = "INSERT INTO ledger (guild_id, data) VALUES (?1, ?2)";
+
+const UPSERT_GUILD_CONFIG: &str = "
+INSERT INTO guild_configs (guild_id, data) VALUES (?1, ?2)
+ON CONFLICT (guild_id) DO UPDATE SET data = excluded.data";
+
+const UPSERT_GUILD_PERMISSIONS: &str = "
+INSERT INTO guild_permissions (guild_id, data) VALUES (?1, ?2)
+ON CONFLICT (guild_id) DO UPDATE SET data = excluded.data";
+
+const UPSERT_GUILD_DICTIONARY: &str = "
+INSERT INTO guild_dictionaries (guild_id, data) VALUES (?1, ?2)
+ON CONFLICT (guild_id) DO UPDATE SET data = excluded.data";
+
+/// Inserts a claim, or moves an older claim forward to today. Reports zero
+/// changed rows when the member has already claimed today.
+const CLAIM_DAILY: &str = "
+INSERT INTO daily_claims (guild_id, user_id, date) VALUES (?1, ?2, ?3)
+ON CONFLICT (guild_id, user_id) DO UPDATE SET date = excluded.date
+WHERE daily_claims.date < excluded.date";
+
+/// [`UserStore`] backed by a single SQLite database file.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path` and make sure the tables
+    /// exist.
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let conn = Connection::open(path)?;
+        // WAL keeps each single-row commit cheap; NORMAL sync is durable
+        // across application crashes, which is all a chat bot needs.
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.execute_batch(SCHEMA)?;
+        log::info!("SQLite store opened at {}", path.display());
+        Ok(Self { conn })
+    }
+
+    /// Replace every daily claim with the members in `guilds`, all marked as
+    /// having claimed on `date`. Used by [`super::import_json`].
+    pub fn import_daily_claims(
+        &mut self,
+        date: NaiveDate,
+        guilds: &HashMap<u64, Vec<u64>>,
+    ) -> Result<(), StoreError> {
+        let date = date.to_string();
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM daily_claims", [])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO daily_claims (guild_id, user_id, date) VALUES (?1, ?2, ?3)",
+            )?;
+            for (guild_id, users) in guilds {
+                for user_id in users {
+                    insert.execute(params![*guild_id as i64, *user_id as i64, date])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl UserStore for SqliteStore {
+    fn load(&mut self) -> Result<UserDB, StoreError> {
+        let mut db = UserDB::default();
+
+        let mut stmt = self.conn.prepare("SELECT user_id, data FROM users")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let user_id = UserId::new(row.get::<_, i64>(0)? as u64);
+            let data: String = row.get(1)?;
+            db.db.insert(user_id, serde_json::from_str(&data)?);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT guild_id, user_id, data FROM guild_users")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let guild_id = GuildId::new(row.get::<_, i64>(0)? as u64);
+            let user_id = UserId::new(row.get::<_, i64>(1)? as u64);
+            let data: String = row.get(2)?;
+            db.guilds
+                .entry(guild_id)
+                .or_default()
+                .db
+                .insert(user_id, serde_json::from_str(&data)?);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT guild_id, data FROM ledger ORDER BY id")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let guild_id = GuildId::new(row.get::<_, i64>(0)? as u64);
+            let data: String = row.get(1)?;
+            db.guilds
+                .entry(guild_id)
+                .or_default()
+                .ledger
+                .push(serde_json::from_str(&data)?);
+        }
+
+        let mut stmt = self.conn.prepare("SELECT guild_id, data FROM guild_configs")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let guild_id = GuildId::new(row.get::<_, i64>(0)? as u64);
+            let data: String = row.get(1)?;
+            db.guilds.entry(guild_id).or_default().config = serde_json::from_str(&data)?;
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT guild_id, data FROM guild_permissions")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let guild_id = GuildId::new(row.get::<_, i64>(0)? as u64);
+            let data: String = row.get(1)?;
+            db.guilds.entry(guild_id).or_default().permissions = serde_json::from_str(&data)?;
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT guild_id, data FROM guild_dictionaries")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let guild_id = GuildId::new(row.get::<_, i64>(0)? as u64);
+            let data: String = row.get(1)?;
+            db.guilds.entry(guild_id).or_default().dictionary = serde_json::from_str(&data)?;
+        }
+
+        log::info!(
+            "Loaded {} user(s) and {} guild(s) from SQLite.",
+            db.db.len(),
+            db.guilds.len()
+        );
+        Ok(db)
+    }
+
+    fn save_all(&mut self, db: &UserDB) -> Result<(), StoreError> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM users", [])?;
+        tx.execute("DELETE FROM guild_users", [])?;
+        tx.execute("DELETE FROM ledger", [])?;
+        tx.execute("DELETE FROM guild_configs", [])?;
+        tx.execute("DELETE FROM guild_permissions", [])?;
+        tx.execute("DELETE FROM guild_dictionaries", [])?;
+        {
+            let mut upsert = tx.prepare(UPSERT_USER)?;
+            for (user_id, user) in &db.db {
+                upsert.execute(params![user_id.get() as i64, serde_json::to_string(user)?])?;
+            }
+            let mut upsert = tx.prepare(UPSERT_GUILD_USER)?;
+            for (guild_id, guild) in &db.guilds {
+                for (user_id, user) in &guild.db {
+                    upsert.execute(params![
+                        guild_id.get() as i64,
+                        user_id.get() as i64,
+                        serde_json::to_string(user)?
+                    ])?;
+                }
+            }
+            let mut insert = tx.prepare(INSERT_LEDGER)?;
+            for (guild_id, guild) in &db.guilds {
+                for entry in &guild.ledger {
+                    insert.execute(params![guild_id.get() as i64, serde_json::to_string(entry)?])?;
+                }
+            }
+            let mut upsert = tx.prepare(UPSERT_GUILD_CONFIG)?;
+            for (guild_id, guild) in &db.guilds {
+                upsert.execute(params![
+                    guild_id.get() as i64,
+                    serde_json::to_string(&guild.config)?
+                ])?;
+            }
+            let mut upsert = tx.prepare(UPSERT_GUILD_PERMISSIONS)?;
+            for (guild_id, guild) in &db.guilds {
+                upsert.execute(params![
+                    guild_id.get() as i64,
+                    serde_json::to_string(&guild.permissions)?
+                ])?;
+            }
+            let mut upsert = tx.prepare(UPSERT_GUILD_DICTIONARY)?;
+            for (guild_id, guild) in &db.guilds {
+                upsert.execute(params![
+                    guild_id.get() as i64,
+                    serde_json::to_string(&guild.dictionary)?
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn save_rows(&mut self, rows: Vec<DbRow>) -> Result<(), StoreError> {
+        let tx = self.conn.transaction()?;
+        for row in rows {
+            match row {
+                DbRow::User { user_id, user } => {
+                    tx.prepare_cached(UPSERT_USER)?.execute(params![
+                        user_id.get() as i64,
+                        serde_json::to_string(&user)?
+                    ])?;
+                }
+                DbRow::GuildUser {
+                    guild_id,
+                    user_id,
+                    user,
+                } => {
+                    tx.prepare_cached(UPSERT_GUILD_USER)?.execute(params![
+                        guild_id.get() as i64,
+                        user_id.get() as i64,
+                        serde_json::to_string(&user)?
+                    ])?;
+                }
+                DbRow::Ledger { guild_id, entry } => {
+                    tx.prepare_cached(INSERT_LEDGER)?.execute(params![
+                        guild_id.get() as i64,
+                        serde_json::to_string(&entry)?
+                    ])?;
+                }
+                DbRow::GuildConfig { guild_id, config } => {
+                    tx.prepare_cached(UPSERT_GUILD_CONFIG)?.execute(params![
+                        guild_id.get() as i64,
+                        serde_json::to_string(&config)?
+                    ])?;
+                }
+                DbRow::GuildPermissions {
+                    guild_id,
+                    permissions,
+                } => {
+                    tx.prepare_cached(UPSERT_GUILD_PERMISSIONS)?.execute(params![
+                        guild_id.get() as i64,
+                        serde_json::to_string(&permissions)?
+                    ])?;
+                }
+                DbRow::GuildDictionary {
+                    guild_id,
+                    dictionary,
+                } => {
+                    tx.prepare_cached(UPSERT_GUILD_DICTIONARY)?.execute(params![
+                        guild_id.get() as i64,
+                        serde_json::to_string(&dictionary)?
+                    ])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn daily_check(
+        &mut self,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<UserDailyClaimed, StoreError> {
+        let today = chrono::Local::now().date_naive().to_string();
+        let changed = self.conn.prepare_cached(CLAIM_DAILY)?.execute(params![
+            guild_id as i64,
+            user_id as i64,
+            today
+        ])?;
+
+        Ok(if changed == 0 {
+            UserDailyClaimed::Claimed
+        } else {
+            UserDailyClaimed::Unclaimed
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        crate::store::tests::assert_round_trips("sqlite-store", |dir| {
+            SqliteStore::open(&dir.join("logos.db")).unwrap()
+        });
+    }
+}
@@ -1,13 +1,19 @@
 //! Shared utility functions and traits.
 //!
 //! Contains the [`ResultExt`] helper trait, the standard embed builder, the
-//! reply helpers ([`reply_ok`], [`reply_info`]), and the webhook
-//! fetch-or-create helper used by the mimic feature. Errors flow through the
+//! reply helpers ([`reply_ok`], [`reply_info`]), the webhook
+//! fetch-or-create helper used by the mimic feature, and the group event
+//! announcement helpers shared by `/schedule group`, the RSVP buttons and the
+//! reminder task, and the reminder DM with its snooze buttons. Errors flow
+//! through the
 //! Poise framework's `on_error` hook in [`crate::handlers`] rather than
 //! through a `reply_err` helper.
 
-use crate::pawthos::consts::{TAB_EMOJI_ID, TAB_EMOJI_NAME};
+use crate::pawthos::consts::{GROUP_EVENT_RSVPS_SHOWN, TAB_EMOJI_ID, TAB_EMOJI_NAME};
 use crate::pawthos::enums::embed_type::EmbedType;
+use crate::pawthos::structs::{
+    group_event::Rsvp, schedule_event::ScheduleEvent, snooze::ReminderButton,
+};
 use crate::pawthos::types::{Embed, Error, Reply};
 use poise::serenity_prelude as serenity;
 use serenity::{EmojiId, ReactionType, Webhook};
@@ -154,3 +160,151 @@ pub async fn get_or_create_webhook(
         .await?;
     Ok(hook)
 }
+
+// ---------------------------------------------------------------------------
+// Group event announcements
+// ---------------------------------------------------------------------------
+
+/// What a group event announcement says about the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Announcement {
+    /// Upcoming; the join/leave buttons are live.
+    Open,
+    /// The last occurrence has been reminded.
+    Started,
+    Cancelled,
+}
+
+/// The embed and buttons of a group event announcement.
+///
+/// `role` and `rsvps` come from the event's
+/// [`crate::pawthos::structs::group_event::GroupEvent`], passed separately
+/// so the announcement can be rendered before it's posted. Only an
+/// [`Announcement::Open`] announcement has buttons.
+pub fn group_announcement(
+    event: &ScheduleEvent,
+    role: Option<serenity::RoleId>,
+    rsvps: &[serenity::UserId],
+    creator: serenity::UserId,
+    state: Announcement,
+) -> (Embed, Vec<serenity::CreateActionRow>) {
+    let ts = event.when.timestamp();
+    let mut description = format!("**When:** <t:{ts}:F> (<t:{ts}:R>)\n");
+    if let Some(recurrence) = &event.recurrence {
+        description.push_str(&format!("**Repeats:** {recurrence}\n"));
+    }
+    description.push_str(&format!("**Host:** <@{creator}>\n"));
+    if let Some(role) = role {
+        description.push_str(&format!("**Reminds:** <@&{role}>\n"));
+    }
+
+    let mut going = rsvps
+        .iter()
+        .take(GROUP_EVENT_RSVPS_SHOWN)
+        .map(|id| format!("<@{id}>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if going.is_empty() {
+        going = "nobody yet".to_string();
+    } else if rsvps.len() > GROUP_EVENT_RSVPS_SHOWN {
+        going.push_str(&format!(
+            " and {} more",
+            rsvps.len() - GROUP_EVENT_RSVPS_SHOWN
+        ));
+    }
+    description.push_str(&format!("\n**Going ({}):** {going}", rsvps.len()));
+
+    let (title, embed_type) = match state {
+        Announcement::Open => (format!("📅 {}", event.name), EmbedType::Neutral),
+        Announcement::Started => (format!("📅 {} — started", event.name), EmbedType::Good),
+        Announcement::Cancelled => (format!("📅 {} — cancelled", event.name), EmbedType::Bad),
+    };
+    let buttons = match state {
+        Announcement::Open => vec![serenity::CreateActionRow::Buttons(vec![
+            serenity::CreateButton::new(Rsvp::Join.custom_id(creator))
+                .label("Join")
+                .style(serenity::ButtonStyle::Success),
+            serenity::CreateButton::new(Rsvp::Leave.custom_id(creator))
+                .label("Leave")
+                .style(serenity::ButtonStyle::Secondary),
+        ])],
+        Announcement::Started | Announcement::Cancelled => Vec::new(),
+    };
+    (
+        create_embed_builder(title, description, embed_type),
+        buttons,
+    )
+}
+
+/// Re-render the posted announcement of a group event. Failures (the
+/// message was deleted, the bot lost access) are logged and ignored.
+pub async fn update_group_announcement(
+    http: &serenity::Http,
+    event: &ScheduleEvent,
+    creator: serenity::UserId,
+    state: Announcement,
+) {
+    let Some(group) = &event.group else {
+        return;
+    };
+    let (embed, buttons) = group_announcement(event, group.role_id, &group.rsvps, creator, state);
+    if let Err(e) = group
+        .channel_id
+        .edit_message(
+            http,
+            group.announcement,
+            serenity::EditMessage::new()
+                .embed(embed)
+                .components(buttons),
+        )
+        .await
+    {
+        log::debug!("Couldn't update announcement of {:?}: {e}", event.name);
+    }
+}
+
+/// DM `message` to everyone on a group event's RSVP list except `skip`
+/// (usually whoever caused the change). Users with DMs closed are skipped.
+pub async fn dm_rsvps(
+    http: &serenity::Http,
+    event: &ScheduleEvent,
+    skip: Option<serenity::UserId>,
+    message: &str,
+) {
+    let Some(group) = &event.group else {
+        return;
+    };
+    for user_id in group.rsvps.iter().filter(|id| Some(**id) != skip) {
+        if let Ok(dm) = user_id.create_dm_channel(http).await {
+            let _ = dm.say(http, message).await;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Reminder DMs
+// ---------------------------------------------------------------------------
+
+/// A private reminder DM: `message` in an embed, with snooze and done
+/// buttons for `occurrence` of `event`.
+pub fn reminder_dm(
+    event: &ScheduleEvent,
+    occurrence: chrono::DateTime<chrono::Utc>,
+    message: &str,
+) -> serenity::CreateMessage {
+    let buttons = ReminderButton::ALL
+        .into_iter()
+        .map(|button| {
+            serenity::CreateButton::new(button.custom_id(event.id, occurrence))
+                .label(button.label())
+                .style(button.style())
+        })
+        .collect();
+    serenity::CreateMessage::new()
+        .embed(create_embed_builder(
+            "Reminder",
+            message,
+            EmbedType::Neutral,
+        ))
+        .components(vec![serenity::CreateActionRow::Buttons(buttons)])
+}
@@ -0,0 +1,83 @@
+//! [`VoiceSink`] over a songbird voice connection.
+//!
+//! Songbird mixes and Opus-encodes whatever it's given; audio arrives here
+//! already at Discord's 48 kHz stereo, so it's handed over as raw float PCM
+//! without further resampling.
+
+use crate::audio::resample::{DISCORD_CHANNELS, DISCORD_SAMPLE_RATE};
+use crate::pawthos::enums::vox_errors::VoxError;
+use crate::voice::VoiceSink;
+use poise::serenity_prelude::{self as serenity, ChannelId, GuildId};
+use songbird::input::{Input, RawAdapter};
+use songbird::tracks::TrackHandle;
+use songbird::{Call, Songbird};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// The bot's voice connection in one guild.
+pub struct Connection {
+    manager: Arc<Songbird>,
+    guild_id: GuildId,
+    call: Arc<Mutex<Call>>,
+}
+
+/// Join `channel_id` in `guild_id`, or move there if already connected
+/// elsewhere in the guild.
+pub async fn connect(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<Connection, VoxError> {
+    // Registered on the client in main.rs.
+    let manager = songbird::get(ctx).await.ok_or(VoxError::VoiceUnsupported)?;
+    let call = manager
+        .join(guild_id, channel_id)
+        .await
+        .map_err(|e| VoxError::Voice(e.to_string()))?;
+    Ok(Connection {
+        manager,
+        guild_id,
+        call,
+    })
+}
+
+/// Stops its track when dropped, which is how a skipped [`VoiceSink::play`]
+/// silences the audio it started.
+struct StopOnDrop(TrackHandle);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        // Fails only if the track already ended.
+        let _ = self.0.stop();
+    }
+}
+
+impl VoiceSink for Connection {
+    async fn play(&mut self, audio: Vec<i16>) -> Result<(), VoxError> {
+        let samples_per_sec = DISCORD_SAMPLE_RATE * u32::from(DISCORD_CHANNELS);
+        let length = Duration::from_secs_f64(audio.len() as f64 / f64::from(samples_per_sec));
+        let bytes: Vec<u8> = audio
+            .iter()
+            .flat_map(|&s| (f32::from(s) / 32768.0).to_le_bytes())
+            .collect();
+        let input: Input = RawAdapter::new(
+            Cursor::new(bytes),
+            DISCORD_SAMPLE_RATE,
+            u32::from(DISCORD_CHANNELS),
+        )
+        .into();
+
+        let _track = StopOnDrop(self.call.lock().await.play_only_input(input));
+        // The audio is all in memory, so its length is known up front.
+        tokio::time::sleep(length).await;
+        Ok(())
+    }
+
+    async fn leave(&mut self) {
+        if let Err(e) = self.manager.remove(self.guild_id).await {
+            log::warn!("Couldn't leave voice in {}: {e}", self.guild_id);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Speaking `/vox` audio live in voice channels.
+//!
+//! Each guild gets at most one player task ([`player::Players`]) with its own
+//! queue: `/vox say live` adds to it, `/vox skip` and `/vox stop` control it,
+//! and it leaves the channel after
+//! [`crate::pawthos::consts::VOX_IDLE_DISCONNECT_SECS`] with nothing to say.
+//!
+//! The player only talks to a [`VoiceSink`], never to Discord directly, so
+//! the queueing can be driven offline by a stand-in sink. The real one, over
+//! a [songbird](https://github.com/serenity-rs/songbird) connection, is built
+//! with the `voice` cargo feature; without it [`connect`] always fails with
+//! [`VoxError::VoiceUnsupported`].
+//!
+//! | Module | Purpose |
+//! |---|---|
+//! | [`player`] | Per-guild playback queue and the task that drives a sink |
+//! | `discord` | (feature `voice`) [`VoiceSink`] over a songbird voice connection |
+
+#[cfg(feature = "voice")]
+mod discord;
+pub mod player;
+
+use crate::pawthos::enums::vox_errors::VoxError;
+#[cfg(not(feature = "voice"))]
+use poise::serenity_prelude::{self as serenity, ChannelId, GuildId};
+use std::future::Future;
+
+#[cfg(feature = "voice")]
+pub use discord::connect;
+
+/// Somewhere to play audio: a voice connection, or a stand-in.
+///
+/// Audio is interleaved 16-bit stereo at
+/// [`crate::audio::resample::DISCORD_SAMPLE_RATE`], as produced by
+/// [`crate::audio::resample::to_discord`].
+pub trait VoiceSink: Send + 'static {
+    /// Play `audio`, resolving once it has finished. Dropping the future
+    /// must stop it — that's how the player skips.
+    fn play(&mut self, audio: Vec<i16>) -> impl Future<Output = Result<(), VoxError>> + Send;
+
+    /// Leave the voice channel. The sink isn't used again afterwards.
+    fn leave(&mut self) -> impl Future<Output = ()> + Send;
+}
+
+/// Stands in for the voice connection when the bot is built without the
+/// `voice` feature. It can't be constructed: [`connect`] always fails.
+#[cfg(not(feature = "voice"))]
+pub enum Connection {}
+
+#[cfg(not(feature = "voice"))]
+impl VoiceSink for Connection {
+    async fn play(&mut self, _: Vec<i16>) -> Result<(), VoxError> {
+        match *self {}
+    }
+
+    async fn leave(&mut self) {
+        match *self {}
+    }
+}
+
+/// Join `channel_id` in `guild_id`.
+///
+/// Always fails with [`VoxError::VoiceUnsupported`]: this build has no
+/// voice support.
+#[cfg(not(feature = "voice"))]
+pub async fn connect(
+    _: &serenity::Context,
+    _: GuildId,
+    _: ChannelId,
+) -> Result<Connection, VoxError> {
+    Err(VoxError::VoiceUnsupported)
+}
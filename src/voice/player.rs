@@ -0,0 +1,436 @@
+//! Per-guild playback queues.
+//!
+//! [`Players`] keeps a command channel to each guild's player task, started
+//! by [`Players::start`] once a [`VoiceSink`] is connected. The task plays
+//! queued audio in order and leaves the channel when told to stop, or after
+//! sitting idle for the timeout it was started with.
+
+use crate::pawthos::enums::vox_errors::VoxError;
+use crate::voice::VoiceSink;
+use poise::serenity_prelude::{ChannelId, GuildId};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// Messages sent to a guild's player task.
+#[derive(Debug)]
+pub enum PlaybackCommand {
+    /// Add audio to the end of the queue.
+    Speak(Vec<i16>),
+
+    /// Stop the current audio and go on to the next.
+    Skip,
+
+    /// Clear the queue and leave the channel.
+    Stop,
+}
+
+/// A running player task, as seen from the commands.
+#[derive(Debug)]
+struct Player {
+    /// Tells this entry from one a later [`Players::start`] put in its place.
+    id: u64,
+    channel_id: ChannelId,
+    commands: UnboundedSender<PlaybackCommand>,
+}
+
+impl Player {
+    /// Queue `audio`, or hand it back if the task has finished.
+    fn queue(&self, audio: Vec<i16>) -> Option<Vec<i16>> {
+        let PlaybackCommand::Speak(audio) =
+            self.commands.send(PlaybackCommand::Speak(audio)).err()?.0
+        else {
+            return None;
+        };
+        Some(audio)
+    }
+}
+
+type Guilds = Arc<Mutex<HashMap<GuildId, Player>>>;
+
+/// The player task of every guild the bot is speaking in.
+///
+/// Commands are only ever sent with the map locked, so a task that takes
+/// its own entry out under the lock knows nothing more can reach it. A task
+/// that has finished (stopped, idle, or its connection dropped) removes its
+/// entry, and one left behind with a closed channel is replaced by the next
+/// [`Players::start`].
+#[derive(Debug, Default)]
+pub struct Players {
+    guilds: Guilds,
+    next_id: AtomicU64,
+}
+
+impl Players {
+    /// Queue `audio` on `guild_id`'s player, which must be in `channel_id`.
+    ///
+    /// Returns the audio back if the guild has no player, so the caller can
+    /// connect and [`Self::start`] one.
+    pub fn speak(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        audio: Vec<i16>,
+    ) -> Result<Option<Vec<i16>>, VoxError> {
+        match lock(&self.guilds).get(&guild_id) {
+            None => Ok(Some(audio)),
+            Some(player) if player.channel_id != channel_id && !player.commands.is_closed() => {
+                Err(VoxError::BusyElsewhere(player.channel_id))
+            }
+            Some(player) => Ok(player.queue(audio)),
+        }
+    }
+
+    /// Start a player for `guild_id` on `sink`, saying `audio` first and
+    /// leaving after `idle` with nothing to say.
+    ///
+    /// If another player started in the meantime, `audio` is queued on that
+    /// one instead and `sink` is dropped without leaving — it shares the
+    /// guild's single voice connection.
+    pub fn start<S: VoiceSink>(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        sink: S,
+        audio: Vec<i16>,
+        idle: Duration,
+    ) {
+        let mut guilds = lock(&self.guilds);
+        let audio = match guilds.get(&guild_id) {
+            Some(player) => match player.queue(audio) {
+                Some(audio) => audio,
+                None => return,
+            },
+            None => audio,
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (commands, receiver) = unbounded_channel();
+        guilds.insert(
+            guild_id,
+            Player {
+                id,
+                channel_id,
+                commands,
+            },
+        );
+        let entry = Entry {
+            guilds: Arc::clone(&self.guilds),
+            guild_id,
+            id,
+        };
+        tokio::spawn(run(sink, audio, receiver, idle, entry));
+    }
+
+    /// Send `command` to `guild_id`'s player.
+    pub fn send(&self, guild_id: GuildId, command: PlaybackCommand) -> Result<(), VoxError> {
+        lock(&self.guilds)
+            .get(&guild_id)
+            .ok_or(VoxError::NotPlaying)?
+            .commands
+            .send(command)
+            .map_err(|_| VoxError::NotPlaying)
+    }
+}
+
+fn lock(guilds: &Guilds) -> MutexGuard<'_, HashMap<GuildId, Player>> {
+    // Every critical section is a map lookup, insert or removal; a panic in
+    // one can't leave the map inconsistent.
+    guilds
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A player task's own entry in [`Players`].
+struct Entry {
+    guilds: Guilds,
+    guild_id: GuildId,
+    id: u64,
+}
+
+impl Entry {
+    /// Remove the entry if it's still this task's, so nothing more is sent
+    /// to it, unless `pending` has a command waiting. Returns that command,
+    /// or `None` once removed.
+    fn retire(&self, pending: &mut UnboundedReceiver<PlaybackCommand>) -> Option<PlaybackCommand> {
+        let mut guilds = lock(&self.guilds);
+        if let Ok(command) = pending.try_recv() {
+            return Some(command);
+        }
+        Self::remove_from(&mut guilds, self.guild_id, self.id);
+        None
+    }
+
+    /// Remove the entry if it's still this task's.
+    fn remove(&self) {
+        Self::remove_from(&mut lock(&self.guilds), self.guild_id, self.id);
+    }
+
+    fn remove_from(guilds: &mut HashMap<GuildId, Player>, guild_id: GuildId, id: u64) {
+        if guilds.get(&guild_id).is_some_and(|p| p.id == id) {
+            guilds.remove(&guild_id);
+        }
+    }
+
+    /// Whether the voice connection is still this task's to leave: no newer
+    /// player has taken over the guild's call.
+    fn owns_connection(&self) -> bool {
+        lock(&self.guilds)
+            .get(&self.guild_id)
+            .is_none_or(|p| p.id == self.id)
+    }
+}
+
+/// Play `first`, then whatever is queued, until told to stop or idle for
+/// `idle`; then leave, unless another player has taken over the call.
+async fn run<S: VoiceSink>(
+    mut sink: S,
+    first: Vec<i16>,
+    mut commands: UnboundedReceiver<PlaybackCommand>,
+    idle: Duration,
+    entry: Entry,
+) {
+    let mut queue = VecDeque::from([first]);
+    'player: loop {
+        let Some(audio) = queue.pop_front() else {
+            let command = match tokio::time::timeout(idle, commands.recv()).await {
+                Ok(command) => command,
+                // Anything sent before the entry is gone still gets said.
+                Err(_) => entry.retire(&mut commands),
+            };
+            match command {
+                Some(PlaybackCommand::Speak(audio)) => queue.push_back(audio),
+                Some(PlaybackCommand::Skip) => {}
+                Some(PlaybackCommand::Stop) | None => break,
+            }
+            continue;
+        };
+
+        let playing = sink.play(audio);
+        tokio::pin!(playing);
+        loop {
+            tokio::select! {
+                result = &mut playing => {
+                    if let Err(e) = result {
+                        log::warn!("Voice playback failed: {e}");
+                    }
+                    break;
+                }
+                command = commands.recv() => match command {
+                    Some(PlaybackCommand::Speak(audio)) => queue.push_back(audio),
+                    Some(PlaybackCommand::Skip) => break,
+                    Some(PlaybackCommand::Stop) | None => break 'player,
+                },
+            }
+        }
+    }
+    entry.remove();
+    if entry.owns_connection() {
+        sink.leave().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const CHANNEL: ChannelId = ChannelId::new(2);
+    const IDLE: Duration = Duration::from_secs(60);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Event {
+        Started(i16),
+        Finished(i16),
+        Left,
+    }
+    use Event::*;
+
+    /// Records what it's asked to do; "plays" one sample a millisecond.
+    #[derive(Debug, Clone, Default)]
+    struct FakeSink {
+        events: Arc<Mutex<Vec<Event>>>,
+    }
+
+    impl FakeSink {
+        fn log(&self, event: Event) {
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn events(&self) -> Vec<Event> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    impl VoiceSink for FakeSink {
+        async fn play(&mut self, audio: Vec<i16>) -> Result<(), VoxError> {
+            self.log(Started(audio[0]));
+            tokio::time::sleep(Duration::from_millis(audio.len() as u64)).await;
+            self.log(Finished(audio[0]));
+            Ok(())
+        }
+
+        async fn leave(&mut self) {
+            self.log(Left);
+        }
+    }
+
+    /// `ms` milliseconds of audio, every sample `id`.
+    fn clip(id: i16, ms: usize) -> Vec<i16> {
+        vec![id; ms]
+    }
+
+    async fn sleep_ms(ms: u64) {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn plays_in_order_then_leaves_when_idle() {
+        let players = Players::default();
+        let sink = FakeSink::default();
+        players.start(GUILD, CHANNEL, sink.clone(), clip(1, 100), IDLE);
+        assert_eq!(players.speak(GUILD, CHANNEL, clip(2, 100)).unwrap(), None);
+        assert_eq!(players.speak(GUILD, CHANNEL, clip(3, 100)).unwrap(), None);
+
+        sleep_ms(350).await;
+        let played = [
+            Started(1),
+            Finished(1),
+            Started(2),
+            Finished(2),
+            Started(3),
+            Finished(3),
+        ];
+        assert_eq!(sink.events(), played);
+
+        tokio::time::sleep(IDLE).await;
+        assert_eq!(sink.events().last(), Some(&Left));
+        assert_eq!(
+            players.speak(GUILD, CHANNEL, clip(4, 100)).unwrap(),
+            Some(clip(4, 100))
+        );
+        assert!(players.send(GUILD, PlaybackCommand::Skip).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn skip_moves_on_to_the_next() {
+        let players = Players::default();
+        let sink = FakeSink::default();
+        players.start(GUILD, CHANNEL, sink.clone(), clip(1, 1_000), IDLE);
+        players.speak(GUILD, CHANNEL, clip(2, 100)).unwrap();
+
+        sleep_ms(10).await;
+        players.send(GUILD, PlaybackCommand::Skip).unwrap();
+        sleep_ms(200).await;
+        assert_eq!(sink.events(), [Started(1), Started(2), Finished(2)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stop_clears_the_queue_and_leaves() {
+        let players = Players::default();
+        let sink = FakeSink::default();
+        players.start(GUILD, CHANNEL, sink.clone(), clip(1, 1_000), IDLE);
+        players.speak(GUILD, CHANNEL, clip(2, 100)).unwrap();
+
+        sleep_ms(10).await;
+        players.send(GUILD, PlaybackCommand::Stop).unwrap();
+        sleep_ms(2_000).await;
+        assert_eq!(sink.events(), [Started(1), Left]);
+        assert!(
+            players
+                .speak(GUILD, CHANNEL, clip(3, 100))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn busy_in_another_channel() {
+        let players = Players::default();
+        players.start(GUILD, CHANNEL, FakeSink::default(), clip(1, 100), IDLE);
+        assert!(matches!(
+            players.speak(GUILD, ChannelId::new(3), clip(2, 100)),
+            Err(VoxError::BusyElsewhere(CHANNEL))
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn audio_sent_as_the_player_idles_out_is_never_lost() {
+        let players = Players::default();
+        let sink = FakeSink::default();
+        players.start(GUILD, CHANNEL, sink.clone(), clip(1, 100), IDLE);
+
+        // Wake at the same instant as the player's idle timeout.
+        tokio::time::sleep(Duration::from_millis(100) + IDLE).await;
+        let handed_back = players.speak(GUILD, CHANNEL, clip(2, 100)).unwrap();
+        sleep_ms(200).await;
+        match handed_back {
+            None => assert_eq!(
+                sink.events(),
+                [Started(1), Finished(1), Started(2), Finished(2)]
+            ),
+            Some(audio) => {
+                assert_eq!(audio, clip(2, 100));
+                assert_eq!(sink.events(), [Started(1), Finished(1), Left]);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn retiring_keeps_the_entry_while_commands_are_queued() {
+        let players = Players::default();
+        let (commands, mut receiver) = unbounded_channel();
+        lock(&players.guilds).insert(
+            GUILD,
+            Player {
+                id: 0,
+                channel_id: CHANNEL,
+                commands,
+            },
+        );
+        let entry = Entry {
+            guilds: Arc::clone(&players.guilds),
+            guild_id: GUILD,
+            id: 0,
+        };
+
+        // Queued just as the idle timeout fired: still handed to the task.
+        players.speak(GUILD, CHANNEL, clip(1, 100)).unwrap();
+        assert!(matches!(
+            entry.retire(&mut receiver),
+            Some(PlaybackCommand::Speak(audio)) if audio == clip(1, 100)
+        ));
+        assert!(lock(&players.guilds).contains_key(&GUILD));
+
+        assert!(entry.retire(&mut receiver).is_none());
+        assert!(!lock(&players.guilds).contains_key(&GUILD));
+        assert!(entry.owns_connection());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_replaced_player_does_not_leave() {
+        let players = Players::default();
+        let sink = FakeSink::default();
+        let (commands, _receiver) = unbounded_channel();
+        let entry = Entry {
+            guilds: Arc::clone(&players.guilds),
+            guild_id: GUILD,
+            id: 0,
+        };
+        lock(&players.guilds).insert(
+            GUILD,
+            Player {
+                id: 1,
+                channel_id: CHANNEL,
+                commands,
+            },
+        );
+
+        // Plays, idles out, then finds a newer player on the call.
+        let (_sender, receiver) = unbounded_channel();
+        run(sink.clone(), clip(1, 100), receiver, IDLE, entry).await;
+        assert_eq!(sink.events(), [Started(1), Finished(1)]);
+        assert!(lock(&players.guilds).contains_key(&GUILD));
+    }
+}